@@ -7,7 +7,7 @@ struct Descriptors {
     image: sierra::Image,
 
     #[sierra(sampler, fragment)]
-    sampler: sierra::Sampler,
+    linear_sampler: sierra::Sampler,
 
     #[sierra(image(sampled), fragment)]
     albedo: sierra::ImageView,
@@ -58,27 +58,20 @@ fn main() -> eyre::Result<()> {
 
     let graphics = sierra::Graphics::get_or_init()?;
 
-    let physical = graphics
-        .devices()?
-        .into_iter()
-        .max_by_key(|d| d.info().kind)
-        .ok_or_else(|| eyre::eyre!("Failed to find physical device"))?;
+    let selection = graphics.select_device(
+        sierra::DeviceRequirements::new(sierra::SingleQueueQuery::GRAPHICS)
+            .require(sierra::Feature::SurfacePresentation)
+            .prefer(sierra::Feature::DynamicRendering),
+    )?;
 
-    let dynamic_rendering = physical
-        .info()
-        .features
+    let dynamic_rendering = selection
+        .enabled_optional
         .contains(&sierra::Feature::DynamicRendering);
 
-    let mut features = vec![sierra::Feature::SurfacePresentation];
-    if dynamic_rendering {
-        features.push(sierra::Feature::DynamicRendering);
-    }
-
-    let (device, mut queue) =
-        physical.create_device(&features[..], sierra::SingleQueueQuery::GRAPHICS)?;
+    let (device, mut queue) = selection.create_device()?;
 
-    let shader_module = device.create_shader_module(sierra::ShaderModuleInfo {
-        code: br#"
+    let shader_module = device.create_shader_module(sierra::ShaderModuleInfo::wgsl(
+        br#"
 @vertex
 fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> @builtin(position) vec4<f32> {
     let x = f32(i32(in_vertex_index) - 1);
@@ -91,15 +84,14 @@ fn fs_main() -> @location(0) vec4<f32> {
     return vec4<f32>(1.0, 0.0, 0.0, 1.0);
 }
         "#
-        .to_vec()
-        .into_boxed_slice(),
-        language: sierra::ShaderLanguage::WGSL,
-    })?;
+        .to_vec(),
+    ))?;
 
     let mut surface = device.create_surface(&window, &window)?;
     surface.configure(
         sierra::ImageUsage::COLOR_ATTACHMENT,
         sierra::Format::BGRA8Srgb,
+        sierra::ColorSpace::SrgbNonlinear,
         sierra::PresentMode::Fifo,
     )?;
 
@@ -213,9 +205,9 @@ fn fs_main() -> @location(0) vec4<f32> {
 
                 queue
                     .submit(
-                        &mut [(sierra::PipelineStages::COLOR_ATTACHMENT_OUTPUT, wait)],
+                        &mut [(sierra::PipelineStages::COLOR_ATTACHMENT_OUTPUT, wait.into())],
                         Some(encoder.finish()),
-                        &mut [signal],
+                        &mut [signal.into()],
                         Some(fence),
                         &scope,
                     )
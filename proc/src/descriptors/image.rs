@@ -1,13 +1,44 @@
 use proc_macro2::TokenStream;
 use syn::spanned::Spanned;
 
-use crate::kw;
+use crate::{format::glsl_image_format, kw};
 
 impl Image {
     #[inline]
     pub fn validate(&self, _item_struct: &syn::ItemStruct) -> syn::Result<()> {
+        if let Some(view) = &self.view {
+            view.dims()?;
+        }
+
+        if !matches!(self.kind, Some(Kind::Storage(_))) {
+            if let Some(format) = &self.format {
+                return Err(syn::Error::new(
+                    format.kw.span(),
+                    "Format can be specified only for storage images",
+                ));
+            }
+            if let Some(access) = &self.access {
+                return Err(syn::Error::new(
+                    access.span(),
+                    "Access can be specified only for storage images",
+                ));
+            }
+        }
+
+        if let Some(format) = &self.format {
+            format.glsl()?;
+        }
         Ok(())
     }
+
+    /// Returns GLSL and WGSL type suffixes for image dimensionality.
+    /// Images are declared as 2D unless `view` argument is specified.
+    pub fn dims(&self) -> (&'static str, Option<&'static str>) {
+        match &self.view {
+            None => ("2D", Some("2d")),
+            Some(view) => view.dims().unwrap(),
+        }
+    }
 }
 
 proc_easy::easy_argument_group! {
@@ -54,11 +85,77 @@ impl Layout {
     }
 }
 
+proc_easy::easy_argument_value! {
+    #[derive(Clone)]
+    pub struct Format {
+        pub kw: kw::format,
+        pub value: syn::Ident,
+    }
+}
+
+impl Format {
+    /// Returns GLSL layout qualifier for the storage image format.
+    pub fn glsl(&self) -> syn::Result<&'static str> {
+        glsl_image_format(&self.value.to_string()).ok_or_else(|| {
+            syn::Error::new(
+                self.value.span(),
+                "Format cannot be used with storage images in shaders",
+            )
+        })
+    }
+}
+
+proc_easy::easy_argument_group! {
+    #[derive(Clone, Copy)]
+    pub enum Access {
+        ReadOnly(kw::readonly),
+        WriteOnly(kw::writeonly),
+    }
+}
+
+impl Access {
+    fn span(&self) -> proc_macro2::Span {
+        match self {
+            Access::ReadOnly(kw) => kw.span(),
+            Access::WriteOnly(kw) => kw.span(),
+        }
+    }
+}
+
+proc_easy::easy_argument_value! {
+    #[derive(Clone)]
+    pub struct View {
+        pub kw: kw::view,
+        pub kind: syn::Ident,
+    }
+}
+
+impl View {
+    fn dims(&self) -> syn::Result<(&'static str, Option<&'static str>)> {
+        match &*self.kind.to_string() {
+            "D1" => Ok(("1D", Some("1d"))),
+            "D2" => Ok(("2D", Some("2d"))),
+            "D3" => Ok(("3D", Some("3d"))),
+            "Cube" => Ok(("Cube", Some("cube"))),
+            "D1Array" => Ok(("1DArray", None)),
+            "D2Array" => Ok(("2DArray", Some("2d_array"))),
+            "CubeArray" => Ok(("CubeArray", Some("cube_array"))),
+            _ => Err(syn::Error::new(
+                self.kind.span(),
+                "Expected one of `D1`, `D2`, `D3`, `Cube`, `D1Array`, `D2Array` or `CubeArray`",
+            )),
+        }
+    }
+}
+
 proc_easy::easy_argument_tuple! {
     #[derive(Clone)]
     pub struct Image {
         pub kw: kw::image,
         pub kind: Option<Kind>,
         pub layout: Option<Layout>,
+        pub view: Option<View>,
+        pub format: Option<Format>,
+        pub access: Option<Access>,
     }
 }
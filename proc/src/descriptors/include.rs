@@ -0,0 +1,255 @@
+use std::convert::TryFrom;
+
+use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+
+use super::{
+    buffer, image,
+    parse::{DescriptorType, Input},
+};
+
+use crate::{
+    format::glsl_image_format,
+    layout::StructLayout,
+    shader::{
+        check_identifier, glsl_deps, glsl_layout_extension, glsl_members, member_name, snake_case,
+        stage_visible, wgsl_deps, wgsl_members, WgslLayout,
+    },
+    shader_stage::combined_shader_stage_flags,
+};
+
+pub(super) fn generate(input: &Input) -> Result<TokenStream, syn::Error> {
+    let mut glsl = TokenStream::new();
+    let mut wgsl = TokenStream::new();
+
+    for (binding, descriptor) in input.descriptors.iter().enumerate() {
+        let binding = u32::try_from(binding).expect("Too many descriptors");
+        let visible = stage_visible(descriptor.stages.bits());
+        let name = member_name(&descriptor.member);
+        check_identifier(&name, descriptor.field.span())?;
+        let ty = &descriptor.field.ty;
+        let descriptor_kind = descriptor.desc_ty.descriptor_kind()?;
+        let count =
+            quote::quote!(<#ty as ::sierra::DescriptorBindingArray<#descriptor_kind>>::COUNT);
+
+        if let Some(glsl_ty) = glsl_descriptor_type(&descriptor.desc_ty) {
+            let (layout, memory) = glsl_descriptor_qualifiers(&descriptor.desc_ty)?;
+            glsl.extend(quote::quote!(
+                if #visible {
+                    let count = #count;
+                    let suffix = if count == 1 {
+                        ::std::string::String::new()
+                    } else {
+                        ::std::format!("[{}]", count)
+                    };
+                    decls += &::std::format!(
+                        "layout({}set = {}, binding = {}) {}uniform {} {}{};\n",
+                        #layout, set, #binding, #memory, #glsl_ty, #name, suffix,
+                    );
+                }
+            ));
+        }
+
+        if let Some(wgsl_ty) = wgsl_descriptor_type(&descriptor.desc_ty) {
+            wgsl.extend(quote::quote!(
+                if #visible {
+                    let count = #count;
+                    let ty = if count == 1 {
+                        ::std::string::String::from(#wgsl_ty)
+                    } else {
+                        ::std::format!("binding_array<{}, {}>", #wgsl_ty, count)
+                    };
+                    decls += &::std::format!(
+                        "@group({}) @binding({}) var {}: {};\n",
+                        group, #binding, #name, ty,
+                    );
+                }
+            ));
+        }
+    }
+
+    let ident = &input.item_struct.ident;
+
+    if !input.uniforms.is_empty() {
+        let binding = u32::try_from(input.descriptors.len()).expect("Too many descriptors");
+        let visible = stage_visible(combined_shader_stage_flags(
+            input
+                .uniforms
+                .iter()
+                .flat_map(|u| u.stages.flags.iter().copied()),
+        ));
+
+        let layout = StructLayout::common_glsl_name(
+            input
+                .uniforms
+                .iter()
+                .map(|u| u.uniform.layout.unwrap_or_default()),
+        );
+
+        let block_name = format!("{}Uniforms", ident);
+        let var_name = snake_case(&block_name);
+        for uniform in &input.uniforms {
+            check_identifier(&member_name(&uniform.member), uniform.field.span())?;
+        }
+        let types = input.uniforms.iter().map(|u| &u.field.ty);
+        let members = || {
+            input
+                .uniforms
+                .iter()
+                .map(|u| (member_name(&u.member), &u.field.ty))
+        };
+
+        let deps = glsl_deps(quote::quote!(ctx), types.clone());
+        let extension = glsl_layout_extension(quote::quote!(ctx), layout);
+        let members_code = glsl_members(members());
+        glsl.extend(quote::quote!(
+            if #visible {
+                #deps
+                #extension
+                decls += &::std::format!(
+                    "layout({}, set = {}, binding = {}) uniform {} {{\n{}}};\n",
+                    #layout, set, #binding, #block_name, #members_code,
+                );
+            }
+        ));
+
+        // Uniform buffers in WGSL always follow uniform layout rules.
+        let supported = match layout {
+            "std140" => TokenStream::new(),
+            _ => quote::quote!(
+                ::std::panic!(
+                    "`{}` uses {} layout, uniform buffers in WGSL support only std140",
+                    #block_name, #layout,
+                );
+            ),
+        };
+        let deps = wgsl_deps(quote::quote!(ctx), types, WgslLayout::Uniform);
+        let members_code = wgsl_members(members(), WgslLayout::Uniform);
+        wgsl.extend(quote::quote!(
+            if #visible {
+                #supported
+                #deps
+                decls += &::std::format!(
+                    "struct {} {{\n{}}}\n@group({}) @binding({}) var<uniform> {}: {};\n",
+                    #block_name, #members_code, group, #binding, #var_name, #block_name,
+                );
+            }
+        ));
+    }
+
+    Ok(quote::quote!(
+        impl ::sierra::DescriptorsInclude for #ident {
+            #[allow(unused_mut, unused_variables)]
+            fn glsl_set(set: u32, stage: ::sierra::ShaderStage, ctx: &mut ::sierra::GlslTypeContext) -> ::std::string::String {
+                let mut decls = ::std::string::String::new();
+                #glsl
+                decls
+            }
+
+            #[allow(unused_mut, unused_variables, unreachable_code)]
+            fn wgsl_group(group: u32, stage: ::sierra::ShaderStage, ctx: &mut ::sierra::WgslTypeContext) -> ::std::string::String {
+                let mut decls = ::std::string::String::new();
+                #wgsl
+                decls
+            }
+        }
+
+        impl ::sierra::ShaderInclude for #ident {
+            fn glsl(stage: ::sierra::ShaderStage) -> ::std::string::String {
+                let mut ctx = ::sierra::GlslTypeContext::new();
+                let decls = <Self as ::sierra::DescriptorsInclude>::glsl_set(0, stage, &mut ctx);
+                ctx.code() + &decls
+            }
+
+            fn wgsl(stage: ::sierra::ShaderStage) -> ::std::string::String {
+                let mut ctx = ::sierra::WgslTypeContext::new();
+                let decls = <Self as ::sierra::DescriptorsInclude>::wgsl_group(0, stage, &mut ctx);
+                ctx.code() + &decls
+            }
+        }
+    ))
+}
+
+/// Returns GLSL type of the descriptor.
+/// Buffers without texel format have no statically known content
+/// and are left for shader to declare.
+fn glsl_descriptor_type(desc_ty: &DescriptorType) -> Option<String> {
+    match desc_ty {
+        DescriptorType::Sampler(_) => Some("sampler".to_owned()),
+        DescriptorType::Image(
+            image @ image::Image {
+                kind: None | Some(image::Kind::Sampled(_)),
+                ..
+            },
+        ) => Some(format!("texture{}", image.dims().0)),
+        DescriptorType::Image(image @ image::Image { .. }) => {
+            Some(format!("image{}", image.dims().0))
+        }
+        DescriptorType::Buffer(buffer::Buffer { texel: None, .. }) => None,
+        DescriptorType::Buffer(buffer::Buffer {
+            kind: None | Some(buffer::Kind::Uniform(_)),
+            ..
+        }) => Some("textureBuffer".to_owned()),
+        DescriptorType::Buffer(buffer::Buffer { .. }) => Some("imageBuffer".to_owned()),
+        DescriptorType::AccelerationStructure(_) => Some("accelerationStructureEXT".to_owned()),
+    }
+}
+
+/// Returns GLSL layout and memory qualifiers of the descriptor,
+/// each followed by separator if not empty.
+/// Only storage images and texel buffers have them.
+fn glsl_descriptor_qualifiers(desc_ty: &DescriptorType) -> syn::Result<(String, &'static str)> {
+    match desc_ty {
+        DescriptorType::Image(image::Image {
+            kind: Some(image::Kind::Storage(_)),
+            format,
+            access,
+            ..
+        }) => {
+            let layout = match format {
+                None => String::new(),
+                Some(format) => format!("{}, ", format.glsl()?),
+            };
+            let memory = match access {
+                None => "",
+                Some(image::Access::ReadOnly(_)) => "readonly ",
+                Some(image::Access::WriteOnly(_)) => "writeonly ",
+            };
+            Ok((layout, memory))
+        }
+        DescriptorType::Buffer(buffer::Buffer {
+            kind: Some(buffer::Kind::Storage(_)),
+            texel:
+                Some(buffer::Texel {
+                    format: buffer::FormatValue::Const(format),
+                    ..
+                }),
+            ..
+        }) => {
+            let layout = match glsl_image_format(&format.to_string()) {
+                None => String::new(),
+                Some(qualifier) => format!("{}, ", qualifier),
+            };
+            Ok((layout, ""))
+        }
+        _ => Ok((String::new(), "")),
+    }
+}
+
+/// Returns WGSL type of the descriptor.
+/// Storage images, texel buffers and acceleration structures
+/// require details not known here and are left for shader to declare.
+fn wgsl_descriptor_type(desc_ty: &DescriptorType) -> Option<String> {
+    match desc_ty {
+        DescriptorType::Sampler(_) => Some("sampler".to_owned()),
+        DescriptorType::Image(
+            image @ image::Image {
+                kind: None | Some(image::Kind::Sampled(_)),
+                ..
+            },
+        ) => image.dims().1.map(|dims| format!("texture_{}<f32>", dims)),
+        DescriptorType::Image(_) => None,
+        DescriptorType::Buffer(_) => None,
+        DescriptorType::AccelerationStructure(_) => None,
+    }
+}
@@ -61,9 +61,13 @@ fn generate_uniform_struct(input: &Input) -> TokenStream {
             .iter()
             .fold(quote::quote!(15), |mut tokens, u| {
                 let field_type = &u.field.ty;
+                let sierra_layout = match u.uniform.layout {
+                    Some(layout) => layout.sierra_type(),
+                    None => StructLayout::default_sierra_type(),
+                };
 
                 tokens.extend(
-                    quote::quote! { | (<#field_type as ::sierra::ShaderRepr<::sierra::Std140>>::ALIGN_MASK) },
+                    quote::quote! { | (<#field_type as ::sierra::ShaderRepr<#sierra_layout>>::ALIGN_MASK) },
                 );
                 tokens
             });
@@ -1,6 +1,7 @@
 mod acceleration_structure;
 mod buffer;
 mod image;
+mod include;
 mod input;
 mod instance;
 mod layout;
@@ -20,7 +21,7 @@ fn try_descriptors(item: proc_macro::TokenStream) -> Result<proc_macro2::TokenSt
     let tokens = std::iter::once(input::generate(&input))
         .chain(Some(instance::generate(&input)?))
         .chain(Some(layout::generate(&input)?))
-        // .chain(Some(generate_glsl_shader_input(&input)))
+        .chain(Some(include::generate(&input)?))
         .collect::<proc_macro2::TokenStream>();
     Ok(tokens)
 }
@@ -72,6 +72,7 @@ impl DescriptorType {
                 kw,
                 kind: None | Some(image::Kind::Sampled(_)),
                 layout,
+                ..
             }) => {
                 let layout = image::Layout::to_tokens_opt(layout.as_ref(), || {
                     quote::quote!(::sierra::ShaderReadOnlyOptimal)
@@ -84,6 +85,7 @@ impl DescriptorType {
                 kw,
                 kind: Some(image::Kind::Storage(_)),
                 layout,
+                ..
             }) => {
                 let layout = image::Layout::to_tokens_opt(layout.as_ref(), || {
                     quote::quote!(::sierra::General)
@@ -35,13 +35,13 @@ pub fn parse_format(s: &str) -> Result<TokenStream, syn::Error> {
         "rgb8uint" | "RGB8Uint" => "::sierra::FormatDescription<::sierra::RGB, ::sierra::ConstBits<8>, ::sierra::Uint>",
         "rgb8sint" | "RGB8Sint" => "::sierra::FormatDescription<::sierra::RGB, ::sierra::ConstBits<8>, ::sierra::Sint>",
         "rgb8srgb" | "RGB8Srgb" => "::sierra::FormatDescription<::sierra::RGB, ::sierra::ConstBits<8>, ::sierra::Srgb>",
-        "bgr8unorm" | "BGR8Unorm" => "::sierra::FormatDescription<::sierra::RBG, ::sierra::ConstBits<8>, ::sierra::Unorm>",
-        "bgr8snorm" | "BGR8Snorm" => "::sierra::FormatDescription<::sierra::RBG, ::sierra::ConstBits<8>, ::sierra::Snorm>",
-        "bgr8uscaled" | "BGR8Uscaled" => "::sierra::FormatDescription<::sierra::RBG, ::sierra::ConstBits<8>, ::sierra::Uscaled>",
-        "bgr8sscaled" | "BGR8Sscaled" => "::sierra::FormatDescription<::sierra::RBG, ::sierra::ConstBits<8>, ::sierra::Sscaled>",
-        "bgr8uint" | "BGR8Uint" => "::sierra::FormatDescription<::sierra::RBG, ::sierra::ConstBits<8>, ::sierra::Uint>",
-        "bgr8sint" | "BGR8Sint" => "::sierra::FormatDescription<::sierra::RBG, ::sierra::ConstBits<8>, ::sierra::Sint>",
-        "bgr8srgb" | "BGR8Srgb" => "::sierra::FormatDescription<::sierra::RBG, ::sierra::ConstBits<8>, ::sierra::Srgb>",
+        "bgr8unorm" | "BGR8Unorm" => "::sierra::FormatDescription<::sierra::BGR, ::sierra::ConstBits<8>, ::sierra::Unorm>",
+        "bgr8snorm" | "BGR8Snorm" => "::sierra::FormatDescription<::sierra::BGR, ::sierra::ConstBits<8>, ::sierra::Snorm>",
+        "bgr8uscaled" | "BGR8Uscaled" => "::sierra::FormatDescription<::sierra::BGR, ::sierra::ConstBits<8>, ::sierra::Uscaled>",
+        "bgr8sscaled" | "BGR8Sscaled" => "::sierra::FormatDescription<::sierra::BGR, ::sierra::ConstBits<8>, ::sierra::Sscaled>",
+        "bgr8uint" | "BGR8Uint" => "::sierra::FormatDescription<::sierra::BGR, ::sierra::ConstBits<8>, ::sierra::Uint>",
+        "bgr8sint" | "BGR8Sint" => "::sierra::FormatDescription<::sierra::BGR, ::sierra::ConstBits<8>, ::sierra::Sint>",
+        "bgr8srgb" | "BGR8Srgb" => "::sierra::FormatDescription<::sierra::BGR, ::sierra::ConstBits<8>, ::sierra::Srgb>",
         "rgba8unorm" | "RGBA8Unorm" => "::sierra::FormatDescription<::sierra::RGBA, ::sierra::ConstBits<8>, ::sierra::Unorm>",
         "rgba8snorm" | "RGBA8Snorm" => "::sierra::FormatDescription<::sierra::RGBA, ::sierra::ConstBits<8>, ::sierra::Snorm>",
         "rgba8uscaled" | "RGBA8Uscaled" => "::sierra::FormatDescription<::sierra::RGBA, ::sierra::ConstBits<8>, ::sierra::Uscaled>",
@@ -114,8 +114,131 @@ pub fn parse_format(s: &str) -> Result<TokenStream, syn::Error> {
         "d16unorms8uint" | "D16UnormS8Uint" => "::sierra::FormatDescription<::sierra::DS, ::sierra::ConstBits<16>, ::sierra::Unorm>",
         "d24unorms8uint" | "D24UnormS8Uint" => "::sierra::FormatDescription<::sierra::DS, ::sierra::ConstBits<24>, ::sierra::Unorm>",
         "d32sfloats8uint" | "D32SfloatS8Uint" => "::sierra::FormatDescription<::sierra::DS, ::sierra::ConstBits<32>, ::sierra::Sfloat>",
+        "r4g4b4a4unorm" | "R4G4B4A4Unorm" => "::sierra::FormatDescription<::sierra::R4G4B4A4, ::sierra::ConstBits<16>, ::sierra::Unorm>",
+        "b4g4r4a4unorm" | "B4G4R4A4Unorm" => "::sierra::FormatDescription<::sierra::B4G4R4A4, ::sierra::ConstBits<16>, ::sierra::Unorm>",
+        "r5g6b5unorm" | "R5G6B5Unorm" => "::sierra::FormatDescription<::sierra::R5G6B5, ::sierra::ConstBits<16>, ::sierra::Unorm>",
+        "b5g6r5unorm" | "B5G6R5Unorm" => "::sierra::FormatDescription<::sierra::B5G6R5, ::sierra::ConstBits<16>, ::sierra::Unorm>",
+        "r5g5b5a1unorm" | "R5G5B5A1Unorm" => "::sierra::FormatDescription<::sierra::R5G5B5A1, ::sierra::ConstBits<16>, ::sierra::Unorm>",
+        "b5g5r5a1unorm" | "B5G5R5A1Unorm" => "::sierra::FormatDescription<::sierra::B5G5R5A1, ::sierra::ConstBits<16>, ::sierra::Unorm>",
+        "a1r5g5b5unorm" | "A1R5G5B5Unorm" => "::sierra::FormatDescription<::sierra::A1R5G5B5, ::sierra::ConstBits<16>, ::sierra::Unorm>",
+        "a2r10g10b10unorm" | "A2R10G10B10Unorm" => "::sierra::FormatDescription<::sierra::A2R10G10B10, ::sierra::ConstBits<32>, ::sierra::Unorm>",
+        "a2r10g10b10snorm" | "A2R10G10B10Snorm" => "::sierra::FormatDescription<::sierra::A2R10G10B10, ::sierra::ConstBits<32>, ::sierra::Snorm>",
+        "a2r10g10b10uscaled" | "A2R10G10B10Uscaled" => "::sierra::FormatDescription<::sierra::A2R10G10B10, ::sierra::ConstBits<32>, ::sierra::Uscaled>",
+        "a2r10g10b10sscaled" | "A2R10G10B10Sscaled" => "::sierra::FormatDescription<::sierra::A2R10G10B10, ::sierra::ConstBits<32>, ::sierra::Sscaled>",
+        "a2r10g10b10uint" | "A2R10G10B10Uint" => "::sierra::FormatDescription<::sierra::A2R10G10B10, ::sierra::ConstBits<32>, ::sierra::Uint>",
+        "a2r10g10b10sint" | "A2R10G10B10Sint" => "::sierra::FormatDescription<::sierra::A2R10G10B10, ::sierra::ConstBits<32>, ::sierra::Sint>",
+        "a2b10g10r10unorm" | "A2B10G10R10Unorm" => "::sierra::FormatDescription<::sierra::A2B10G10R10, ::sierra::ConstBits<32>, ::sierra::Unorm>",
+        "a2b10g10r10snorm" | "A2B10G10R10Snorm" => "::sierra::FormatDescription<::sierra::A2B10G10R10, ::sierra::ConstBits<32>, ::sierra::Snorm>",
+        "a2b10g10r10uscaled" | "A2B10G10R10Uscaled" => "::sierra::FormatDescription<::sierra::A2B10G10R10, ::sierra::ConstBits<32>, ::sierra::Uscaled>",
+        "a2b10g10r10sscaled" | "A2B10G10R10Sscaled" => "::sierra::FormatDescription<::sierra::A2B10G10R10, ::sierra::ConstBits<32>, ::sierra::Sscaled>",
+        "a2b10g10r10uint" | "A2B10G10R10Uint" => "::sierra::FormatDescription<::sierra::A2B10G10R10, ::sierra::ConstBits<32>, ::sierra::Uint>",
+        "a2b10g10r10sint" | "A2B10G10R10Sint" => "::sierra::FormatDescription<::sierra::A2B10G10R10, ::sierra::ConstBits<32>, ::sierra::Sint>",
+        "b10g11r11ufloat" | "B10G11R11Ufloat" => "::sierra::FormatDescription<::sierra::B10G11R11, ::sierra::ConstBits<32>, ::sierra::Ufloat>",
+        "e5b9g9r9ufloat" | "E5B9G9R9Ufloat" => "::sierra::FormatDescription<::sierra::E5B9G9R9, ::sierra::ConstBits<32>, ::sierra::Ufloat>",
+        "bc1rgbunorm" | "BC1RGBUnorm" => "::sierra::FormatDescription<::sierra::BC1RGB, ::sierra::ConstBits<64>, ::sierra::Unorm>",
+        "bc1rgbsrgb" | "BC1RGBSrgb" => "::sierra::FormatDescription<::sierra::BC1RGB, ::sierra::ConstBits<64>, ::sierra::Srgb>",
+        "bc1rgbaunorm" | "BC1RGBAUnorm" => "::sierra::FormatDescription<::sierra::BC1RGBA, ::sierra::ConstBits<64>, ::sierra::Unorm>",
+        "bc1rgbasrgb" | "BC1RGBASrgb" => "::sierra::FormatDescription<::sierra::BC1RGBA, ::sierra::ConstBits<64>, ::sierra::Srgb>",
+        "bc2unorm" | "BC2Unorm" => "::sierra::FormatDescription<::sierra::BC2, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "bc2srgb" | "BC2Srgb" => "::sierra::FormatDescription<::sierra::BC2, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "bc3unorm" | "BC3Unorm" => "::sierra::FormatDescription<::sierra::BC3, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "bc3srgb" | "BC3Srgb" => "::sierra::FormatDescription<::sierra::BC3, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "bc4unorm" | "BC4Unorm" => "::sierra::FormatDescription<::sierra::BC4, ::sierra::ConstBits<64>, ::sierra::Unorm>",
+        "bc4snorm" | "BC4Snorm" => "::sierra::FormatDescription<::sierra::BC4, ::sierra::ConstBits<64>, ::sierra::Snorm>",
+        "bc5unorm" | "BC5Unorm" => "::sierra::FormatDescription<::sierra::BC5, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "bc5snorm" | "BC5Snorm" => "::sierra::FormatDescription<::sierra::BC5, ::sierra::ConstBits<128>, ::sierra::Snorm>",
+        "bc6hufloat" | "BC6HUfloat" => "::sierra::FormatDescription<::sierra::BC6H, ::sierra::ConstBits<128>, ::sierra::Ufloat>",
+        "bc6hsfloat" | "BC6HSfloat" => "::sierra::FormatDescription<::sierra::BC6H, ::sierra::ConstBits<128>, ::sierra::Sfloat>",
+        "bc7unorm" | "BC7Unorm" => "::sierra::FormatDescription<::sierra::BC7, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "bc7srgb" | "BC7Srgb" => "::sierra::FormatDescription<::sierra::BC7, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "etc2rgb8unorm" | "ETC2RGB8Unorm" => "::sierra::FormatDescription<::sierra::ETC2RGB, ::sierra::ConstBits<64>, ::sierra::Unorm>",
+        "etc2rgb8srgb" | "ETC2RGB8Srgb" => "::sierra::FormatDescription<::sierra::ETC2RGB, ::sierra::ConstBits<64>, ::sierra::Srgb>",
+        "etc2rgb8a1unorm" | "ETC2RGB8A1Unorm" => "::sierra::FormatDescription<::sierra::ETC2RGBA1, ::sierra::ConstBits<64>, ::sierra::Unorm>",
+        "etc2rgb8a1srgb" | "ETC2RGB8A1Srgb" => "::sierra::FormatDescription<::sierra::ETC2RGBA1, ::sierra::ConstBits<64>, ::sierra::Srgb>",
+        "etc2rgba8unorm" | "ETC2RGBA8Unorm" => "::sierra::FormatDescription<::sierra::ETC2RGBA, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "etc2rgba8srgb" | "ETC2RGBA8Srgb" => "::sierra::FormatDescription<::sierra::ETC2RGBA, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "eacr11unorm" | "EACR11Unorm" => "::sierra::FormatDescription<::sierra::EACR, ::sierra::ConstBits<64>, ::sierra::Unorm>",
+        "eacr11snorm" | "EACR11Snorm" => "::sierra::FormatDescription<::sierra::EACR, ::sierra::ConstBits<64>, ::sierra::Snorm>",
+        "eacrg11unorm" | "EACRG11Unorm" => "::sierra::FormatDescription<::sierra::EACRG, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "eacrg11snorm" | "EACRG11Snorm" => "::sierra::FormatDescription<::sierra::EACRG, ::sierra::ConstBits<128>, ::sierra::Snorm>",
+        "astc4x4unorm" | "ASTC4x4Unorm" => "::sierra::FormatDescription<::sierra::ASTC4x4, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc4x4srgb" | "ASTC4x4Srgb" => "::sierra::FormatDescription<::sierra::ASTC4x4, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "astc5x4unorm" | "ASTC5x4Unorm" => "::sierra::FormatDescription<::sierra::ASTC5x4, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc5x4srgb" | "ASTC5x4Srgb" => "::sierra::FormatDescription<::sierra::ASTC5x4, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "astc5x5unorm" | "ASTC5x5Unorm" => "::sierra::FormatDescription<::sierra::ASTC5x5, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc5x5srgb" | "ASTC5x5Srgb" => "::sierra::FormatDescription<::sierra::ASTC5x5, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "astc6x5unorm" | "ASTC6x5Unorm" => "::sierra::FormatDescription<::sierra::ASTC6x5, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc6x5srgb" | "ASTC6x5Srgb" => "::sierra::FormatDescription<::sierra::ASTC6x5, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "astc6x6unorm" | "ASTC6x6Unorm" => "::sierra::FormatDescription<::sierra::ASTC6x6, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc6x6srgb" | "ASTC6x6Srgb" => "::sierra::FormatDescription<::sierra::ASTC6x6, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "astc8x5unorm" | "ASTC8x5Unorm" => "::sierra::FormatDescription<::sierra::ASTC8x5, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc8x5srgb" | "ASTC8x5Srgb" => "::sierra::FormatDescription<::sierra::ASTC8x5, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "astc8x6unorm" | "ASTC8x6Unorm" => "::sierra::FormatDescription<::sierra::ASTC8x6, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc8x6srgb" | "ASTC8x6Srgb" => "::sierra::FormatDescription<::sierra::ASTC8x6, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "astc8x8unorm" | "ASTC8x8Unorm" => "::sierra::FormatDescription<::sierra::ASTC8x8, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc8x8srgb" | "ASTC8x8Srgb" => "::sierra::FormatDescription<::sierra::ASTC8x8, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "astc10x5unorm" | "ASTC10x5Unorm" => "::sierra::FormatDescription<::sierra::ASTC10x5, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc10x5srgb" | "ASTC10x5Srgb" => "::sierra::FormatDescription<::sierra::ASTC10x5, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "astc10x6unorm" | "ASTC10x6Unorm" => "::sierra::FormatDescription<::sierra::ASTC10x6, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc10x6srgb" | "ASTC10x6Srgb" => "::sierra::FormatDescription<::sierra::ASTC10x6, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "astc10x8unorm" | "ASTC10x8Unorm" => "::sierra::FormatDescription<::sierra::ASTC10x8, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc10x8srgb" | "ASTC10x8Srgb" => "::sierra::FormatDescription<::sierra::ASTC10x8, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "astc10x10unorm" | "ASTC10x10Unorm" => "::sierra::FormatDescription<::sierra::ASTC10x10, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc10x10srgb" | "ASTC10x10Srgb" => "::sierra::FormatDescription<::sierra::ASTC10x10, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "astc12x10unorm" | "ASTC12x10Unorm" => "::sierra::FormatDescription<::sierra::ASTC12x10, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc12x10srgb" | "ASTC12x10Srgb" => "::sierra::FormatDescription<::sierra::ASTC12x10, ::sierra::ConstBits<128>, ::sierra::Srgb>",
+        "astc12x12unorm" | "ASTC12x12Unorm" => "::sierra::FormatDescription<::sierra::ASTC12x12, ::sierra::ConstBits<128>, ::sierra::Unorm>",
+        "astc12x12srgb" | "ASTC12x12Srgb" => "::sierra::FormatDescription<::sierra::ASTC12x12, ::sierra::ConstBits<128>, ::sierra::Srgb>",
         _ => return Err(syn::Error::new(Span::call_site(), "Unrecognized format")),
     };
 
     Ok(output.parse().unwrap())
 }
+
+/// Returns GLSL image format layout qualifier for the format.
+/// `None` if format cannot be used with storage images in GLSL.
+pub fn glsl_image_format(s: &str) -> Option<&'static str> {
+    let qualifier = match &*s.to_lowercase() {
+        "rgba32sfloat" => "rgba32f",
+        "rgba16sfloat" => "rgba16f",
+        "rg32sfloat" => "rg32f",
+        "rg16sfloat" => "rg16f",
+        "b10g11r11ufloat" => "r11f_g11f_b10f",
+        "r32sfloat" => "r32f",
+        "r16sfloat" => "r16f",
+        "rgba16unorm" => "rgba16",
+        "a2b10g10r10unorm" => "rgb10_a2",
+        "rgba8unorm" => "rgba8",
+        "rg16unorm" => "rg16",
+        "rg8unorm" => "rg8",
+        "r16unorm" => "r16",
+        "r8unorm" => "r8",
+        "rgba16snorm" => "rgba16_snorm",
+        "rgba8snorm" => "rgba8_snorm",
+        "rg16snorm" => "rg16_snorm",
+        "rg8snorm" => "rg8_snorm",
+        "r16snorm" => "r16_snorm",
+        "r8snorm" => "r8_snorm",
+        "rgba32sint" => "rgba32i",
+        "rgba16sint" => "rgba16i",
+        "rgba8sint" => "rgba8i",
+        "rg32sint" => "rg32i",
+        "rg16sint" => "rg16i",
+        "rg8sint" => "rg8i",
+        "r32sint" => "r32i",
+        "r16sint" => "r16i",
+        "r8sint" => "r8i",
+        "rgba32uint" => "rgba32ui",
+        "rgba16uint" => "rgba16ui",
+        "a2b10g10r10uint" => "rgb10_a2ui",
+        "rgba8uint" => "rgba8ui",
+        "rg32uint" => "rg32ui",
+        "rg16uint" => "rg16ui",
+        "rg8uint" => "rg8ui",
+        "r32uint" => "r32ui",
+        "r16uint" => "r16ui",
+        "r8uint" => "r8ui",
+        _ => return None,
+    };
+    Some(qualifier)
+}
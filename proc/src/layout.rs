@@ -5,6 +5,7 @@ proc_easy::easy_flags! {
     pub StructLayouts(layouts) {
         Std140(std140),
         Std430(std430),
+        Scalar(scalar),
     }
 }
 
@@ -19,6 +20,36 @@ impl StructLayout {
         match self {
             StructLayout::Std140(_) => "Std140",
             StructLayout::Std430(_) => "Std430",
+            StructLayout::Scalar(_) => "Scalar",
+        }
+    }
+
+    /// Name of the layout qualifier in GLSL.
+    pub fn glsl_name(&self) -> &'static str {
+        match self {
+            StructLayout::Std140(_) => "std140",
+            StructLayout::Std430(_) => "std430",
+            StructLayout::Scalar(_) => "scalar",
+        }
+    }
+
+    /// Returns GLSL layout qualifier for a block with members in specified layouts.
+    /// Falls back to `std140` if members use different layouts.
+    pub fn common_glsl_name(mut layouts: impl Iterator<Item = StructLayout>) -> &'static str {
+        let first = layouts.next().unwrap_or_default();
+        if layouts.all(|layout| layout.name() == first.name()) {
+            first.glsl_name()
+        } else {
+            "std140"
+        }
+    }
+
+    /// Minimal alignment mask of structures with this layout.
+    /// std140 rounds structure alignment up to `vec4`.
+    pub fn struct_align_mask(&self) -> usize {
+        match self {
+            StructLayout::Std140(_) => 15,
+            StructLayout::Std430(_) | StructLayout::Scalar(_) => 0,
         }
     }
 
@@ -30,6 +61,7 @@ impl StructLayout {
         match self {
             StructLayout::Std140(kw) => quote::quote_spanned!(kw.span() => ::sierra::Std140),
             StructLayout::Std430(kw) => quote::quote_spanned!(kw.span() => ::sierra::Std430),
+            StructLayout::Scalar(kw) => quote::quote_spanned!(kw.span() => ::sierra::Scalar),
         }
     }
 }
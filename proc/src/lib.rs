@@ -11,6 +11,7 @@ mod pass;
 mod pipeline;
 mod pipeline_stages;
 mod repr;
+mod shader;
 mod shader_stage;
 mod swizzle;
 
@@ -136,6 +137,9 @@ mod kw {
     proc_easy::easy_token!(subpass);
     proc_easy::easy_token!(color);
     proc_easy::easy_token!(depth);
+    proc_easy::easy_token!(input);
+    proc_easy::easy_token!(resolve);
+    proc_easy::easy_token!(depth_resolve);
     proc_easy::easy_token!(clear);
     proc_easy::easy_token!(load);
     proc_easy::easy_token!(store);
@@ -143,6 +147,10 @@ mod kw {
     proc_easy::easy_token!(set);
     proc_easy::easy_token!(push);
     proc_easy::easy_token!(layout);
+    proc_easy::easy_token!(view);
+    proc_easy::easy_token!(format);
+    proc_easy::easy_token!(readonly);
+    proc_easy::easy_token!(writeonly);
     proc_easy::easy_token!(attachment);
     proc_easy::easy_token!(top_of_pipe);
     proc_easy::easy_token!(draw_indirect);
@@ -3,7 +3,7 @@ use std::convert::TryFrom;
 use proc_easy::ReferenceExpr;
 use proc_macro2::TokenStream;
 
-use super::parse::{Clear, Input, Load, LoadOp, Store, StoreOp, SubpassDependency};
+use super::parse::{Clear, Input, Load, LoadOp, Store, StoreOp, Subpass, SubpassDependency};
 
 pub(super) fn generate(input: &Input) -> TokenStream {
     let vis = &input.item_struct.vis;
@@ -62,6 +62,7 @@ pub(super) fn generate(input: &Input) -> TokenStream {
                 None => quote::quote!(DontCare),
             };
 
+            let format = quote::quote!(::sierra::Attachment::format(&input.#member));
             let initial_layout = initial_layout(&a.load_op);
             let final_layout = match a.store_op.as_ref().map(final_layout) {
                 None => {
@@ -69,17 +70,7 @@ pub(super) fn generate(input: &Input) -> TokenStream {
                     input
                         .subpasses
                         .iter()
-                        .filter_map(|s| {
-                            if s.colors.iter().any(|&c| c == index) {
-                                Some(quote::quote!(::sierra::Layout::ColorAttachmentOptimal))
-                            } else if s.depth == Some(index) {
-                                Some(quote::quote!(
-                                    ::sierra::Layout::DepthStencilAttachmentOptimal
-                                ))
-                            } else {
-                                None
-                            }
-                        })
+                        .filter_map(|s| subpass_layout(s, index, &format))
                         .rev()
                         .next()
                         .unwrap_or_else(|| quote::quote!(::sierra::Layout::General))
@@ -94,7 +85,7 @@ pub(super) fn generate(input: &Input) -> TokenStream {
 
             quote::quote!(
                 attachments.push(::sierra::AttachmentInfo {
-                    format: ::sierra::Attachment::format(&input.#member),
+                    format: #format,
                     samples: ::sierra::Attachment::samples(&input.#member).unwrap_or_default(),
                     load_op: ::sierra::LoadOp::#load_op,
                     store_op: ::sierra::StoreOp::#store_op,
@@ -113,40 +104,89 @@ pub(super) fn generate(input: &Input) -> TokenStream {
                 .colors
                 .iter()
                 .map(|&c| {
+                    let layout = if s.is_feedback(c) {
+                        quote::quote!(::sierra::Layout::General)
+                    } else {
+                        quote::quote!(::sierra::Layout::ColorAttachmentOptimal)
+                    };
                     quote::quote! {
-                        colors.push((#c, ::sierra::Layout::ColorAttachmentOptimal));
+                        colors.push((#c, #layout));
                     }
                 })
                 .collect::<TokenStream>();
 
             let color_count = s.colors.len();
 
-            match s.depth {
-                Some(depth) => {
-                    quote::quote!(
-                        subpasses.push(::sierra::Subpass {
-                            colors: {
-                                let mut colors = ::std::vec::Vec::with_capacity(#color_count);
-                                #push_colors
-                                colors
-                            },
-                            depth: Some((#depth, ::sierra::Layout::DepthStencilAttachmentOptimal)),
-                        });
-                    )
+            let depth = match s.depth {
+                Some(depth) if s.is_feedback(depth) => {
+                    quote::quote!(Some((#depth, ::sierra::Layout::General)))
                 }
-                None => {
-                    quote::quote!(
-                        subpasses.push(::sierra::Subpass {
-                            colors: {
-                                let mut colors = ::std::vec::Vec::with_capacity(#color_count);
-                                #push_colors
-                                colors
-                            },
-                            depth: None,
-                        });
-                    )
+                Some(depth) => {
+                    quote::quote!(Some((#depth, ::sierra::Layout::DepthStencilAttachmentOptimal)))
                 }
-            }
+                None => quote::quote!(None),
+            };
+
+            let push_inputs = s
+                .inputs
+                .iter()
+                .map(|&i| {
+                    let layout = if s.is_feedback(i) {
+                        quote::quote!(::sierra::Layout::General)
+                    } else {
+                        quote::quote!(::sierra::Layout::input_attachment(attachments[#i as usize].format))
+                    };
+                    quote::quote! {
+                        inputs.push((#i, #layout));
+                    }
+                })
+                .collect::<TokenStream>();
+
+            let input_count = s.inputs.len();
+
+            let push_resolves = s
+                .resolves
+                .iter()
+                .map(|&r| {
+                    quote::quote! {
+                        resolves.push(Some((#r, ::sierra::Layout::ColorAttachmentOptimal)));
+                    }
+                })
+                .collect::<TokenStream>();
+
+            let resolve_count = s.resolves.len();
+
+            let depth_resolve = match s.depth_resolve {
+                Some(depth_resolve) => quote::quote!(Some(::sierra::DepthStencilResolve {
+                    attachment: #depth_resolve,
+                    layout: ::sierra::Layout::DepthStencilAttachmentOptimal,
+                    depth_mode: Some(::sierra::ResolveMode::SampleZero),
+                    stencil_mode: Some(::sierra::ResolveMode::SampleZero),
+                })),
+                None => quote::quote!(None),
+            };
+
+            quote::quote!(
+                subpasses.push(::sierra::Subpass {
+                    colors: {
+                        let mut colors = ::std::vec::Vec::with_capacity(#color_count);
+                        #push_colors
+                        colors
+                    },
+                    depth: #depth,
+                    inputs: {
+                        let mut inputs = ::std::vec::Vec::with_capacity(#input_count);
+                        #push_inputs
+                        inputs
+                    },
+                    resolves: {
+                        let mut resolves = ::std::vec::Vec::with_capacity(#resolve_count);
+                        #push_resolves
+                        resolves
+                    },
+                    depth_resolve: #depth_resolve,
+                });
+            )
         })
         .collect::<TokenStream>();
 
@@ -214,16 +254,16 @@ pub(super) fn generate(input: &Input) -> TokenStream {
             let index = u32::try_from(index).unwrap();
 
             let member = &a.member;
-            let usages = input.subpasses.iter().filter_map(|s| {
-                if s.colors.iter().any(|&c| c == index) {
-                    Some(quote::quote!(::sierra::ImageUsage::COLOR_ATTACHMENT))
-                } else if s.depth == Some(index) {
-                    Some(quote::quote!(
-                        ::sierra::ImageUsage::DEPTH_STENCIL_ATTACHMENT
-                    ))
-                } else {
-                    None
-                }
+            let usages = input.subpasses.iter().flat_map(|s| {
+                let color = s.colors.contains(&index) || s.resolves.contains(&index);
+                let depth = s.depth == Some(index) || s.depth_resolve == Some(index);
+                let input = s.inputs.contains(&index);
+
+                let color = color.then(|| quote::quote!(::sierra::ImageUsage::COLOR_ATTACHMENT));
+                let depth = depth.then(|| quote::quote!(::sierra::ImageUsage::DEPTH_STENCIL_ATTACHMENT));
+                let input = input.then(|| quote::quote!(::sierra::ImageUsage::INPUT_ATTACHMENT));
+
+                color.into_iter().chain(depth).chain(input)
             });
 
             quote::quote!(
@@ -394,6 +434,24 @@ pub(super) fn generate(input: &Input) -> TokenStream {
     )
 }
 
+/// Returns layout of the attachment in the subpass.
+/// `None` if attachment is not used in the subpass.
+fn subpass_layout(s: &Subpass, index: u32, format: &TokenStream) -> Option<TokenStream> {
+    if s.is_feedback(index) {
+        Some(quote::quote!(::sierra::Layout::General))
+    } else if s.colors.contains(&index) || s.resolves.contains(&index) {
+        Some(quote::quote!(::sierra::Layout::ColorAttachmentOptimal))
+    } else if s.depth == Some(index) || s.depth_resolve == Some(index) {
+        Some(quote::quote!(
+            ::sierra::Layout::DepthStencilAttachmentOptimal
+        ))
+    } else if s.inputs.contains(&index) {
+        Some(quote::quote!(::sierra::Layout::input_attachment(#format)))
+    } else {
+        None
+    }
+}
+
 fn initial_layout(load_op: &Option<LoadOp>) -> TokenStream {
     match load_op {
         Some(LoadOp::Clear(_)) => quote::quote!(::std::option::Option::None),
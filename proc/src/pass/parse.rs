@@ -118,6 +118,16 @@ pub struct SubpassDependency {
 pub struct Subpass {
     pub colors: Vec<u32>,
     pub depth: Option<u32>,
+    pub inputs: Vec<u32>,
+    pub resolves: Vec<u32>,
+    pub depth_resolve: Option<u32>,
+}
+
+impl Subpass {
+    /// Checks if attachment is used as both input and color or depth attachment.
+    pub fn is_feedback(&self, index: u32) -> bool {
+        self.inputs.contains(&index) && (self.colors.contains(&index) || self.depth == Some(index))
+    }
 }
 
 proc_easy::easy_argument_value! {
@@ -128,6 +138,18 @@ proc_easy::easy_argument_value! {
     pub struct Depth(pub kw::depth, pub syn::Member);
 }
 
+proc_easy::easy_argument_value! {
+    pub struct InputAttachment(pub kw::input, pub syn::Member);
+}
+
+proc_easy::easy_argument_value! {
+    pub struct Resolve(pub kw::resolve, pub syn::Member);
+}
+
+proc_easy::easy_argument_value! {
+    pub struct DepthResolve(pub kw::depth_resolve, pub syn::Member);
+}
+
 proc_easy::easy_parse! {
     enum SubpassIndex {
         !Index(syn::Index),
@@ -179,6 +201,9 @@ proc_easy::easy_argument_tuple! {
         subpass: kw::subpass,
         colors: Vec<Color>,
         depth: Option<Depth>,
+        inputs: Vec<InputAttachment>,
+        resolves: Vec<Resolve>,
+        depth_resolve: Option<DepthResolve>,
     }
 }
 
@@ -188,66 +213,96 @@ impl SubpassArgument {
         attachments: &[Attachment],
         item_struct: &syn::ItemStruct,
     ) -> syn::Result<Subpass> {
-        let mut unique = HashSet::with_capacity(self.colors.len() + self.depth.is_some() as usize);
+        let attachment_index = |member: &syn::Member| -> syn::Result<u32> {
+            validate_member(member, item_struct)?;
+
+            match attachments.iter().position(|a| a.member == *member) {
+                Some(index) => Ok(u32::try_from(index).unwrap()),
+                None => Err(syn::Error::new_spanned(
+                    member,
+                    "Member is not an attachment",
+                )),
+            }
+        };
+
+        // Input attachments may alias color and depth attachments,
+        // so they are checked for uniqueness separately.
+        let mut unique = HashSet::with_capacity(
+            self.colors.len()
+                + self.depth.is_some() as usize
+                + self.resolves.len()
+                + self.depth_resolve.is_some() as usize,
+        );
+        let mut unique_inputs = HashSet::with_capacity(self.inputs.len());
 
         let mut color_indices = Vec::with_capacity(self.colors.len());
         let mut depth_index = None;
+        let mut input_indices = Vec::with_capacity(self.inputs.len());
+        let mut resolve_indices = Vec::with_capacity(self.resolves.len());
+        let mut depth_resolve_index = None;
 
         for Color(_, member) in self.colors.iter() {
-            if !unique.insert(member) {
-                return Err(syn::Error::new_spanned(
-                    member,
-                    "Duplicate attachment references are not allowed",
-                ));
-            }
+            check_unique(member, &mut unique)?;
+            color_indices.push(attachment_index(member)?);
+        }
 
-            validate_member(member, item_struct)?;
+        if let Some(Depth(_, member)) = &self.depth {
+            check_unique(member, &mut unique)?;
+            depth_index = Some(attachment_index(member)?);
+        }
 
-            match attachments.iter().position(|a| a.member == *member) {
-                Some(index) => {
-                    let index = u32::try_from(index).unwrap();
-
-                    color_indices.push(index);
-                }
-                None => {
-                    return Err(syn::Error::new_spanned(
-                        member,
-                        "Member is not an attachment",
-                    ))
-                }
-            }
+        for InputAttachment(_, member) in self.inputs.iter() {
+            check_unique(member, &mut unique_inputs)?;
+            input_indices.push(attachment_index(member)?);
         }
 
-        if let Some(Depth(_, member)) = &self.depth {
-            if !unique.insert(member) {
+        if !self.resolves.is_empty() && self.resolves.len() != self.colors.len() {
+            return Err(syn::Error::new_spanned(
+                &self.resolves[0].1,
+                "Number of resolve attachments must match number of color attachments",
+            ));
+        }
+
+        for Resolve(_, member) in self.resolves.iter() {
+            check_unique(member, &mut unique)?;
+            resolve_indices.push(attachment_index(member)?);
+        }
+
+        if let Some(DepthResolve(_, member)) = &self.depth_resolve {
+            if self.depth.is_none() {
                 return Err(syn::Error::new_spanned(
                     member,
-                    "Duplicate attachment references are not allowed",
+                    "Depth resolve attachment requires depth attachment",
                 ));
             }
-            validate_member(member, item_struct)?;
 
-            match attachments.iter().position(|a| a.member == *member) {
-                Some(index) => {
-                    let index = u32::try_from(index).unwrap();
-                    depth_index = Some(index);
-                }
-                None => {
-                    return Err(syn::Error::new_spanned(
-                        member,
-                        "Member is not an attachment",
-                    ))
-                }
-            }
+            check_unique(member, &mut unique)?;
+            depth_resolve_index = Some(attachment_index(member)?);
         }
 
         Ok(Subpass {
             colors: color_indices,
             depth: depth_index,
+            inputs: input_indices,
+            resolves: resolve_indices,
+            depth_resolve: depth_resolve_index,
         })
     }
 }
 
+fn check_unique<'a>(
+    member: &'a syn::Member,
+    unique: &mut HashSet<&'a syn::Member>,
+) -> syn::Result<()> {
+    if !unique.insert(member) {
+        return Err(syn::Error::new_spanned(
+            member,
+            "Duplicate attachment references are not allowed",
+        ));
+    }
+    Ok(())
+}
+
 impl SubpassDependencyArgument {
     fn convert(&self, subpasses: &[Subpass]) -> syn::Result<SubpassDependency> {
         let count = u32::try_from(subpasses.len()).unwrap();
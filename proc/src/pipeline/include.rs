@@ -0,0 +1,115 @@
+use std::convert::TryFrom;
+
+use proc_macro2::TokenStream;
+
+use crate::{
+    layout::StructLayout,
+    shader::{
+        glsl_deps, glsl_layout_extension, glsl_members, member_name, stage_visible, wgsl_deps,
+        wgsl_members, WgslLayout,
+    },
+    shader_stage::combined_shader_stage_flags,
+};
+
+use super::parse::Input;
+
+pub(super) fn generate(input: &Input) -> TokenStream {
+    let (glsl_sets, wgsl_sets): (Vec<_>, Vec<_>) = input
+        .sets
+        .iter()
+        .enumerate()
+        .map(|(index, set)| {
+            let ty = &set.field.ty;
+            let index = u32::try_from(index).expect("Too many sets");
+            (
+                quote::quote!(
+                    decls += &<#ty as ::sierra::DescriptorsInclude>::glsl_set(#index, stage, &mut ctx);
+                ),
+                quote::quote!(
+                    decls += &<#ty as ::sierra::DescriptorsInclude>::wgsl_group(#index, stage, &mut ctx);
+                ),
+            )
+        })
+        .unzip();
+
+    let ident = &input.item_struct.ident;
+
+    let (glsl_push_constants, wgsl_push_constants) = if input.push_constants.is_empty() {
+        (TokenStream::new(), TokenStream::new())
+    } else {
+        let visible = stage_visible(combined_shader_stage_flags(
+            input
+                .push_constants
+                .iter()
+                .flat_map(|p| p.stages.flags.iter().copied()),
+        ));
+
+        let layout = StructLayout::common_glsl_name(input.push_constants.iter().map(|p| p.layout));
+
+        let block_name = format!("{}PushConstants", ident);
+        let types = input.push_constants.iter().map(|p| &p.field.ty);
+        let members = || {
+            input.push_constants.iter().enumerate().map(|(index, p)| {
+                let member = match &p.field.ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed(syn::Index::from(index)),
+                };
+                (member_name(&member), &p.field.ty)
+            })
+        };
+
+        let deps = glsl_deps(quote::quote!(&mut ctx), types.clone());
+        let extension = glsl_layout_extension(quote::quote!(&mut ctx), layout);
+        let members_code = glsl_members(members());
+        let glsl = quote::quote!(
+            if #visible {
+                #deps
+                #extension
+                decls += &::std::format!(
+                    "layout(push_constant, {}) uniform {} {{\n{}}};\n",
+                    #layout, #block_name, #members_code,
+                );
+            }
+        );
+
+        let deps = wgsl_deps(
+            quote::quote!(&mut ctx),
+            types,
+            WgslLayout::from_glsl_name(layout),
+        );
+        let members_code = wgsl_members(members(), WgslLayout::from_glsl_name(layout));
+        let wgsl = quote::quote!(
+            if #visible {
+                #deps
+                decls += &::std::format!(
+                    "struct {} {{\n{}}}\nvar<push_constant> push_constants: {};\n",
+                    #block_name, #members_code, #block_name,
+                );
+            }
+        );
+
+        (glsl, wgsl)
+    };
+
+    quote::quote!(
+        impl ::sierra::ShaderInclude for #ident {
+            #[allow(unused_mut)]
+            fn glsl(stage: ::sierra::ShaderStage) -> ::std::string::String {
+                let mut ctx = ::sierra::GlslTypeContext::new();
+                let mut decls = ::std::string::String::new();
+                #(#glsl_sets)*
+                #glsl_push_constants
+                ctx.code() + &decls
+            }
+
+            #[allow(unused_mut)]
+            fn wgsl(stage: ::sierra::ShaderStage) -> ::std::string::String {
+                let mut ctx = ::sierra::WgslTypeContext::new();
+                let mut decls = ::std::string::String::new();
+                #(#wgsl_sets)*
+                #wgsl_push_constants
+                ctx.code() + &decls
+            }
+        }
+    )
+}
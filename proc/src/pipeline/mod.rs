@@ -1,3 +1,4 @@
+mod include;
 mod input;
 // mod instance;
 mod layout;
@@ -9,6 +10,7 @@ pub fn pipeline_input(item: proc_macro::TokenStream) -> proc_macro2::TokenStream
             std::iter::once(input::generate(&input))
                 // .chain(Some(instance::generate(&input)))
                 .chain(Some(layout::generate(&input)))
+                .chain(Some(include::generate(&input)))
                 .collect::<proc_macro2::TokenStream>()
         }
         Err(err) => err.into_compile_error(),
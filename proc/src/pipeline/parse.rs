@@ -1,6 +1,11 @@
 use proc_easy::{private::Spanned, EasyAttributes};
 
-use crate::{kw, layout::StructLayout, shader_stage::ShaderStages};
+use crate::{
+    kw,
+    layout::StructLayout,
+    shader::{check_identifier, member_name},
+    shader_stage::ShaderStages,
+};
 
 pub(super) struct Input {
     pub item_struct: syn::ItemStruct,
@@ -47,7 +52,7 @@ pub(super) fn parse(item: proc_macro::TokenStream) -> syn::Result<Input> {
     let mut sets = Vec::new();
     let mut push_constants = Vec::new();
 
-    for field in &item_struct.fields {
+    for (index, field) in item_struct.fields.iter().enumerate() {
         let attrs = FieldAttrs::parse(&field.attrs, field.span())?;
         match attrs.kind {
             KindAttr::Set(_) => {
@@ -68,6 +73,12 @@ pub(super) fn parse(item: proc_macro::TokenStream) -> syn::Result<Input> {
                     .unwrap_or_else(|| ShaderStages::new(field.span()));
                 let layout = layout.unwrap_or_default();
 
+                let member = match &field.ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed(syn::Index::from(index)),
+                };
+                check_identifier(&member_name(&member), field.span())?;
+
                 push_constants.push(PushConstants {
                     field: field.clone(),
                     stages,
@@ -49,10 +49,11 @@ fn generate_layout(item_struct: &syn::ItemStruct, layout: StructLayout) -> Token
         })
         .collect();
 
+    let struct_align_mask = layout.struct_align_mask();
     let align_mask = item_struct
         .fields
         .iter()
-        .fold(quote::quote!(15), |mut tokens, field| {
+        .fold(quote::quote!(#struct_align_mask), |mut tokens, field| {
             let field_type = &field.ty;
 
             tokens.extend(
@@ -0,0 +1,93 @@
+use proc_macro2::TokenStream;
+use syn::ext::IdentExt;
+
+use crate::{
+    layout::StructLayout,
+    shader::{glsl_deps, glsl_members, wgsl_deps, wgsl_members, wgsl_uniform_layout, WgslLayout},
+};
+
+use super::parse::Input;
+
+pub(super) fn generate(input: &Input) -> TokenStream {
+    let ident = &input.item_struct.ident;
+    let name = ident.to_string();
+
+    let types = input.item_struct.fields.iter().map(|field| &field.ty);
+    let members = || {
+        input
+            .item_struct
+            .fields
+            .iter()
+            .map(|field| (field.ident.as_ref().unwrap().unraw().to_string(), &field.ty))
+    };
+
+    let glsl_deps = glsl_deps(quote::quote!(ctx), types.clone());
+    let glsl_members = glsl_members(members());
+    // WGSL struct has single layout. Pick std140 one if struct has it.
+    let layout = WgslLayout::from_glsl_name(StructLayout::common_glsl_name(
+        input.layouts.flags.iter().copied(),
+    ));
+    let wgsl_layout = wgsl_uniform_layout(types.clone());
+    let wgsl_deps = wgsl_deps(quote::quote!(ctx), types, layout);
+    let wgsl_members = wgsl_members(members(), layout);
+
+    // Definition above follows other layout and cannot be nested in scalar one.
+    let wgsl_scalar_name = match layout {
+        WgslLayout::Scalar => TokenStream::new(),
+        _ => quote::quote!(
+            fn scalar_name() -> ::std::string::String {
+                ::std::panic!("`{}` has no WGSL declaration with scalar layout", #name)
+            }
+        ),
+    };
+
+    quote::quote!(
+        impl ::sierra::GlslType for #ident {
+            fn name() -> &'static str {
+                #name
+            }
+
+            fn deps(ctx: &mut ::sierra::GlslTypeContext) {
+                #glsl_deps
+            }
+
+            fn def() -> ::std::string::String {
+                ::std::format!("struct {} {{\n{}}};\n", #name, #glsl_members)
+            }
+        }
+
+        impl ::sierra::WgslType for #ident {
+            fn name() -> ::std::string::String {
+                ::std::string::String::from(#name)
+            }
+
+            fn deps(ctx: &mut ::sierra::WgslTypeContext) {
+                #wgsl_deps
+            }
+
+            fn def() -> ::std::string::String {
+                ::std::format!("struct {} {{\n{}}}\n", #name, #wgsl_members)
+            }
+
+            fn uniform_layout() -> (usize, usize) {
+                #wgsl_layout
+            }
+
+            #wgsl_scalar_name
+        }
+
+        impl ::sierra::ShaderInclude for #ident {
+            fn glsl(_stage: ::sierra::ShaderStage) -> ::std::string::String {
+                let mut ctx = ::sierra::GlslTypeContext::new();
+                ctx.add::<Self>();
+                ctx.code()
+            }
+
+            fn wgsl(_stage: ::sierra::ShaderStage) -> ::std::string::String {
+                let mut ctx = ::sierra::WgslTypeContext::new();
+                ctx.add::<Self>();
+                ctx.code()
+            }
+        }
+    )
+}
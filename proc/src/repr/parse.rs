@@ -1,7 +1,7 @@
 use proc_easy::EasyAttributes;
-use syn::spanned::Spanned;
+use syn::{ext::IdentExt, spanned::Spanned};
 
-use crate::layout::StructLayouts;
+use crate::{layout::StructLayouts, shader::check_identifier};
 
 pub(super) struct Input {
     pub item_struct: syn::ItemStruct,
@@ -11,7 +11,7 @@ pub(super) struct Input {
 proc_easy::easy_attributes! {
     @(sierra)
     struct ReprAttributes {
-        layouts: StructLayouts,
+        layouts: Option<StructLayouts>,
     }
 }
 
@@ -29,10 +29,19 @@ pub(super) fn parse(item: proc_macro::TokenStream) -> syn::Result<Input> {
         }
     }
 
+    for field in &item_struct.fields {
+        if let Some(ident) = &field.ident {
+            check_identifier(&ident.unraw().to_string(), ident.span())?;
+        }
+    }
+
     let attributes = ReprAttributes::parse(&item_struct.attrs, item_struct.span())?;
+    let layouts = attributes
+        .layouts
+        .unwrap_or_else(|| StructLayouts::new(item_struct.span()));
 
     Ok(Input {
         item_struct,
-        layouts: attributes.layouts,
+        layouts,
     })
 }
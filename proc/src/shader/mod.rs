@@ -1,3 +1,288 @@
 mod glsl;
+mod wgsl;
 
-pub use self::glsl::*;
+use proc_macro2::{Span, TokenStream};
+use syn::ext::IdentExt;
+
+pub use self::{glsl::*, wgsl::*};
+
+/// Name used to declare a field in shader code.
+pub fn member_name(member: &syn::Member) -> String {
+    match member {
+        syn::Member::Named(ident) => ident.unraw().to_string(),
+        syn::Member::Unnamed(index) => format!("field_{}", index.index),
+    }
+}
+
+/// Generates expression that checks if `stage` is in `stages` flags.
+pub fn stage_visible(stages: u32) -> TokenStream {
+    quote::quote!(::sierra::ShaderStageFlags::from_bits_truncate(#stages).contains(stage.flag()))
+}
+
+/// Words reserved in GLSL or WGSL that cannot name declarations.
+/// GLSL sampler, texture and image types are checked separately.
+const RESERVED: &[&str] = &[
+    // GLSL
+    "active",
+    "asm",
+    "atomic_uint",
+    "attribute",
+    "bool",
+    "break",
+    "buffer",
+    "bvec2",
+    "bvec3",
+    "bvec4",
+    "case",
+    "cast",
+    "centroid",
+    "class",
+    "coherent",
+    "common",
+    "const",
+    "continue",
+    "default",
+    "discard",
+    "dmat2",
+    "dmat2x2",
+    "dmat2x3",
+    "dmat2x4",
+    "dmat3",
+    "dmat3x2",
+    "dmat3x3",
+    "dmat3x4",
+    "dmat4",
+    "dmat4x2",
+    "dmat4x3",
+    "dmat4x4",
+    "do",
+    "double",
+    "dvec2",
+    "dvec3",
+    "dvec4",
+    "else",
+    "enum",
+    "extern",
+    "external",
+    "false",
+    "filter",
+    "fixed",
+    "flat",
+    "float",
+    "for",
+    "fvec2",
+    "fvec3",
+    "fvec4",
+    "goto",
+    "half",
+    "highp",
+    "hvec2",
+    "hvec3",
+    "hvec4",
+    "if",
+    "in",
+    "inline",
+    "inout",
+    "input",
+    "int",
+    "interface",
+    "invariant",
+    "ivec2",
+    "ivec3",
+    "ivec4",
+    "layout",
+    "long",
+    "lowp",
+    "mat2",
+    "mat2x2",
+    "mat2x3",
+    "mat2x4",
+    "mat3",
+    "mat3x2",
+    "mat3x3",
+    "mat3x4",
+    "mat4",
+    "mat4x2",
+    "mat4x3",
+    "mat4x4",
+    "mediump",
+    "namespace",
+    "noinline",
+    "noperspective",
+    "out",
+    "output",
+    "partition",
+    "patch",
+    "precise",
+    "precision",
+    "public",
+    "readonly",
+    "resource",
+    "restrict",
+    "return",
+    "sample",
+    "sampler",
+    "samplerShadow",
+    "shared",
+    "short",
+    "sizeof",
+    "smooth",
+    "static",
+    "struct",
+    "subroutine",
+    "superp",
+    "switch",
+    "template",
+    "this",
+    "true",
+    "typedef",
+    "uint",
+    "uniform",
+    "union",
+    "unsigned",
+    "using",
+    "uvec2",
+    "uvec3",
+    "uvec4",
+    "varying",
+    "vec2",
+    "vec3",
+    "vec4",
+    "void",
+    "volatile",
+    "while",
+    "writeonly",
+    // WGSL
+    "alias",
+    "array",
+    "atomic",
+    "bitcast",
+    "continuing",
+    "enable",
+    "f16",
+    "f32",
+    "fallthrough",
+    "fn",
+    "i32",
+    "let",
+    "loop",
+    "override",
+    "ptr",
+    "sampler_comparison",
+    "static_assert",
+    "type",
+    "u32",
+    "var",
+    "texture_1d",
+    "texture_2d",
+    "texture_2d_array",
+    "texture_3d",
+    "texture_cube",
+    "texture_cube_array",
+    "texture_multisampled_2d",
+    "texture_external",
+    "texture_storage_1d",
+    "texture_storage_2d",
+    "texture_storage_2d_array",
+    "texture_storage_3d",
+    "texture_depth_2d",
+    "texture_depth_2d_array",
+    "texture_depth_cube",
+    "texture_depth_cube_array",
+    "texture_depth_multisampled_2d",
+];
+
+/// GLSL opaque type prefixes and dimensionalities.
+const GLSL_OPAQUE_PREFIXES: &[&str] = &[
+    "sampler", "isampler", "usampler", "texture", "itexture", "utexture", "image", "iimage",
+    "uimage",
+];
+const GLSL_OPAQUE_DIMS: &[&str] = &[
+    "1D",
+    "2D",
+    "3D",
+    "Cube",
+    "2DRect",
+    "1DArray",
+    "2DArray",
+    "CubeArray",
+    "Buffer",
+    "2DMS",
+    "2DMSArray",
+];
+
+/// Returns error if `name` is reserved in GLSL or WGSL.
+pub fn check_identifier(name: &str, span: Span) -> syn::Result<()> {
+    let opaque = || {
+        GLSL_OPAQUE_PREFIXES.iter().any(|prefix| {
+            name.strip_prefix(prefix).is_some_and(|rest| {
+                let rest = rest.strip_suffix("Shadow").unwrap_or(rest);
+                GLSL_OPAQUE_DIMS.contains(&rest)
+            })
+        })
+    };
+
+    if RESERVED.contains(&name)
+        || name.starts_with("gl_")
+        || name.starts_with("subpassInput")
+        || opaque()
+    {
+        return Err(syn::Error::new(
+            span,
+            format!("`{}` is reserved in shader languages", name),
+        ));
+    }
+    Ok(())
+}
+
+/// Converts `CamelCase` type name into `snake_case` variable name.
+pub fn snake_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    for (index, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if index > 0 {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserved_identifiers() {
+        for name in [
+            "sampler",
+            "image2D",
+            "usampler2DArrayShadow",
+            "texture_2d",
+            "var",
+            "gl_Position",
+        ] {
+            assert!(
+                check_identifier(name, Span::call_site()).is_err(),
+                "{}",
+                name
+            );
+        }
+        for name in ["linear", "image", "albedo", "samplers", "texture_size"] {
+            assert!(
+                check_identifier(name, Span::call_site()).is_ok(),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn snake_case_names() {
+        assert_eq!(snake_case("MaterialUniforms"), "material_uniforms");
+        assert_eq!(snake_case("Scene"), "scene");
+    }
+}
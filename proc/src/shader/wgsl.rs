@@ -0,0 +1,87 @@
+use proc_macro2::TokenStream;
+
+/// Layout rules WGSL declarations must follow.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WgslLayout {
+    /// Native WGSL layout, matches std430.
+    Native,
+
+    /// Layout required by uniform buffers, matches std140.
+    Uniform,
+
+    /// Scalar block layout.
+    Scalar,
+}
+
+impl WgslLayout {
+    /// Returns WGSL layout that matches specified GLSL layout.
+    pub fn from_glsl_name(name: &str) -> Self {
+        match name {
+            "std140" => WgslLayout::Uniform,
+            "scalar" => WgslLayout::Scalar,
+            _ => WgslLayout::Native,
+        }
+    }
+}
+
+/// Generates statements that add WGSL definitions of `types` to `ctx`
+/// following specified `layout`.
+pub fn wgsl_deps<'a>(
+    ctx: TokenStream,
+    types: impl IntoIterator<Item = &'a syn::Type>,
+    layout: WgslLayout,
+) -> TokenStream {
+    let types = types.into_iter();
+    match layout {
+        WgslLayout::Native => {
+            quote::quote!(#(::sierra::WgslTypeContext::add::<#types>(#ctx);)*)
+        }
+        WgslLayout::Uniform => {
+            quote::quote!(#(<#types as ::sierra::WgslType>::uniform_deps(#ctx);)*)
+        }
+        WgslLayout::Scalar => {
+            quote::quote!(#(<#types as ::sierra::WgslType>::scalar_deps(#ctx);)*)
+        }
+    }
+}
+
+/// Generates expression with WGSL struct members, one per line,
+/// declared following specified `layout`.
+pub fn wgsl_members<'a>(
+    members: impl IntoIterator<Item = (String, &'a syn::Type)>,
+    layout: WgslLayout,
+) -> TokenStream {
+    let members = members.into_iter().map(|(name, ty)| {
+        let declare = match layout {
+            WgslLayout::Native => quote::quote!(<#ty as ::sierra::WgslType>::declare(#name)),
+            WgslLayout::Uniform => {
+                quote::quote!(<#ty as ::sierra::WgslType>::declare_uniform(#name))
+            }
+            WgslLayout::Scalar => {
+                quote::quote!(<#ty as ::sierra::WgslType>::declare_scalar(#name))
+            }
+        };
+
+        quote::quote!(
+            members += "    ";
+            members += &#declare;
+            members += ",\n";
+        )
+    });
+
+    quote::quote!({
+        #[allow(unused_mut)]
+        let mut members = ::std::string::String::new();
+        #(#members)*
+        members
+    })
+}
+
+/// Generates expression with alignment and size of WGSL struct
+/// with members of `types` in uniform buffer.
+pub fn wgsl_uniform_layout<'a>(types: impl IntoIterator<Item = &'a syn::Type>) -> TokenStream {
+    let types = types.into_iter();
+    quote::quote!(::sierra::wgsl_struct_layout([
+        #(<#types as ::sierra::WgslType>::uniform_layout(),)*
+    ]))
+}
@@ -15,6 +15,7 @@ proc_easy::easy_flags! {
         ClosestHit(closest_hit),
         Miss(miss),
         Intersection(intersection),
+        Callable(callable),
     }
 }
 
@@ -32,6 +33,7 @@ impl ShaderStage {
             ShaderStage::ClosestHit(_) => 0b0010000000000,
             ShaderStage::Miss(_) => 0b0100000000000,
             ShaderStage::Intersection(_) => 0b1000000000000,
+            ShaderStage::Callable(_) => 0b10000000000000,
         }
     }
 }
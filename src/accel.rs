@@ -1,5 +1,15 @@
+use std::mem::size_of_val;
+
 pub use crate::backend::AccelerationStructure;
-use crate::{buffer::BufferRange, format::Format, DeviceAddress, IndexType};
+use crate::{
+    access::Access,
+    align_up,
+    buffer::{Buffer, BufferInfo, BufferRange, BufferUsage},
+    encode::Encoder,
+    format::Format,
+    stage::PipelineStages,
+    Device, DeviceAddress, IndexType, OutOfMemory,
+};
 
 bitflags::bitflags! {
     /// Bits which can be set in `AccelerationStructureInfo` specifying additional parameters for acceleration structure builds.
@@ -66,6 +76,78 @@ pub enum AccelerationStructureLevel {
     Top,
 }
 
+/// Mode of acceleration structure copy operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub enum AccelerationStructureCopyMode {
+    /// Copies acceleration structure as is.
+    /// Destination must be at least as large as source.
+    Clone,
+
+    /// Copies acceleration structure into more compact form.
+    /// Source must be built with `AccelerationStructureBuildFlags::ALLOW_COMPACTION`.
+    /// Destination must be at least compacted size large,
+    /// which can be queried with `QueryType::AccelerationStructureCompactedSize`.
+    Compact,
+}
+
+/// Header of acceleration structure serialized with
+/// `Encoder::serialize_acceleration_structure`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct SerializedAccelerationStructureHeader {
+    /// UUID of the driver that serialized acceleration structure.
+    pub driver_uuid: [u8; 16],
+
+    /// UUID of the data format.
+    /// Serialized data can be deserialized only by compatible device.
+    /// See `Device::is_acceleration_structure_compatible`.
+    pub compatibility_uuid: [u8; 16],
+
+    /// Size of serialized data, including this header.
+    pub serialized_size: u64,
+
+    /// Size of acceleration structure region required to deserialize the data.
+    pub deserialized_size: u64,
+
+    /// Number of bottom level acceleration structure handles following this header.
+    /// Always zero for bottom level acceleration structures.
+    pub handle_count: u64,
+}
+
+impl SerializedAccelerationStructureHeader {
+    /// Size of the header in serialized data.
+    pub const SIZE: usize = 56;
+
+    /// Parses header from the beginning of serialized data.
+    /// Returns `None` if data is too short.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < Self::SIZE {
+            return None;
+        }
+
+        let u64_at = |offset: usize| {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(&data[offset..offset + 8]);
+            u64::from_ne_bytes(bytes)
+        };
+
+        let mut driver_uuid = [0; 16];
+        driver_uuid.copy_from_slice(&data[..16]);
+
+        let mut compatibility_uuid = [0; 16];
+        compatibility_uuid.copy_from_slice(&data[16..32]);
+
+        Some(SerializedAccelerationStructureHeader {
+            driver_uuid,
+            compatibility_uuid,
+            serialized_size: u64_at(32),
+            deserialized_size: u64_at(40),
+            handle_count: u64_at(48),
+        })
+    }
+}
+
 /// Specifies the shape of geometries that will be built into an acceleration
 /// structure.
 #[derive(Clone, Copy, Debug)]
@@ -206,12 +288,61 @@ pub enum AccelerationStructureGeometry {
     },
 }
 
+impl AccelerationStructureGeometry {
+    /// Returns shape of this geometry to query build sizes.
+    pub fn info(&self) -> AccelerationStructureGeometryInfo {
+        match self {
+            AccelerationStructureGeometry::Triangles {
+                vertex_format,
+                vertex_count,
+                primitive_count,
+                index_data,
+                transform_data,
+                ..
+            } => AccelerationStructureGeometryInfo::Triangles {
+                max_primitive_count: *primitive_count,
+                index_type: index_data.as_ref().map(IndexData::index_type),
+                max_vertex_count: *vertex_count,
+                vertex_format: *vertex_format,
+                allows_transforms: transform_data.is_some(),
+            },
+            AccelerationStructureGeometry::AABBs {
+                primitive_count, ..
+            } => AccelerationStructureGeometryInfo::AABBs {
+                max_primitive_count: *primitive_count,
+            },
+            AccelerationStructureGeometry::Instances {
+                primitive_count, ..
+            } => AccelerationStructureGeometryInfo::Instances {
+                max_primitive_count: *primitive_count,
+            },
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum IndexData {
     U16(BufferRange),
     U32(BufferRange),
 }
 
+impl IndexData {
+    /// Returns type of indices.
+    pub fn index_type(&self) -> IndexType {
+        match self {
+            IndexData::U16(_) => IndexType::U16,
+            IndexData::U32(_) => IndexType::U32,
+        }
+    }
+
+    /// Returns buffer range with indices.
+    pub fn range(&self) -> &BufferRange {
+        match self {
+            IndexData::U16(range) | IndexData::U32(range) => range,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
@@ -251,6 +382,9 @@ pub struct AabbPositions {
     pub max_z: f32,
 }
 
+unsafe impl bytemuck::Zeroable for AabbPositions {}
+unsafe impl bytemuck::Pod for AabbPositions {}
+
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
@@ -351,3 +485,489 @@ impl AccelerationStructureInstance {
         self
     }
 }
+
+/// Instance of bottom level acceleration structure to add into [`TlasBuilder`].
+#[derive(Clone, Copy, Debug)]
+pub struct TlasInstance<'a> {
+    /// Instanced bottom level acceleration structure.
+    pub blas: &'a AccelerationStructure,
+
+    /// Transformation applied to the instance.
+    pub transform: TransformMatrix,
+
+    /// Value accessible in shaders as `gl_InstanceCustomIndexEXT`.
+    /// Must be less than `2^24`.
+    pub custom_index: u32,
+
+    /// Visibility mask. Instance is skipped if `mask & cull_mask` is zero.
+    pub mask: u8,
+
+    /// Offset of instance hit records in hit region of shader binding table.
+    /// Must be less than `2^24`.
+    pub shader_binding_offset: u32,
+
+    /// Flags for this instance.
+    pub flags: GeometryInstanceFlags,
+}
+
+impl<'a> TlasInstance<'a> {
+    /// Returns instance with identity transform visible to all rays.
+    pub fn new(blas: &'a AccelerationStructure) -> Self {
+        TlasInstance {
+            blas,
+            transform: TransformMatrix::identity(),
+            custom_index: 0,
+            mask: !0,
+            shader_binding_offset: 0,
+            flags: GeometryInstanceFlags::empty(),
+        }
+    }
+}
+
+impl From<TlasInstance<'_>> for AccelerationStructureInstance {
+    fn from(instance: TlasInstance<'_>) -> Self {
+        assert_eq!(
+            instance.blas.info().level,
+            AccelerationStructureLevel::Bottom,
+            "Only bottom level acceleration structures can be instanced"
+        );
+
+        AccelerationStructureInstance {
+            transform: instance.transform,
+            custom_index_mask: InstanceCustomIndexAndMask::new(
+                instance.custom_index,
+                instance.mask,
+            ),
+            shader_binding_offset_flags: InstanceShaderBindingOffsetAndFlags::new(
+                instance.shader_binding_offset,
+                instance.flags,
+            ),
+            acceleration_structure_reference: instance.blas.address(),
+        }
+    }
+}
+
+/// Triangle mesh to add into [`BlasBuilder`].
+#[derive(Clone, Debug)]
+pub struct TriangleMesh {
+    /// Flags for this geometry.
+    pub flags: GeometryFlags,
+
+    /// Vertex positions.
+    ///
+    /// Buffer must be created with `ACCELERATION_STRUCTURE_BUILD_INPUT`
+    /// and `DEVICE_ADDRESS` usage flags.
+    pub vertices: BufferRange,
+
+    /// Format of vertex positions.
+    pub vertex_format: Format,
+
+    /// Distance in bytes between consecutive vertices.
+    pub vertex_stride: u64,
+
+    /// Number of vertices.
+    pub vertex_count: u32,
+
+    /// Indices of vertices, three per triangle.
+    /// If `None` every three consecutive vertices form a triangle.
+    pub indices: Option<IndexData>,
+
+    /// Optional `TransformMatrix` applied to vertices.
+    pub transform: Option<BufferRange>,
+}
+
+impl TriangleMesh {
+    /// Returns non-indexed mesh with tightly packed `RGB32Sfloat` vertices.
+    pub fn new(vertices: BufferRange, vertex_count: u32) -> Self {
+        TriangleMesh {
+            flags: GeometryFlags::empty(),
+            vertices,
+            vertex_format: Format::RGB32Sfloat,
+            vertex_stride: 12,
+            vertex_count,
+            indices: None,
+            transform: None,
+        }
+    }
+
+    /// Returns number of triangles in the mesh.
+    pub fn primitive_count(&self) -> u32 {
+        let count = match &self.indices {
+            None => u64::from(self.vertex_count) / 3,
+            Some(indices) => indices.range().size / (u64::from(indices.index_type().size()) * 3),
+        };
+
+        u32::try_from(count).expect("Too many triangles")
+    }
+
+    fn geometry(&self) -> AccelerationStructureGeometry {
+        AccelerationStructureGeometry::Triangles {
+            flags: self.flags,
+            vertex_format: self.vertex_format,
+            vertex_data: self.vertices.clone(),
+            vertex_stride: self.vertex_stride,
+            vertex_count: self.vertex_count,
+            first_vertex: 0,
+            primitive_count: self.primitive_count(),
+            index_data: self.indices.clone(),
+            transform_data: self.transform.clone(),
+        }
+    }
+}
+
+/// Collects geometry of bottom level acceleration structures
+/// to allocate and build them together.
+#[derive(Clone, Debug)]
+pub struct BlasBuilder {
+    flags: AccelerationStructureBuildFlags,
+    blases: Vec<Vec<AccelerationStructureGeometry>>,
+}
+
+impl BlasBuilder {
+    /// Returns new builder.
+    /// All acceleration structures are built with specified flags.
+    pub fn new(flags: AccelerationStructureBuildFlags) -> Self {
+        BlasBuilder {
+            flags,
+            blases: Vec::new(),
+        }
+    }
+
+    /// Adds acceleration structure built from triangle meshes.
+    /// Returns index of the acceleration structure in the batch.
+    pub fn add_meshes(&mut self, meshes: &[TriangleMesh]) -> usize {
+        self.add_geometries(meshes.iter().map(TriangleMesh::geometry).collect())
+    }
+
+    /// Adds acceleration structure built from single triangle mesh.
+    /// Returns index of the acceleration structure in the batch.
+    pub fn add_mesh(&mut self, mesh: &TriangleMesh) -> usize {
+        self.add_meshes(std::slice::from_ref(mesh))
+    }
+
+    /// Adds acceleration structure built from array of [`AabbPositions`].
+    /// Returns index of the acceleration structure in the batch.
+    pub fn add_aabbs(
+        &mut self,
+        flags: GeometryFlags,
+        data: BufferRange,
+        stride: u64,
+        primitive_count: u32,
+    ) -> usize {
+        self.add_geometries(vec![AccelerationStructureGeometry::AABBs {
+            flags,
+            data,
+            stride,
+            primitive_count,
+        }])
+    }
+
+    /// Adds acceleration structure built from arbitrary bottom level geometries.
+    /// Returns index of the acceleration structure in the batch.
+    pub fn add_geometries(&mut self, geometries: Vec<AccelerationStructureGeometry>) -> usize {
+        assert!(
+            geometries
+                .iter()
+                .all(|g| !matches!(g, AccelerationStructureGeometry::Instances { .. })),
+            "Instances must be built into top level acceleration structure"
+        );
+
+        self.blases.push(geometries);
+        self.blases.len() - 1
+    }
+
+    /// Allocates all collected acceleration structures
+    /// and scratch memory to build them.
+    pub fn allocate(&self, device: &Device) -> Result<AccelerationStructureBatch, OutOfMemory> {
+        AccelerationStructureBatch::new(
+            device,
+            AccelerationStructureLevel::Bottom,
+            self.flags,
+            self.blases.clone(),
+        )
+    }
+}
+
+/// Collects instances of top level acceleration structure
+/// to allocate and build it.
+#[derive(Clone, Debug)]
+pub struct TlasBuilder {
+    flags: AccelerationStructureBuildFlags,
+    instances: Vec<AccelerationStructureInstance>,
+}
+
+impl TlasBuilder {
+    /// Returns new builder.
+    /// Acceleration structure is built with specified flags.
+    pub fn new(flags: AccelerationStructureBuildFlags) -> Self {
+        TlasBuilder {
+            flags,
+            instances: Vec::new(),
+        }
+    }
+
+    /// Adds instance.
+    /// Returns its index accessible in shaders as `gl_InstanceID`.
+    pub fn add_instance(&mut self, instance: TlasInstance<'_>) -> usize {
+        self.instances.push(instance.into());
+        self.instances.len() - 1
+    }
+
+    /// Returns collected instances.
+    pub fn instances(&self) -> &[AccelerationStructureInstance] {
+        &self.instances
+    }
+
+    /// Returns collected instances for modification before refit.
+    pub fn instances_mut(&mut self) -> &mut [AccelerationStructureInstance] {
+        &mut self.instances
+    }
+
+    /// Allocates top level acceleration structure, buffer with instances
+    /// and scratch memory to build it.
+    pub fn allocate(&self, device: &Device) -> Result<AccelerationStructureBatch, OutOfMemory> {
+        assert!(
+            !self.instances.is_empty(),
+            "Top level acceleration structure must have at least one instance"
+        );
+
+        let size = size_of_val(&self.instances[..]) as u64;
+
+        let buffer = device.create_buffer_static(
+            BufferInfo {
+                align: 15,
+                size,
+                usage: BufferUsage::ACCELERATION_STRUCTURE_BUILD_INPUT
+                    | BufferUsage::DEVICE_ADDRESS
+                    | BufferUsage::TRANSFER_DST,
+            },
+            &self.instances,
+        )?;
+
+        let geometry = AccelerationStructureGeometry::Instances {
+            flags: GeometryFlags::empty(),
+            data: BufferRange::whole(buffer),
+            primitive_count: u32::try_from(self.instances.len()).expect("Too many instances"),
+        };
+
+        AccelerationStructureBatch::new(
+            device,
+            AccelerationStructureLevel::Top,
+            self.flags,
+            vec![vec![geometry]],
+        )
+    }
+
+    /// Uploads instances into buffer of `batch` allocated by this builder
+    /// and encodes refit of top level acceleration structure.
+    ///
+    /// Number of instances must not change since allocation.
+    pub fn encode_update<'a>(
+        &self,
+        batch: &'a AccelerationStructureBatch,
+        encoder: &mut Encoder<'a>,
+        device: &Device,
+    ) -> Result<(), OutOfMemory> {
+        let data = match batch.entries.as_slice() {
+            [BatchEntry { geometries, .. }] => match geometries.as_slice() {
+                [AccelerationStructureGeometry::Instances {
+                    data,
+                    primitive_count,
+                    ..
+                }] => {
+                    assert_eq!(
+                        *primitive_count as usize,
+                        self.instances.len(),
+                        "Number of instances changed since allocation"
+                    );
+                    data
+                }
+                _ => panic!("Batch was not allocated by `TlasBuilder`"),
+            },
+            _ => panic!("Batch was not allocated by `TlasBuilder`"),
+        };
+
+        let instances = encoder
+            .scope()
+            .to_scope_from_iter(self.instances.iter().copied());
+
+        encoder.upload_buffer(&data.buffer, data.offset, instances, device)?;
+        encoder.memory_barrier(
+            PipelineStages::TRANSFER,
+            Access::TRANSFER_WRITE,
+            PipelineStages::ACCELERATION_STRUCTURE_BUILD,
+            Access::ACCELERATION_STRUCTURE_READ,
+        );
+        batch.encode_update(encoder);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct BatchEntry {
+    acceleration_structure: AccelerationStructure,
+    geometries: Vec<AccelerationStructureGeometry>,
+    scratch: DeviceAddress,
+}
+
+/// Acceleration structures allocated by [`BlasBuilder`] or [`TlasBuilder`].
+///
+/// All acceleration structures in the batch are built with one command,
+/// each one using own region of shared scratch buffer.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureBatch {
+    flags: AccelerationStructureBuildFlags,
+    entries: Vec<BatchEntry>,
+    scratch: Option<Buffer>,
+}
+
+impl AccelerationStructureBatch {
+    fn new(
+        device: &Device,
+        level: AccelerationStructureLevel,
+        flags: AccelerationStructureBuildFlags,
+        geometries: Vec<Vec<AccelerationStructureGeometry>>,
+    ) -> Result<Self, OutOfMemory> {
+        if geometries.is_empty() {
+            return Ok(AccelerationStructureBatch {
+                flags,
+                entries: Vec::new(),
+                scratch: None,
+            });
+        }
+
+        let scratch_align = device.get_acceleration_structure_scratch_alignment() - 1;
+
+        let mut scratch_size = 0;
+        let mut acceleration_structures = Vec::with_capacity(geometries.len());
+        let mut scratch_offsets = Vec::with_capacity(geometries.len());
+
+        for geometries in &geometries {
+            let infos: Vec<_> = geometries.iter().map(|g| g.info()).collect();
+            let sizes = device.get_acceleration_structure_build_sizes(level, flags, &infos);
+
+            let buffer = device.create_buffer(BufferInfo {
+                align: 255,
+                size: sizes.acceleration_structure_size,
+                usage: BufferUsage::ACCELERATION_STRUCTURE_STORAGE | BufferUsage::DEVICE_ADDRESS,
+            })?;
+
+            acceleration_structures.push(device.create_acceleration_structure(
+                AccelerationStructureInfo {
+                    level,
+                    region: BufferRange::whole(buffer),
+                },
+            )?);
+
+            // Same scratch region is used for both builds and updates.
+            let mut scratch = sizes.build_scratch_size;
+            if flags.contains(AccelerationStructureBuildFlags::ALLOW_UPDATE) {
+                scratch = scratch.max(sizes.update_scratch_size);
+            }
+
+            let offset = align_up(scratch_align, scratch_size).ok_or(OutOfMemory)?;
+            scratch_offsets.push(offset);
+            scratch_size = offset.checked_add(scratch).ok_or(OutOfMemory)?;
+        }
+
+        let scratch = device.create_buffer(BufferInfo {
+            align: scratch_align,
+            size: scratch_size,
+            usage: BufferUsage::STORAGE | BufferUsage::DEVICE_ADDRESS,
+        })?;
+
+        let mut scratch_address = device
+            .get_buffer_device_address(&scratch)
+            .expect("Scratch buffer must have device address");
+
+        let entries = acceleration_structures
+            .into_iter()
+            .zip(geometries)
+            .zip(scratch_offsets)
+            .map(
+                |((acceleration_structure, geometries), offset)| BatchEntry {
+                    acceleration_structure,
+                    geometries,
+                    scratch: scratch_address.offset(offset),
+                },
+            )
+            .collect();
+
+        Ok(AccelerationStructureBatch {
+            flags,
+            entries,
+            scratch: Some(scratch),
+        })
+    }
+
+    /// Returns number of acceleration structures in the batch.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the batch has no acceleration structures.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns acceleration structure by index returned from builder.
+    pub fn get(&self, index: usize) -> &AccelerationStructure {
+        &self.entries[index].acceleration_structure
+    }
+
+    /// Returns iterator over acceleration structures in the batch.
+    pub fn iter(&self) -> impl Iterator<Item = &AccelerationStructure> + '_ {
+        self.entries.iter().map(|e| &e.acceleration_structure)
+    }
+
+    /// Returns scratch buffer shared by builds in the batch.
+    ///
+    /// Encoder does not track scratch memory,
+    /// so the batch must be kept alive until encoded builds complete.
+    pub fn scratch_buffer(&self) -> Option<&Buffer> {
+        self.scratch.as_ref()
+    }
+
+    /// Encodes build of all acceleration structures in the batch.
+    ///
+    /// Geometry data must be available to acceleration structure builds
+    /// and acceleration structures must not be in use by other commands.
+    pub fn encode_build(&self, encoder: &mut Encoder<'_>) {
+        self.encode(encoder, false)
+    }
+
+    /// Encodes refit of all acceleration structures in the batch
+    /// with current content of geometry buffers.
+    ///
+    /// Acceleration structures must be built before and
+    /// allocated with `AccelerationStructureBuildFlags::ALLOW_UPDATE` flag.
+    /// Number of primitives and vertices must not change.
+    pub fn encode_update(&self, encoder: &mut Encoder<'_>) {
+        assert!(
+            self.flags
+                .contains(AccelerationStructureBuildFlags::ALLOW_UPDATE),
+            "`ALLOW_UPDATE` flag is required to refit acceleration structures"
+        );
+        self.encode(encoder, true)
+    }
+
+    fn encode(&self, encoder: &mut Encoder<'_>, update: bool) {
+        let infos: Vec<_> = self
+            .entries
+            .iter()
+            .map(|entry| AccelerationStructureBuildGeometryInfo {
+                src: if update {
+                    Some(&entry.acceleration_structure)
+                } else {
+                    None
+                },
+                dst: &entry.acceleration_structure,
+                flags: self.flags,
+                geometries: &entry.geometries,
+                scratch: entry.scratch,
+            })
+            .collect();
+
+        encoder.build_acceleration_structure(&infos);
+    }
+}
@@ -1,19 +1,23 @@
 use crate::{
-    out_of_host_memory, AccelerationStructureBuildFlags, AccelerationStructureLevel, Access,
-    AspectFlags, BlendFactor, BlendOp, BorderColor, BufferCopy, BufferImageCopy, BufferUsage,
-    CompareOp, ComponentMapping, ComponentMask, CompositeAlphaFlags, Culling,
-    DescriptorBindingFlags, DescriptorSetLayoutFlags, DescriptorType, DeviceAddress, Extent2,
-    Extent3, Filter, Format, FrontFace, GeometryFlags, ImageBlit, ImageCopy, ImageExtent,
-    ImageUsage, ImageViewKind, IndexType, Layout, LoadOp, LogicOp, MemoryUsage, MipmapMode,
-    Offset2, Offset3, OutOfMemory, PipelineStages, PolygonMode, PresentMode, PresentationTiming,
-    PrimitiveTopology, QueueCapabilityFlags, Rect, SamplerAddressMode, Samples, ShaderStage,
-    ShaderStageFlags, StencilOp, StoreOp, Subresource, SubresourceLayers, SubresourceRange,
-    SurfaceTransformFlags, Swizzle, VertexInputRate, Viewport,
+    out_of_host_memory, AccelerationStructureBuildFlags, AccelerationStructureCopyMode,
+    AccelerationStructureLevel, Access, AspectFlags, BlendFactor, BlendOp, BorderColor, BufferCopy,
+    BufferImageCopy, BufferUsage, Chromaticity, ColorSpace, CompareOp, ComponentMapping,
+    ComponentMask, CompositeAlphaFlags, Culling, DescriptorBindingFlags, DescriptorSetLayoutFlags,
+    DescriptorType, DeviceAddress, Extent2, Extent3, Filter, Format, FormatFeatures, FrontFace,
+    GeometryFlags, HdrMetadata, ImageBlit, ImageCopy, ImageExtent, ImageUsage, ImageViewKind,
+    IndexType, Layout, LoadOp, LogicOp, MemoryUsage, MipmapMode, Offset2, Offset3, OutOfMemory,
+    PipelineStages, PipelineStatisticFlags, PolygonMode, PresentMode, PresentationTiming,
+    PrimitiveTopology, QueryResultFlags, QueueCapabilityFlags, Rect, ResolveMode,
+    SamplerAddressMode, Samples, ShaderStage, ShaderStageFlags, StencilOp, StoreOp, Subresource,
+    SubresourceLayers, SubresourceRange, SurfaceTransformFlags, Swizzle, VertexInputRate, Viewport,
 };
 use erupt::{
     extensions::{
+        ext_hdr_metadata::{HdrMetadataEXT, XYColorEXT},
         google_display_timing as gdt, khr_acceleration_structure as vkacc,
-        khr_surface::{CompositeAlphaFlagsKHR, PresentModeKHR, SurfaceTransformFlagsKHR},
+        khr_surface::{
+            ColorSpaceKHR, CompositeAlphaFlagsKHR, PresentModeKHR, SurfaceTransformFlagsKHR,
+        },
     },
     vk1_0, vk1_2,
 };
@@ -134,6 +138,81 @@ impl FromErupt<vk1_0::Format> for Option<Format> {
             vk1_0::Format::D16_UNORM_S8_UINT => Some(Format::D16UnormS8Uint),
             vk1_0::Format::D24_UNORM_S8_UINT => Some(Format::D24UnormS8Uint),
             vk1_0::Format::D32_SFLOAT_S8_UINT => Some(Format::D32SfloatS8Uint),
+            vk1_0::Format::R4G4B4A4_UNORM_PACK16 => Some(Format::R4G4B4A4Unorm),
+            vk1_0::Format::B4G4R4A4_UNORM_PACK16 => Some(Format::B4G4R4A4Unorm),
+            vk1_0::Format::R5G6B5_UNORM_PACK16 => Some(Format::R5G6B5Unorm),
+            vk1_0::Format::B5G6R5_UNORM_PACK16 => Some(Format::B5G6R5Unorm),
+            vk1_0::Format::R5G5B5A1_UNORM_PACK16 => Some(Format::R5G5B5A1Unorm),
+            vk1_0::Format::B5G5R5A1_UNORM_PACK16 => Some(Format::B5G5R5A1Unorm),
+            vk1_0::Format::A1R5G5B5_UNORM_PACK16 => Some(Format::A1R5G5B5Unorm),
+            vk1_0::Format::A2R10G10B10_UNORM_PACK32 => Some(Format::A2R10G10B10Unorm),
+            vk1_0::Format::A2R10G10B10_SNORM_PACK32 => Some(Format::A2R10G10B10Snorm),
+            vk1_0::Format::A2R10G10B10_USCALED_PACK32 => Some(Format::A2R10G10B10Uscaled),
+            vk1_0::Format::A2R10G10B10_SSCALED_PACK32 => Some(Format::A2R10G10B10Sscaled),
+            vk1_0::Format::A2R10G10B10_UINT_PACK32 => Some(Format::A2R10G10B10Uint),
+            vk1_0::Format::A2R10G10B10_SINT_PACK32 => Some(Format::A2R10G10B10Sint),
+            vk1_0::Format::A2B10G10R10_UNORM_PACK32 => Some(Format::A2B10G10R10Unorm),
+            vk1_0::Format::A2B10G10R10_SNORM_PACK32 => Some(Format::A2B10G10R10Snorm),
+            vk1_0::Format::A2B10G10R10_USCALED_PACK32 => Some(Format::A2B10G10R10Uscaled),
+            vk1_0::Format::A2B10G10R10_SSCALED_PACK32 => Some(Format::A2B10G10R10Sscaled),
+            vk1_0::Format::A2B10G10R10_UINT_PACK32 => Some(Format::A2B10G10R10Uint),
+            vk1_0::Format::A2B10G10R10_SINT_PACK32 => Some(Format::A2B10G10R10Sint),
+            vk1_0::Format::B10G11R11_UFLOAT_PACK32 => Some(Format::B10G11R11Ufloat),
+            vk1_0::Format::E5B9G9R9_UFLOAT_PACK32 => Some(Format::E5B9G9R9Ufloat),
+            vk1_0::Format::BC1_RGB_UNORM_BLOCK => Some(Format::BC1RGBUnorm),
+            vk1_0::Format::BC1_RGB_SRGB_BLOCK => Some(Format::BC1RGBSrgb),
+            vk1_0::Format::BC1_RGBA_UNORM_BLOCK => Some(Format::BC1RGBAUnorm),
+            vk1_0::Format::BC1_RGBA_SRGB_BLOCK => Some(Format::BC1RGBASrgb),
+            vk1_0::Format::BC2_UNORM_BLOCK => Some(Format::BC2Unorm),
+            vk1_0::Format::BC2_SRGB_BLOCK => Some(Format::BC2Srgb),
+            vk1_0::Format::BC3_UNORM_BLOCK => Some(Format::BC3Unorm),
+            vk1_0::Format::BC3_SRGB_BLOCK => Some(Format::BC3Srgb),
+            vk1_0::Format::BC4_UNORM_BLOCK => Some(Format::BC4Unorm),
+            vk1_0::Format::BC4_SNORM_BLOCK => Some(Format::BC4Snorm),
+            vk1_0::Format::BC5_UNORM_BLOCK => Some(Format::BC5Unorm),
+            vk1_0::Format::BC5_SNORM_BLOCK => Some(Format::BC5Snorm),
+            vk1_0::Format::BC6H_UFLOAT_BLOCK => Some(Format::BC6HUfloat),
+            vk1_0::Format::BC6H_SFLOAT_BLOCK => Some(Format::BC6HSfloat),
+            vk1_0::Format::BC7_UNORM_BLOCK => Some(Format::BC7Unorm),
+            vk1_0::Format::BC7_SRGB_BLOCK => Some(Format::BC7Srgb),
+            vk1_0::Format::ETC2_R8G8B8_UNORM_BLOCK => Some(Format::ETC2RGB8Unorm),
+            vk1_0::Format::ETC2_R8G8B8_SRGB_BLOCK => Some(Format::ETC2RGB8Srgb),
+            vk1_0::Format::ETC2_R8G8B8A1_UNORM_BLOCK => Some(Format::ETC2RGB8A1Unorm),
+            vk1_0::Format::ETC2_R8G8B8A1_SRGB_BLOCK => Some(Format::ETC2RGB8A1Srgb),
+            vk1_0::Format::ETC2_R8G8B8A8_UNORM_BLOCK => Some(Format::ETC2RGBA8Unorm),
+            vk1_0::Format::ETC2_R8G8B8A8_SRGB_BLOCK => Some(Format::ETC2RGBA8Srgb),
+            vk1_0::Format::EAC_R11_UNORM_BLOCK => Some(Format::EACR11Unorm),
+            vk1_0::Format::EAC_R11_SNORM_BLOCK => Some(Format::EACR11Snorm),
+            vk1_0::Format::EAC_R11G11_UNORM_BLOCK => Some(Format::EACRG11Unorm),
+            vk1_0::Format::EAC_R11G11_SNORM_BLOCK => Some(Format::EACRG11Snorm),
+            vk1_0::Format::ASTC_4X4_UNORM_BLOCK => Some(Format::ASTC4x4Unorm),
+            vk1_0::Format::ASTC_4X4_SRGB_BLOCK => Some(Format::ASTC4x4Srgb),
+            vk1_0::Format::ASTC_5X4_UNORM_BLOCK => Some(Format::ASTC5x4Unorm),
+            vk1_0::Format::ASTC_5X4_SRGB_BLOCK => Some(Format::ASTC5x4Srgb),
+            vk1_0::Format::ASTC_5X5_UNORM_BLOCK => Some(Format::ASTC5x5Unorm),
+            vk1_0::Format::ASTC_5X5_SRGB_BLOCK => Some(Format::ASTC5x5Srgb),
+            vk1_0::Format::ASTC_6X5_UNORM_BLOCK => Some(Format::ASTC6x5Unorm),
+            vk1_0::Format::ASTC_6X5_SRGB_BLOCK => Some(Format::ASTC6x5Srgb),
+            vk1_0::Format::ASTC_6X6_UNORM_BLOCK => Some(Format::ASTC6x6Unorm),
+            vk1_0::Format::ASTC_6X6_SRGB_BLOCK => Some(Format::ASTC6x6Srgb),
+            vk1_0::Format::ASTC_8X5_UNORM_BLOCK => Some(Format::ASTC8x5Unorm),
+            vk1_0::Format::ASTC_8X5_SRGB_BLOCK => Some(Format::ASTC8x5Srgb),
+            vk1_0::Format::ASTC_8X6_UNORM_BLOCK => Some(Format::ASTC8x6Unorm),
+            vk1_0::Format::ASTC_8X6_SRGB_BLOCK => Some(Format::ASTC8x6Srgb),
+            vk1_0::Format::ASTC_8X8_UNORM_BLOCK => Some(Format::ASTC8x8Unorm),
+            vk1_0::Format::ASTC_8X8_SRGB_BLOCK => Some(Format::ASTC8x8Srgb),
+            vk1_0::Format::ASTC_10X5_UNORM_BLOCK => Some(Format::ASTC10x5Unorm),
+            vk1_0::Format::ASTC_10X5_SRGB_BLOCK => Some(Format::ASTC10x5Srgb),
+            vk1_0::Format::ASTC_10X6_UNORM_BLOCK => Some(Format::ASTC10x6Unorm),
+            vk1_0::Format::ASTC_10X6_SRGB_BLOCK => Some(Format::ASTC10x6Srgb),
+            vk1_0::Format::ASTC_10X8_UNORM_BLOCK => Some(Format::ASTC10x8Unorm),
+            vk1_0::Format::ASTC_10X8_SRGB_BLOCK => Some(Format::ASTC10x8Srgb),
+            vk1_0::Format::ASTC_10X10_UNORM_BLOCK => Some(Format::ASTC10x10Unorm),
+            vk1_0::Format::ASTC_10X10_SRGB_BLOCK => Some(Format::ASTC10x10Srgb),
+            vk1_0::Format::ASTC_12X10_UNORM_BLOCK => Some(Format::ASTC12x10Unorm),
+            vk1_0::Format::ASTC_12X10_SRGB_BLOCK => Some(Format::ASTC12x10Srgb),
+            vk1_0::Format::ASTC_12X12_UNORM_BLOCK => Some(Format::ASTC12x12Unorm),
+            vk1_0::Format::ASTC_12X12_SRGB_BLOCK => Some(Format::ASTC12x12Srgb),
             _ => None,
         }
     }
@@ -242,6 +321,81 @@ impl ToErupt<vk1_0::Format> for Format {
             Format::D16UnormS8Uint => vk1_0::Format::D16_UNORM_S8_UINT,
             Format::D24UnormS8Uint => vk1_0::Format::D24_UNORM_S8_UINT,
             Format::D32SfloatS8Uint => vk1_0::Format::D32_SFLOAT_S8_UINT,
+            Format::R4G4B4A4Unorm => vk1_0::Format::R4G4B4A4_UNORM_PACK16,
+            Format::B4G4R4A4Unorm => vk1_0::Format::B4G4R4A4_UNORM_PACK16,
+            Format::R5G6B5Unorm => vk1_0::Format::R5G6B5_UNORM_PACK16,
+            Format::B5G6R5Unorm => vk1_0::Format::B5G6R5_UNORM_PACK16,
+            Format::R5G5B5A1Unorm => vk1_0::Format::R5G5B5A1_UNORM_PACK16,
+            Format::B5G5R5A1Unorm => vk1_0::Format::B5G5R5A1_UNORM_PACK16,
+            Format::A1R5G5B5Unorm => vk1_0::Format::A1R5G5B5_UNORM_PACK16,
+            Format::A2R10G10B10Unorm => vk1_0::Format::A2R10G10B10_UNORM_PACK32,
+            Format::A2R10G10B10Snorm => vk1_0::Format::A2R10G10B10_SNORM_PACK32,
+            Format::A2R10G10B10Uscaled => vk1_0::Format::A2R10G10B10_USCALED_PACK32,
+            Format::A2R10G10B10Sscaled => vk1_0::Format::A2R10G10B10_SSCALED_PACK32,
+            Format::A2R10G10B10Uint => vk1_0::Format::A2R10G10B10_UINT_PACK32,
+            Format::A2R10G10B10Sint => vk1_0::Format::A2R10G10B10_SINT_PACK32,
+            Format::A2B10G10R10Unorm => vk1_0::Format::A2B10G10R10_UNORM_PACK32,
+            Format::A2B10G10R10Snorm => vk1_0::Format::A2B10G10R10_SNORM_PACK32,
+            Format::A2B10G10R10Uscaled => vk1_0::Format::A2B10G10R10_USCALED_PACK32,
+            Format::A2B10G10R10Sscaled => vk1_0::Format::A2B10G10R10_SSCALED_PACK32,
+            Format::A2B10G10R10Uint => vk1_0::Format::A2B10G10R10_UINT_PACK32,
+            Format::A2B10G10R10Sint => vk1_0::Format::A2B10G10R10_SINT_PACK32,
+            Format::B10G11R11Ufloat => vk1_0::Format::B10G11R11_UFLOAT_PACK32,
+            Format::E5B9G9R9Ufloat => vk1_0::Format::E5B9G9R9_UFLOAT_PACK32,
+            Format::BC1RGBUnorm => vk1_0::Format::BC1_RGB_UNORM_BLOCK,
+            Format::BC1RGBSrgb => vk1_0::Format::BC1_RGB_SRGB_BLOCK,
+            Format::BC1RGBAUnorm => vk1_0::Format::BC1_RGBA_UNORM_BLOCK,
+            Format::BC1RGBASrgb => vk1_0::Format::BC1_RGBA_SRGB_BLOCK,
+            Format::BC2Unorm => vk1_0::Format::BC2_UNORM_BLOCK,
+            Format::BC2Srgb => vk1_0::Format::BC2_SRGB_BLOCK,
+            Format::BC3Unorm => vk1_0::Format::BC3_UNORM_BLOCK,
+            Format::BC3Srgb => vk1_0::Format::BC3_SRGB_BLOCK,
+            Format::BC4Unorm => vk1_0::Format::BC4_UNORM_BLOCK,
+            Format::BC4Snorm => vk1_0::Format::BC4_SNORM_BLOCK,
+            Format::BC5Unorm => vk1_0::Format::BC5_UNORM_BLOCK,
+            Format::BC5Snorm => vk1_0::Format::BC5_SNORM_BLOCK,
+            Format::BC6HUfloat => vk1_0::Format::BC6H_UFLOAT_BLOCK,
+            Format::BC6HSfloat => vk1_0::Format::BC6H_SFLOAT_BLOCK,
+            Format::BC7Unorm => vk1_0::Format::BC7_UNORM_BLOCK,
+            Format::BC7Srgb => vk1_0::Format::BC7_SRGB_BLOCK,
+            Format::ETC2RGB8Unorm => vk1_0::Format::ETC2_R8G8B8_UNORM_BLOCK,
+            Format::ETC2RGB8Srgb => vk1_0::Format::ETC2_R8G8B8_SRGB_BLOCK,
+            Format::ETC2RGB8A1Unorm => vk1_0::Format::ETC2_R8G8B8A1_UNORM_BLOCK,
+            Format::ETC2RGB8A1Srgb => vk1_0::Format::ETC2_R8G8B8A1_SRGB_BLOCK,
+            Format::ETC2RGBA8Unorm => vk1_0::Format::ETC2_R8G8B8A8_UNORM_BLOCK,
+            Format::ETC2RGBA8Srgb => vk1_0::Format::ETC2_R8G8B8A8_SRGB_BLOCK,
+            Format::EACR11Unorm => vk1_0::Format::EAC_R11_UNORM_BLOCK,
+            Format::EACR11Snorm => vk1_0::Format::EAC_R11_SNORM_BLOCK,
+            Format::EACRG11Unorm => vk1_0::Format::EAC_R11G11_UNORM_BLOCK,
+            Format::EACRG11Snorm => vk1_0::Format::EAC_R11G11_SNORM_BLOCK,
+            Format::ASTC4x4Unorm => vk1_0::Format::ASTC_4X4_UNORM_BLOCK,
+            Format::ASTC4x4Srgb => vk1_0::Format::ASTC_4X4_SRGB_BLOCK,
+            Format::ASTC5x4Unorm => vk1_0::Format::ASTC_5X4_UNORM_BLOCK,
+            Format::ASTC5x4Srgb => vk1_0::Format::ASTC_5X4_SRGB_BLOCK,
+            Format::ASTC5x5Unorm => vk1_0::Format::ASTC_5X5_UNORM_BLOCK,
+            Format::ASTC5x5Srgb => vk1_0::Format::ASTC_5X5_SRGB_BLOCK,
+            Format::ASTC6x5Unorm => vk1_0::Format::ASTC_6X5_UNORM_BLOCK,
+            Format::ASTC6x5Srgb => vk1_0::Format::ASTC_6X5_SRGB_BLOCK,
+            Format::ASTC6x6Unorm => vk1_0::Format::ASTC_6X6_UNORM_BLOCK,
+            Format::ASTC6x6Srgb => vk1_0::Format::ASTC_6X6_SRGB_BLOCK,
+            Format::ASTC8x5Unorm => vk1_0::Format::ASTC_8X5_UNORM_BLOCK,
+            Format::ASTC8x5Srgb => vk1_0::Format::ASTC_8X5_SRGB_BLOCK,
+            Format::ASTC8x6Unorm => vk1_0::Format::ASTC_8X6_UNORM_BLOCK,
+            Format::ASTC8x6Srgb => vk1_0::Format::ASTC_8X6_SRGB_BLOCK,
+            Format::ASTC8x8Unorm => vk1_0::Format::ASTC_8X8_UNORM_BLOCK,
+            Format::ASTC8x8Srgb => vk1_0::Format::ASTC_8X8_SRGB_BLOCK,
+            Format::ASTC10x5Unorm => vk1_0::Format::ASTC_10X5_UNORM_BLOCK,
+            Format::ASTC10x5Srgb => vk1_0::Format::ASTC_10X5_SRGB_BLOCK,
+            Format::ASTC10x6Unorm => vk1_0::Format::ASTC_10X6_UNORM_BLOCK,
+            Format::ASTC10x6Srgb => vk1_0::Format::ASTC_10X6_SRGB_BLOCK,
+            Format::ASTC10x8Unorm => vk1_0::Format::ASTC_10X8_UNORM_BLOCK,
+            Format::ASTC10x8Srgb => vk1_0::Format::ASTC_10X8_SRGB_BLOCK,
+            Format::ASTC10x10Unorm => vk1_0::Format::ASTC_10X10_UNORM_BLOCK,
+            Format::ASTC10x10Srgb => vk1_0::Format::ASTC_10X10_SRGB_BLOCK,
+            Format::ASTC12x10Unorm => vk1_0::Format::ASTC_12X10_UNORM_BLOCK,
+            Format::ASTC12x10Srgb => vk1_0::Format::ASTC_12X10_SRGB_BLOCK,
+            Format::ASTC12x12Unorm => vk1_0::Format::ASTC_12X12_UNORM_BLOCK,
+            Format::ASTC12x12Srgb => vk1_0::Format::ASTC_12X12_SRGB_BLOCK,
         }
     }
 }
@@ -333,6 +487,76 @@ impl ToErupt<vk1_0::Rect2D> for Rect {
     }
 }
 
+impl FromErupt<vk1_0::FormatProperties> for FormatFeatures {
+    fn from_erupt(properties: vk1_0::FormatProperties) -> FormatFeatures {
+        let image = properties.optimal_tiling_features;
+        let buffer = properties.buffer_features;
+        let mut result = FormatFeatures::empty();
+
+        if image.contains(vk1_0::FormatFeatureFlags::SAMPLED_IMAGE) {
+            result |= FormatFeatures::SAMPLED_IMAGE;
+        }
+
+        if image.contains(vk1_0::FormatFeatureFlags::STORAGE_IMAGE) {
+            result |= FormatFeatures::STORAGE_IMAGE;
+        }
+
+        if image.contains(vk1_0::FormatFeatureFlags::STORAGE_IMAGE_ATOMIC) {
+            result |= FormatFeatures::STORAGE_IMAGE_ATOMIC;
+        }
+
+        if image.contains(vk1_0::FormatFeatureFlags::COLOR_ATTACHMENT) {
+            result |= FormatFeatures::COLOR_ATTACHMENT;
+        }
+
+        if image.contains(vk1_0::FormatFeatureFlags::COLOR_ATTACHMENT_BLEND) {
+            result |= FormatFeatures::COLOR_ATTACHMENT_BLEND;
+        }
+
+        if image.contains(vk1_0::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT) {
+            result |= FormatFeatures::DEPTH_STENCIL_ATTACHMENT;
+        }
+
+        if image.contains(vk1_0::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR) {
+            result |= FormatFeatures::SAMPLED_IMAGE_FILTER_LINEAR;
+        }
+
+        if image.contains(vk1_0::FormatFeatureFlags::BLIT_SRC) {
+            result |= FormatFeatures::BLIT_SRC;
+        }
+
+        if image.contains(vk1_0::FormatFeatureFlags::BLIT_DST) {
+            result |= FormatFeatures::BLIT_DST;
+        }
+
+        if image.contains(vk1_0::FormatFeatureFlags::TRANSFER_SRC) {
+            result |= FormatFeatures::TRANSFER_SRC;
+        }
+
+        if image.contains(vk1_0::FormatFeatureFlags::TRANSFER_DST) {
+            result |= FormatFeatures::TRANSFER_DST;
+        }
+
+        if buffer.contains(vk1_0::FormatFeatureFlags::VERTEX_BUFFER) {
+            result |= FormatFeatures::VERTEX_BUFFER;
+        }
+
+        if buffer.contains(vk1_0::FormatFeatureFlags::UNIFORM_TEXEL_BUFFER) {
+            result |= FormatFeatures::UNIFORM_TEXEL_BUFFER;
+        }
+
+        if buffer.contains(vk1_0::FormatFeatureFlags::STORAGE_TEXEL_BUFFER) {
+            result |= FormatFeatures::STORAGE_TEXEL_BUFFER;
+        }
+
+        if buffer.contains(vk1_0::FormatFeatureFlags::ACCELERATION_STRUCTURE_VERTEX_BUFFER_KHR) {
+            result |= FormatFeatures::ACCELERATION_STRUCTURE_VERTEX_BUFFER;
+        }
+
+        result
+    }
+}
+
 impl FromErupt<vk1_0::ImageUsageFlags> for ImageUsage {
     fn from_erupt(usage: vk1_0::ImageUsageFlags) -> ImageUsage {
         let mut result = ImageUsage::empty();
@@ -581,6 +805,56 @@ impl ToErupt<PresentModeKHR> for PresentMode {
     }
 }
 
+impl FromErupt<ColorSpaceKHR> for Option<ColorSpace> {
+    fn from_erupt(color_space: ColorSpaceKHR) -> Option<ColorSpace> {
+        match color_space {
+            ColorSpaceKHR::SRGB_NONLINEAR_KHR => Some(ColorSpace::SrgbNonlinear),
+            ColorSpaceKHR::EXTENDED_SRGB_LINEAR_EXT => Some(ColorSpace::ExtendedSrgbLinear),
+            ColorSpaceKHR::EXTENDED_SRGB_NONLINEAR_EXT => Some(ColorSpace::ExtendedSrgbNonlinear),
+            ColorSpaceKHR::DISPLAY_P3_NONLINEAR_EXT => Some(ColorSpace::DisplayP3Nonlinear),
+            ColorSpaceKHR::HDR10_ST2084_EXT => Some(ColorSpace::Hdr10St2084),
+            _ => None,
+        }
+    }
+}
+
+impl ToErupt<ColorSpaceKHR> for ColorSpace {
+    fn to_erupt(self) -> ColorSpaceKHR {
+        match self {
+            ColorSpace::SrgbNonlinear => ColorSpaceKHR::SRGB_NONLINEAR_KHR,
+            ColorSpace::ExtendedSrgbLinear => ColorSpaceKHR::EXTENDED_SRGB_LINEAR_EXT,
+            ColorSpace::ExtendedSrgbNonlinear => ColorSpaceKHR::EXTENDED_SRGB_NONLINEAR_EXT,
+            ColorSpace::DisplayP3Nonlinear => ColorSpaceKHR::DISPLAY_P3_NONLINEAR_EXT,
+            ColorSpace::Hdr10St2084 => ColorSpaceKHR::HDR10_ST2084_EXT,
+        }
+    }
+}
+
+impl ToErupt<XYColorEXT> for Chromaticity {
+    fn to_erupt(self) -> XYColorEXT {
+        XYColorEXT {
+            x: self.x,
+            y: self.y,
+        }
+    }
+}
+
+impl ToErupt<HdrMetadataEXT> for HdrMetadata {
+    fn to_erupt(self) -> HdrMetadataEXT {
+        HdrMetadataEXT {
+            display_primary_red: self.display_primary_red.to_erupt(),
+            display_primary_green: self.display_primary_green.to_erupt(),
+            display_primary_blue: self.display_primary_blue.to_erupt(),
+            white_point: self.white_point.to_erupt(),
+            max_luminance: self.max_luminance,
+            min_luminance: self.min_luminance,
+            max_content_light_level: self.max_content_light_level,
+            max_frame_average_light_level: self.max_frame_average_light_level,
+            ..Default::default()
+        }
+    }
+}
+
 #[track_caller]
 pub(crate) fn oom_error_from_erupt(err: vk1_0::Result) -> OutOfMemory {
     match err {
@@ -609,6 +883,18 @@ impl ToErupt<vk1_0::AttachmentStoreOp> for StoreOp {
     }
 }
 
+impl ToErupt<vk1_2::ResolveModeFlagBits> for Option<ResolveMode> {
+    fn to_erupt(self) -> vk1_2::ResolveModeFlagBits {
+        match self {
+            None => vk1_2::ResolveModeFlagBits::NONE,
+            Some(ResolveMode::SampleZero) => vk1_2::ResolveModeFlagBits::SAMPLE_ZERO,
+            Some(ResolveMode::Average) => vk1_2::ResolveModeFlagBits::AVERAGE,
+            Some(ResolveMode::Min) => vk1_2::ResolveModeFlagBits::MIN,
+            Some(ResolveMode::Max) => vk1_2::ResolveModeFlagBits::MAX,
+        }
+    }
+}
+
 impl FromErupt<vk1_0::QueueFlags> for QueueCapabilityFlags {
     fn from_erupt(flags: vk1_0::QueueFlags) -> QueueCapabilityFlags {
         let mut result = QueueCapabilityFlags::empty();
@@ -721,6 +1007,18 @@ impl ToErupt<vk1_0::PipelineStageFlags> for PipelineStages {
     }
 }
 
+impl ToErupt<vk1_0::QueryPipelineStatisticFlags> for PipelineStatisticFlags {
+    fn to_erupt(self) -> vk1_0::QueryPipelineStatisticFlags {
+        vk1_0::QueryPipelineStatisticFlags::from_bits_truncate(self.bits())
+    }
+}
+
+impl ToErupt<vk1_0::QueryResultFlags> for QueryResultFlags {
+    fn to_erupt(self) -> vk1_0::QueryResultFlags {
+        vk1_0::QueryResultFlags::from_bits_truncate(self.bits())
+    }
+}
+
 impl ToErupt<vk1_0::ShaderStageFlags> for ShaderStageFlags {
     fn to_erupt(self) -> vk1_0::ShaderStageFlags {
         if self == ShaderStageFlags::all() {
@@ -773,6 +1071,10 @@ impl ToErupt<vk1_0::ShaderStageFlags> for ShaderStageFlags {
             result |= vk1_0::ShaderStageFlags::INTERSECTION_KHR;
         }
 
+        if self.contains(ShaderStageFlags::CALLABLE) {
+            result |= vk1_0::ShaderStageFlags::CALLABLE_KHR;
+        }
+
         if self.contains(ShaderStageFlags::ALL_GRAPHICS) {
             result |= vk1_0::ShaderStageFlags::ALL_GRAPHICS;
         }
@@ -797,6 +1099,7 @@ impl ToErupt<vk1_0::ShaderStageFlagBits> for ShaderStage {
             ShaderStage::ClosestHit => vk1_0::ShaderStageFlagBits::CLOSEST_HIT_KHR,
             ShaderStage::Miss => vk1_0::ShaderStageFlagBits::MISS_KHR,
             ShaderStage::Intersection => vk1_0::ShaderStageFlagBits::INTERSECTION_KHR,
+            ShaderStage::Callable => vk1_0::ShaderStageFlagBits::CALLABLE_KHR,
         }
     }
 }
@@ -1012,6 +1315,9 @@ impl ToErupt<vk1_0::ImageViewType> for ImageViewKind {
             ImageViewKind::D2 => vk1_0::ImageViewType::_2D,
             ImageViewKind::D3 => vk1_0::ImageViewType::_3D,
             ImageViewKind::Cube => vk1_0::ImageViewType::CUBE,
+            ImageViewKind::D1Array => vk1_0::ImageViewType::_1D_ARRAY,
+            ImageViewKind::D2Array => vk1_0::ImageViewType::_2D_ARRAY,
+            ImageViewKind::CubeArray => vk1_0::ImageViewType::CUBE_ARRAY,
         }
     }
 }
@@ -1080,6 +1386,19 @@ impl ToErupt<vkacc::AccelerationStructureTypeKHR> for AccelerationStructureLevel
     }
 }
 
+impl ToErupt<vkacc::CopyAccelerationStructureModeKHR> for AccelerationStructureCopyMode {
+    fn to_erupt(self) -> vkacc::CopyAccelerationStructureModeKHR {
+        match self {
+            AccelerationStructureCopyMode::Clone => {
+                vkacc::CopyAccelerationStructureModeKHR::CLONE_KHR
+            }
+            AccelerationStructureCopyMode::Compact => {
+                vkacc::CopyAccelerationStructureModeKHR::COMPACT_KHR
+            }
+        }
+    }
+}
+
 impl ToErupt<vkacc::BuildAccelerationStructureFlagsKHR> for AccelerationStructureBuildFlags {
     fn to_erupt(self) -> vkacc::BuildAccelerationStructureFlagsKHR {
         vkacc::BuildAccelerationStructureFlagsKHR::from_bits(self.bits()).unwrap()
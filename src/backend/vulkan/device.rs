@@ -14,7 +14,7 @@ use bytemuck::Pod;
 #[cfg(any(feature = "glsl", feature = "wgsl"))]
 use codespan_reporting::{
     diagnostic::{Diagnostic, Label},
-    files::SimpleFile,
+    files::SimpleFiles,
     term::termcolor::{ColorChoice, StandardStream},
 };
 
@@ -33,6 +33,15 @@ use gpu_descriptor_erupt::EruptDescriptorDevice;
 #[cfg(any(feature = "glsl", feature = "wgsl"))]
 use naga::WithSpan;
 
+#[cfg(any(feature = "glsl", feature = "wgsl"))]
+use crate::preprocess::SourceMap;
+
+#[cfg(feature = "glsl")]
+use crate::shader::naga_glsl_stage;
+
+#[cfg(feature = "hlsl")]
+use crate::shader::shaderc_shader_kind;
+
 use parking_lot::Mutex;
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
 use slab::Slab;
@@ -44,7 +53,7 @@ use crate::{
         AccelerationStructureBuildSizesInfo, AccelerationStructureGeometryInfo,
         AccelerationStructureInfo, AccelerationStructureLevel,
     },
-    align_up, arith_eq, arith_le, arith_ne, assert_object,
+    align_up, arith_eq, arith_lt, arith_ne, assert_object,
     buffer::{
         Buffer, BufferInfo, BufferRange, BufferUsage, BufferView, BufferViewInfo, MappableBuffer,
         StridedBufferRange,
@@ -55,28 +64,32 @@ use crate::{
         DescriptorsAllocationError, UpdateDescriptorSet, WritableDescriptorSet,
     },
     fence::Fence,
+    format::AspectFlags,
     framebuffer::{Framebuffer, FramebufferInfo},
     host_memory_space_overflow,
-    image::{Image, ImageInfo},
+    image::{Image, ImageExtent, ImageInfo, Layout, Samples},
     memory::MemoryUsage,
     out_of_host_memory,
+    physical::DeviceInfo,
     pipeline::{
         ColorBlend, ComputePipeline, ComputePipelineInfo, GraphicsPipeline, GraphicsPipelineInfo,
-        PipelineLayout, PipelineLayoutInfo, RayTracingPipeline, RayTracingPipelineInfo,
-        RayTracingShaderGroupInfo, ShaderBindingTable, ShaderBindingTableInfo, State,
+        PipelineCache, PipelineCacheHeader, PipelineLayout, PipelineLayoutInfo, RayTracingPipeline,
+        RayTracingPipelineInfo, RayTracingShaderGroupInfo, ShaderBindingTable,
+        ShaderBindingTableInfo, ShaderRecord, State,
     },
+    query::{QueryError, QueryPool, QueryPoolInfo, QueryType},
     queue::QueueId,
     render_pass::{CreateRenderPassError, RenderPass, RenderPassInfo},
     sampler::{Sampler, SamplerInfo},
-    semaphore::Semaphore,
+    semaphore::{Semaphore, TimelineSemaphore},
     shader::{
-        CreateShaderModuleError, InvalidShader, ShaderLanguage, ShaderModule, ShaderModuleInfo,
-        ShaderStage,
+        CreateShaderModuleError, InvalidShader, Shader, ShaderLanguage, ShaderModule,
+        ShaderModuleInfo, ShaderStage, Specialization,
     },
     surface::Surface,
     view::{ImageView, ImageViewInfo, ImageViewKind},
-    CreateSurfaceError, DeviceAddress, DeviceLost, GraphicsPipelineRenderingInfo, IndexType,
-    MapError, OutOfMemory, SurfaceInfo,
+    CreateSurfaceError, DeviceAddress, DeviceLost, GraphicsPipelineRenderingInfo,
+    HeadlessSurfaceInfo, IndexType, MapError, OutOfMemory, SurfaceInfo,
 };
 
 use super::{
@@ -85,6 +98,7 @@ use super::{
     epochs::Epochs,
     graphics::Graphics,
     physical::{Features, Properties},
+    queue::Queue,
     resources::FenceState,
     unexpected_result,
 };
@@ -106,6 +120,7 @@ impl From<gpu_alloc::MapError> for MapError {
 pub(crate) struct Inner {
     logical: DeviceLoader,
     physical: vk1_0::PhysicalDevice,
+    info: DeviceInfo,
     properties: Properties,
     features: Features,
     allocator: Mutex<GpuAllocator<vk1_0::DeviceMemory>>,
@@ -120,6 +135,7 @@ pub(crate) struct Inner {
     image_views: Mutex<Slab<vk1_0::ImageView>>,
     pipelines: Mutex<Slab<vk1_0::Pipeline>>,
     pipeline_layouts: Mutex<Slab<vk1_0::PipelineLayout>>,
+    query_pools: Mutex<Slab<vk1_0::QueryPool>>,
     render_passes: Mutex<Slab<vk1_0::RenderPass>>,
     semaphores: Mutex<Slab<vk1_0::Semaphore>>,
     shaders: Mutex<Slab<vk1_0::ShaderModule>>,
@@ -127,7 +143,10 @@ pub(crate) struct Inner {
     samplers: Mutex<Slab<vk1_0::Sampler>>,
     swapchains: Mutex<Slab<vksw::SwapchainKHR>>,
 
+    pipeline_caches: Mutex<Slab<vk1_0::PipelineCache>>,
+
     samplers_cache: Mutex<HashMap<SamplerInfo, Sampler>>,
+    pipeline_cache: Mutex<Option<PipelineCache>>,
 
     epochs: Epochs,
 }
@@ -289,6 +308,7 @@ impl Device {
     pub(super) fn new(
         logical: DeviceLoader,
         physical: vk1_0::PhysicalDevice,
+        info: DeviceInfo,
         properties: Properties,
         features: Features,
         version: u32,
@@ -317,6 +337,7 @@ impl Device {
                 image_views: Mutex::new(Slab::with_capacity(4096)),
                 pipelines: Mutex::new(Slab::with_capacity(128)),
                 pipeline_layouts: Mutex::new(Slab::with_capacity(64)),
+                query_pools: Mutex::new(Slab::with_capacity(32)),
                 render_passes: Mutex::new(Slab::with_capacity(32)),
                 semaphores: Mutex::new(Slab::with_capacity(128)),
                 shaders: Mutex::new(Slab::with_capacity(512)),
@@ -326,11 +347,15 @@ impl Device {
 
                 logical,
                 physical,
+                info,
                 version,
                 properties,
                 features,
 
+                pipeline_caches: Mutex::new(Slab::with_capacity(4)),
+
                 samplers_cache: Mutex::new(HashMap::new()),
+                pipeline_cache: Mutex::new(None),
 
                 epochs: Epochs::new(queues),
             }),
@@ -584,6 +609,11 @@ impl Device {
         let handle = unsafe {
             self.inner.logical.create_image(
                 &vk1_0::ImageCreateInfoBuilder::new()
+                    .flags(if is_cube_compatible(&info) {
+                        vk1_0::ImageCreateFlags::CUBE_COMPATIBLE
+                    } else {
+                        vk1_0::ImageCreateFlags::empty()
+                    })
                     .image_type(info.extent.to_erupt())
                     .format(info.format.to_erupt())
                     .extent(info.extent.into_3d().to_erupt())
@@ -828,6 +858,40 @@ impl Device {
         assert_owner!(info.image, self);
 
         let image = &info.image;
+        let image_info = image.info();
+
+        assert!(
+            info.view_kind.is_compatible_with(image_info.extent),
+            "Image view kind `{:?}` is incompatible with image extent `{:?}`",
+            info.view_kind,
+            image_info.extent,
+        );
+
+        assert!(
+            info.view_kind.is_valid_layer_count(info.range.layer_count),
+            "Image view kind `{:?}` cannot have {} layers",
+            info.view_kind,
+            info.range.layer_count,
+        );
+
+        assert!(
+            info.range.first_layer + info.range.layer_count <= image_info.layers,
+            "Image view layers are out of image layers bound",
+        );
+
+        if let ImageViewKind::Cube | ImageViewKind::CubeArray = info.view_kind {
+            assert!(
+                is_cube_compatible(image_info),
+                "Image is not cube compatible",
+            );
+        }
+
+        if info.view_kind == ImageViewKind::CubeArray {
+            assert_ne!(
+                self.inner.features.v10.image_cube_array, 0,
+                "`ImageCubeArray` feature is not enabled"
+            );
+        }
 
         let view = unsafe {
             self.inner.logical.create_image_view(
@@ -953,6 +1017,7 @@ impl Device {
 
         let mut dynamic_rendering_info;
         let colors_count;
+        let mut samples = vk1_0::SampleCountFlagBits::_1;
 
         let mut builder = vk1_0::GraphicsPipelineCreateInfoBuilder::new();
 
@@ -984,9 +1049,18 @@ impl Device {
                 subpass,
             } => {
                 assert_owner!(render_pass, self);
-                colors_count = render_pass.info().subpasses[usize::try_from(subpass).unwrap()]
-                    .colors
-                    .len();
+                let render_pass_info = render_pass.info();
+                let subpass_info = &render_pass_info.subpasses[usize::try_from(subpass).unwrap()];
+                colors_count = subpass_info.colors.len();
+
+                // All color and depth attachments of the subpass must have same sample count.
+                if let Some(&(attachment, _)) =
+                    subpass_info.colors.first().or(subpass_info.depth.as_ref())
+                {
+                    samples = render_pass_info.attachments[attachment as usize]
+                        .samples
+                        .to_erupt();
+                }
 
                 builder = builder.render_pass(render_pass.handle()).subpass(subpass);
             }
@@ -1034,12 +1108,19 @@ impl Device {
             .vertex_attribute_descriptions(&vertex_attribute_descriptions);
 
         let vertex_shader_entry = entry_name_to_cstr(desc.vertex_shader.entry());
+        let vertex_specialization_entries =
+            specialization_entries(desc.vertex_shader.specialization());
+        let vertex_specialization = specialization_info(
+            &vertex_specialization_entries,
+            desc.vertex_shader.specialization(),
+        );
 
         shader_stages.push(
             vk1_0::PipelineShaderStageCreateInfoBuilder::new()
                 .stage(vk1_0::ShaderStageFlagBits::VERTEX)
                 .module(desc.vertex_shader.module().handle())
-                .name(&vertex_shader_entry),
+                .name(&vertex_shader_entry)
+                .specialization_info(&vertex_specialization),
         );
 
         let input_assembly_state = vk1_0::PipelineInputAssemblyStateCreateInfoBuilder::new()
@@ -1063,6 +1144,8 @@ impl Device {
         let mut color_blend_state = None;
 
         let fragment_shader_entry;
+        let fragment_specialization_entries;
+        let fragment_specialization;
 
         let with_rasterizer = if let Some(rasterizer) = &desc.rasterizer {
             let mut builder = vk1_0::PipelineViewportStateCreateInfoBuilder::new();
@@ -1103,7 +1186,7 @@ impl Device {
 
             multisample_state = Some(
                 vk1_0::PipelineMultisampleStateCreateInfoBuilder::new()
-                    .rasterization_samples(vk1_0::SampleCountFlagBits::_1),
+                    .rasterization_samples(samples),
             );
 
             let mut builder = vk1_0::PipelineDepthStencilStateCreateInfoBuilder::new();
@@ -1197,11 +1280,15 @@ impl Device {
 
             if let Some(shader) = &rasterizer.fragment_shader {
                 fragment_shader_entry = entry_name_to_cstr(shader.entry());
+                fragment_specialization_entries = specialization_entries(shader.specialization());
+                fragment_specialization =
+                    specialization_info(&fragment_specialization_entries, shader.specialization());
                 shader_stages.push(
                     vk1_0::PipelineShaderStageCreateInfoBuilder::new()
                         .stage(vk1_0::ShaderStageFlagBits::FRAGMENT)
                         .module(shader.module().handle())
-                        .name(&fragment_shader_entry),
+                        .name(&fragment_shader_entry)
+                        .specialization_info(&fragment_specialization),
                 );
             }
 
@@ -1291,9 +1378,11 @@ impl Device {
                 .depth_stencil_state(depth_stencil_state.as_ref().unwrap());
         }
 
+        let pipeline_cache = self.inner.pipeline_cache.lock().clone();
+
         let pipelines = unsafe {
             self.inner.logical.create_graphics_pipelines(
-                vk1_0::PipelineCache::null(),
+                pipeline_cache_handle(&pipeline_cache),
                 &[builder],
                 None,
             )
@@ -1317,6 +1406,89 @@ impl Device {
         ))
     }
 
+    /// Creates pipeline cache.
+    ///
+    /// `initial_data` should be obtained with `PipelineCache::data`.
+    /// Data saved on different device or driver version is ignored
+    /// and empty cache is created instead.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(initial_data)))]
+    pub fn create_pipeline_cache(&self, initial_data: &[u8]) -> Result<PipelineCache, OutOfMemory> {
+        let compatible = PipelineCacheHeader::parse(initial_data)
+            .is_some_and(|header| header.is_compatible(&self.inner.info));
+
+        let initial_data = if compatible {
+            initial_data
+        } else {
+            if !initial_data.is_empty() {
+                warn!("Pipeline cache data is not compatible with the device and is ignored");
+            }
+            &[]
+        };
+
+        let handle = unsafe {
+            self.inner.logical.create_pipeline_cache(
+                &vk1_0::PipelineCacheCreateInfoBuilder::new()
+                    .initial_data_size(initial_data.len())
+                    .initial_data(initial_data.as_ptr().cast()),
+                None,
+            )
+        }
+        .result()
+        .map_err(oom_error_from_erupt)?;
+
+        let index = self.inner.pipeline_caches.lock().insert(handle);
+
+        debug!("PipelineCache created {:p}", handle);
+        Ok(PipelineCache::new(self.downgrade(), handle, index))
+    }
+
+    pub(super) unsafe fn destroy_pipeline_cache(&self, index: usize) {
+        let handle = self.inner.pipeline_caches.lock().remove(index);
+        self.inner.logical.destroy_pipeline_cache(handle, None);
+    }
+
+    /// Returns data of the pipeline cache to save and pass to
+    /// `create_pipeline_cache` later.
+    #[cfg_attr(feature = "tracing", tracing::instrument)]
+    pub fn get_pipeline_cache_data(&self, cache: &PipelineCache) -> Result<Vec<u8>, OutOfMemory> {
+        assert_owner!(cache, self);
+
+        let mut size = 0;
+        unsafe {
+            self.inner.logical.get_pipeline_cache_data(
+                cache.handle(),
+                &mut size,
+                std::ptr::null_mut(),
+            )
+        }
+        .result()
+        .map_err(oom_error_from_erupt)?;
+
+        let mut data = vec![0u8; size];
+        unsafe {
+            self.inner.logical.get_pipeline_cache_data(
+                cache.handle(),
+                &mut size,
+                data.as_mut_ptr().cast(),
+            )
+        }
+        .result()
+        .map_err(oom_error_from_erupt)?;
+
+        data.truncate(size);
+        Ok(data)
+    }
+
+    /// Sets pipeline cache used to create all pipelines with this device.
+    /// Pass `None` to create pipelines without cache.
+    #[cfg_attr(feature = "tracing", tracing::instrument)]
+    pub fn set_pipeline_cache(&self, cache: Option<PipelineCache>) {
+        if let Some(cache) = &cache {
+            assert_owner!(cache, self);
+        }
+        *self.inner.pipeline_cache.lock() = cache;
+    }
+
     /// Creates compute pipeline.
     #[cfg_attr(feature = "tracing", tracing::instrument)]
     pub fn create_compute_pipeline(
@@ -1327,16 +1499,22 @@ impl Device {
         assert_owner!(info.layout, self);
 
         let shader_entry = entry_name_to_cstr(info.shader.entry());
+        let specialization_entries = specialization_entries(info.shader.specialization());
+        let specialization =
+            specialization_info(&specialization_entries, info.shader.specialization());
+
+        let pipeline_cache = self.inner.pipeline_cache.lock().clone();
 
         let pipelines = unsafe {
             self.inner.logical.create_compute_pipelines(
-                vk1_0::PipelineCache::null(),
+                pipeline_cache_handle(&pipeline_cache),
                 &[vk1_0::ComputePipelineCreateInfoBuilder::new()
                     .stage(
                         vk1_0::PipelineShaderStageCreateInfoBuilder::new()
                             .stage(vk1_0::ShaderStageFlagBits::COMPUTE)
                             .module(info.shader.module().handle())
                             .name(&shader_entry)
+                            .specialization_info(&specialization)
                             .build_dangling(),
                     )
                     .layout(info.layout.handle())],
@@ -1419,83 +1597,266 @@ impl Device {
         self.inner.logical.destroy_pipeline_layout(handle, None);
     }
 
+    /// Creates query pool.
+    #[cfg_attr(feature = "tracing", tracing::instrument)]
+    pub fn create_query_pool(&self, info: QueryPoolInfo) -> Result<QueryPool, OutOfMemory> {
+        let mut builder = vk1_0::QueryPoolCreateInfoBuilder::new().query_count(info.count);
+
+        builder = match info.ty {
+            QueryType::Occlusion => builder.query_type(vk1_0::QueryType::OCCLUSION),
+            QueryType::PipelineStatistics(flags) => {
+                assert_ne!(
+                    self.inner.features.v10.pipeline_statistics_query, 0,
+                    "`PipelineStatisticsQuery` feature must be enabled to create pipeline statistics queries"
+                );
+
+                builder
+                    .query_type(vk1_0::QueryType::PIPELINE_STATISTICS)
+                    .pipeline_statistics(flags.to_erupt())
+            }
+            QueryType::Timestamp => builder.query_type(vk1_0::QueryType::TIMESTAMP),
+            QueryType::AccelerationStructureCompactedSize => {
+                assert!(
+                    self.inner.logical.enabled().khr_acceleration_structure,
+                    "`AccelerationStructure` feature is not enabled"
+                );
+                builder.query_type(vk1_0::QueryType::ACCELERATION_STRUCTURE_COMPACTED_SIZE_KHR)
+            }
+            QueryType::AccelerationStructureSerializationSize => {
+                assert!(
+                    self.inner.logical.enabled().khr_acceleration_structure,
+                    "`AccelerationStructure` feature is not enabled"
+                );
+                builder.query_type(vk1_0::QueryType::ACCELERATION_STRUCTURE_SERIALIZATION_SIZE_KHR)
+            }
+        };
+
+        let query_pool = unsafe { self.inner.logical.create_query_pool(&builder, None) }
+            .result()
+            .map_err(oom_error_from_erupt)?;
+
+        let index = self.inner.query_pools.lock().insert(query_pool);
+
+        debug!("Query pool created {:p}", query_pool);
+        Ok(QueryPool::new(info, self.downgrade(), query_pool, index))
+    }
+
+    pub(super) unsafe fn destroy_query_pool(&self, index: usize) {
+        let handle = self.inner.query_pools.lock().remove(index);
+        self.inner.logical.destroy_query_pool(handle, None);
+    }
+
+    /// Fetches results of the queries from the pool.
+    ///
+    /// Returns `QueryType::values_per_query` values for each query in the range.
+    /// Timestamps are converted to nanoseconds.
+    ///
+    /// If `wait` is `true` this function waits for results to become available.
+    /// Otherwise returns `None` if results of any query are not available yet.
+    #[cfg_attr(feature = "tracing", tracing::instrument)]
+    pub fn get_query_results(
+        &self,
+        pool: &QueryPool,
+        queries: Range<u32>,
+        wait: bool,
+    ) -> Result<Option<Vec<u64>>, QueryError> {
+        assert_owner!(pool, self);
+        assert!(queries.start <= queries.end);
+        assert!(queries.end <= pool.info().count);
+
+        let count = queries.end - queries.start;
+        let values_per_query = pool.info().ty.values_per_query();
+        let stride = u64::from(values_per_query) * 8;
+
+        let mut results = vec![0u64; usize::try_from(count * values_per_query).unwrap()];
+
+        let mut flags = vk1_0::QueryResultFlags::_64;
+        if wait {
+            flags |= vk1_0::QueryResultFlags::WAIT;
+        }
+
+        let result = unsafe {
+            self.inner.logical.get_query_pool_results(
+                pool.handle(),
+                queries.start,
+                count,
+                size_of_val(&results[..]),
+                results.as_mut_ptr().cast(),
+                stride,
+                flags,
+            )
+        };
+
+        match result.raw {
+            vk1_0::Result::SUCCESS => {}
+            vk1_0::Result::NOT_READY => return Ok(None),
+            vk1_0::Result::ERROR_OUT_OF_HOST_MEMORY => out_of_host_memory(),
+            vk1_0::Result::ERROR_OUT_OF_DEVICE_MEMORY => return Err(OutOfMemory.into()),
+            vk1_0::Result::ERROR_DEVICE_LOST => return Err(DeviceLost.into()),
+            err => unexpected_result(err),
+        }
+
+        if let QueryType::Timestamp = pool.info().ty {
+            let period = f64::from(self.inner.properties.v10.limits.timestamp_period);
+            for value in &mut results {
+                *value = (*value as f64 * period) as u64;
+            }
+        }
+
+        Ok(Some(results))
+    }
+
     /// Creates render pass.
     #[cfg_attr(feature = "tracing", tracing::instrument)]
     pub fn create_render_pass(
         &self,
         info: RenderPassInfo,
     ) -> Result<RenderPass, CreateRenderPassError> {
-        let mut subpass_attachments = Vec::new();
+        for (si, s) in info.subpasses.iter().enumerate() {
+            for (ci, &(c, _)) in s.colors.iter().enumerate() {
+                if !arith_lt(c, info.attachments.len()) {
+                    return Err(CreateRenderPassError::ColorAttachmentReferenceOutOfBound {
+                        subpass: si,
+                        index: ci,
+                        attachment: c,
+                    });
+                }
+            }
 
-        let subpasses =
-            info.subpasses
-                .iter()
-                .enumerate()
-                .map(|(si, s)| -> Result<_, CreateRenderPassError> {
-                    let color_offset = subpass_attachments.len();
-                    subpass_attachments.extend(
-                        s.colors
-                            .iter()
-                            .enumerate()
-                            .map(|(ci, &(c, cl))| -> Result<_, CreateRenderPassError> {
-                                Ok(vk1_0::AttachmentReferenceBuilder::new()
-                                .attachment(if arith_le(c, info.attachments.len()) {
-                                    Some(c)
-                                } else {
-                                    None
-                                }
-                                .ok_or(
-                                    CreateRenderPassError::ColorAttachmentReferenceOutOfBound {
-                                        subpass: si,
-                                        index: ci,
-                                        attachment: c,
-                                    }
-                                )?)
-                                .layout(cl.to_erupt())
-                            )
-                            })
-                            .collect::<Result<SmallVec<[_; 16]>, _>>()?,
-                    );
+            if let Some((d, _)) = s.depth {
+                if !arith_lt(d, info.attachments.len()) {
+                    return Err(CreateRenderPassError::DepthAttachmentReferenceOutOfBound {
+                        subpass: si,
+                        attachment: d,
+                    });
+                }
+            }
+
+            for (ii, &(i, _)) in s.inputs.iter().enumerate() {
+                if !arith_lt(i, info.attachments.len()) {
+                    return Err(CreateRenderPassError::InputAttachmentReferenceOutOfBound {
+                        subpass: si,
+                        index: ii,
+                        attachment: i,
+                    });
+                }
+            }
+
+            if !s.resolves.is_empty() && s.resolves.len() != s.colors.len() {
+                return Err(CreateRenderPassError::ResolveAttachmentCountMismatch {
+                    subpass: si,
+                    colors: s.colors.len(),
+                    resolves: s.resolves.len(),
+                });
+            }
 
-                    let depth_offset = subpass_attachments.len();
-                    if let Some((d, dl)) = s.depth {
-                        subpass_attachments.push(
-                            vk1_0::AttachmentReferenceBuilder::new()
-                                .attachment(
-                                    if arith_le(d, info.attachments.len()) {
-                                        Some(d)
-                                    } else {
-                                        None
-                                    }
-                                    .ok_or(
-                                        CreateRenderPassError::DepthAttachmentReferenceOutOfBound {
-                                            subpass: si,
-                                            attachment: d,
-                                        },
-                                    )?,
-                                )
-                                .layout(dl.to_erupt()),
+            for (ri, r) in s.resolves.iter().enumerate() {
+                if let Some((r, _)) = *r {
+                    if !arith_lt(r, info.attachments.len()) {
+                        return Err(
+                            CreateRenderPassError::ResolveAttachmentReferenceOutOfBound {
+                                subpass: si,
+                                index: ri,
+                                attachment: r,
+                            },
                         );
                     }
-                    Ok((color_offset, depth_offset))
-                })
-                .collect::<Result<SmallVec<[_; 16]>, _>>()?;
+                }
+            }
+
+            if let Some(r) = &s.depth_resolve {
+                if !arith_lt(r.attachment, info.attachments.len()) {
+                    return Err(
+                        CreateRenderPassError::DepthResolveAttachmentReferenceOutOfBound {
+                            subpass: si,
+                            attachment: r.attachment,
+                        },
+                    );
+                }
+            }
+        }
+
+        let depth_resolve = info
+            .subpasses
+            .iter()
+            .position(|s| s.depth_resolve.is_some());
+
+        let render_pass = if let Some(subpass) = depth_resolve {
+            if vk1_0::make_api_version(0, 1, 2, 0) > self.inner.version {
+                return Err(CreateRenderPassError::DepthResolveNotSupported { subpass });
+            }
+
+            self.create_render_pass2(&info)
+        } else {
+            self.create_render_pass1(&info)
+        }
+        .map_err(create_render_pass_error_from_erupt)?;
+
+        let index = self.inner.render_passes.lock().insert(render_pass);
+
+        debug!("Render pass created: {:p}", render_pass);
+        Ok(RenderPass::new(info, self.downgrade(), render_pass, index))
+    }
+
+    pub(super) unsafe fn destroy_render_pass(&self, index: usize) {
+        let handle = self.inner.render_passes.lock().remove(index);
+        self.inner.logical.destroy_render_pass(handle, None);
+    }
+
+    fn create_render_pass1(
+        &self,
+        info: &RenderPassInfo,
+    ) -> Result<vk1_0::RenderPass, vk1_0::Result> {
+        let reference = |(index, layout): (u32, Layout)| {
+            vk1_0::AttachmentReferenceBuilder::new()
+                .attachment(index)
+                .layout(layout.to_erupt())
+        };
+
+        let mut references = Vec::new();
+
+        let offsets = info
+            .subpasses
+            .iter()
+            .map(|s| {
+                let inputs = references.len();
+                references.extend(s.inputs.iter().copied().map(reference));
+                let colors = references.len();
+                references.extend(s.colors.iter().copied().map(reference));
+                let resolves = references.len();
+                references.extend(s.resolves.iter().map(|r| {
+                    match *r {
+                        Some(r) => reference(r),
+                        None => vk1_0::AttachmentReferenceBuilder::new()
+                            .attachment(vk1_0::ATTACHMENT_UNUSED),
+                    }
+                }));
+                let depth = references.len();
+                references.extend(s.depth.map(reference));
+                (inputs, colors, resolves, depth)
+            })
+            .collect::<SmallVec<[_; 16]>>();
 
         let subpasses = info
             .subpasses
             .iter()
-            .zip(subpasses)
-            .map(|(s, (color_offset, depth_offset))| {
-                let builder = vk1_0::SubpassDescriptionBuilder::new()
-                    .color_attachments(&subpass_attachments[color_offset..depth_offset]);
+            .zip(offsets)
+            .map(|(s, (inputs, colors, resolves, depth))| {
+                let mut builder = vk1_0::SubpassDescriptionBuilder::new()
+                    .input_attachments(&references[inputs..colors])
+                    .color_attachments(&references[colors..resolves]);
+
+                if !s.resolves.is_empty() {
+                    builder = builder.resolve_attachments(&references[resolves..depth]);
+                }
 
                 if s.depth.is_some() {
-                    builder.depth_stencil_attachment(&subpass_attachments[depth_offset])
-                } else {
-                    builder
+                    builder = builder.depth_stencil_attachment(&references[depth]);
                 }
+
+                builder
             })
-            .collect::<Vec<_>>();
+            .collect::<SmallVec<[_; 16]>>();
 
         let attachments = info
             .attachments
@@ -1507,7 +1868,7 @@ impl Device {
                     .store_op(a.store_op.to_erupt())
                     .initial_layout(a.initial_layout.to_erupt())
                     .final_layout(a.final_layout.to_erupt())
-                    .samples(vk1_0::SampleCountFlagBits::_1)
+                    .samples(a.samples.to_erupt())
             })
             .collect::<SmallVec<[_; 16]>>();
 
@@ -1530,23 +1891,138 @@ impl Device {
             .subpasses(&subpasses)
             .dependencies(&dependencies);
 
-        let render_pass = unsafe {
+        unsafe {
             self.inner
                 .logical
                 .create_render_pass(&render_passs_create_info, None)
         }
         .result()
-        .map_err(create_render_pass_error_from_erupt)?;
+    }
 
-        let index = self.inner.render_passes.lock().insert(render_pass);
+    fn create_render_pass2(
+        &self,
+        info: &RenderPassInfo,
+    ) -> Result<vk1_0::RenderPass, vk1_0::Result> {
+        let reference = |(index, layout): (u32, Layout)| {
+            vk1_2::AttachmentReference2Builder::new()
+                .attachment(index)
+                .layout(layout.to_erupt())
+        };
 
-        debug!("Render pass created: {:p}", render_pass);
-        Ok(RenderPass::new(info, self.downgrade(), render_pass, index))
-    }
+        let mut references = Vec::new();
 
-    pub(super) unsafe fn destroy_render_pass(&self, index: usize) {
-        let handle = self.inner.render_passes.lock().remove(index);
-        self.inner.logical.destroy_render_pass(handle, None);
+        let offsets = info
+            .subpasses
+            .iter()
+            .map(|s| {
+                let inputs = references.len();
+                references.extend(s.inputs.iter().map(|&(index, layout)| {
+                    let aspect = info
+                        .attachments
+                        .get(index as usize)
+                        .map_or(AspectFlags::empty(), |a| a.format.aspect_flags());
+                    reference((index, layout)).aspect_mask(aspect.to_erupt())
+                }));
+                let colors = references.len();
+                references.extend(s.colors.iter().copied().map(reference));
+                let resolves = references.len();
+                references.extend(s.resolves.iter().map(|r| {
+                    match *r {
+                        Some(r) => reference(r),
+                        None => vk1_2::AttachmentReference2Builder::new()
+                            .attachment(vk1_0::ATTACHMENT_UNUSED),
+                    }
+                }));
+                let depth = references.len();
+                references.extend(s.depth.map(reference));
+                let depth_resolve = references.len();
+                references.extend(s.depth_resolve.map(|r| reference((r.attachment, r.layout))));
+                (inputs, colors, resolves, depth, depth_resolve)
+            })
+            .collect::<SmallVec<[_; 16]>>();
+
+        let mut depth_resolves = info
+            .subpasses
+            .iter()
+            .zip(&offsets)
+            .map(|(s, &(_, _, _, _, depth_resolve))| {
+                s.depth_resolve.map(|r| {
+                    vk1_2::SubpassDescriptionDepthStencilResolveBuilder::new()
+                        .depth_resolve_mode(r.depth_mode.to_erupt())
+                        .stencil_resolve_mode(r.stencil_mode.to_erupt())
+                        .depth_stencil_resolve_attachment(&references[depth_resolve])
+                })
+            })
+            .collect::<SmallVec<[_; 16]>>();
+
+        let subpasses = info
+            .subpasses
+            .iter()
+            .zip(&offsets)
+            .zip(&mut depth_resolves)
+            .map(
+                |((s, &(inputs, colors, resolves, depth, _)), depth_resolve)| {
+                    let mut builder = vk1_2::SubpassDescription2Builder::new()
+                        .pipeline_bind_point(vk1_0::PipelineBindPoint::GRAPHICS)
+                        .input_attachments(&references[inputs..colors])
+                        .color_attachments(&references[colors..resolves]);
+
+                    if !s.resolves.is_empty() {
+                        builder = builder.resolve_attachments(&references[resolves..depth]);
+                    }
+
+                    if s.depth.is_some() {
+                        builder = builder.depth_stencil_attachment(&references[depth]);
+                    }
+
+                    if let Some(depth_resolve) = depth_resolve {
+                        builder = builder.extend_from(depth_resolve);
+                    }
+
+                    builder
+                },
+            )
+            .collect::<SmallVec<[_; 16]>>();
+
+        let attachments = info
+            .attachments
+            .iter()
+            .map(|a| {
+                vk1_2::AttachmentDescription2Builder::new()
+                    .format(a.format.to_erupt())
+                    .load_op(a.load_op.to_erupt())
+                    .store_op(a.store_op.to_erupt())
+                    .initial_layout(a.initial_layout.to_erupt())
+                    .final_layout(a.final_layout.to_erupt())
+                    .samples(a.samples.to_erupt())
+            })
+            .collect::<SmallVec<[_; 16]>>();
+
+        let dependencies = info
+            .dependencies
+            .iter()
+            .map(|d| {
+                vk1_2::SubpassDependency2Builder::new()
+                    .src_subpass(d.src.unwrap_or(vk1_0::SUBPASS_EXTERNAL))
+                    .dst_subpass(d.dst.unwrap_or(vk1_0::SUBPASS_EXTERNAL))
+                    .src_stage_mask(d.src_stages.to_erupt())
+                    .dst_stage_mask(d.dst_stages.to_erupt())
+                    .src_access_mask(supported_access(d.src_stages.to_erupt()))
+                    .dst_access_mask(supported_access(d.dst_stages.to_erupt()))
+            })
+            .collect::<SmallVec<[_; 16]>>();
+
+        let render_passs_create_info = vk1_2::RenderPassCreateInfo2Builder::new()
+            .attachments(&attachments)
+            .subpasses(&subpasses)
+            .dependencies(&dependencies);
+
+        unsafe {
+            self.inner
+                .logical
+                .create_render_pass2(&render_passs_create_info, None)
+        }
+        .result()
     }
 
     pub(crate) fn create_semaphore_raw(&self) -> Result<(vk1_0::Semaphore, usize), vk1_0::Result> {
@@ -1571,6 +2047,36 @@ impl Device {
         Ok(Semaphore::new(self.downgrade(), handle, index))
     }
 
+    /// Creates timeline semaphore with specified initial counter value.
+    #[cfg_attr(feature = "tracing", tracing::instrument)]
+    pub fn create_timeline_semaphore(
+        &self,
+        initial_value: u64,
+    ) -> Result<TimelineSemaphore, OutOfMemory> {
+        assert_ne!(
+            self.inner.features.v12.timeline_semaphore, 0,
+            "`TimelineSemaphore` feature is not enabled"
+        );
+
+        let mut type_info = vk1_2::SemaphoreTypeCreateInfoBuilder::new()
+            .semaphore_type(vk1_2::SemaphoreType::TIMELINE)
+            .initial_value(initial_value);
+
+        let handle = unsafe {
+            self.inner.logical.create_semaphore(
+                &vk1_0::SemaphoreCreateInfoBuilder::new().extend_from(&mut type_info),
+                None,
+            )
+        }
+        .result()
+        .map_err(oom_error_from_erupt)?;
+
+        let index = self.inner.semaphores.lock().insert(handle);
+
+        debug!("Timeline semaphore created: {:p}", handle);
+        Ok(TimelineSemaphore::new(self.downgrade(), handle, index))
+    }
+
     pub(super) unsafe fn destroy_semaphore(&self, index: usize) {
         let handle = self.inner.semaphores.lock().remove(index);
         self.inner.logical.destroy_semaphore(handle, None);
@@ -1585,7 +2091,10 @@ impl Device {
         #[allow(unused)]
         let spv: Vec<u32>;
 
-        #[cfg(feature = "naga")]
+        #[cfg(feature = "hlsl")]
+        let hlsl_artifact: shaderc::CompilationArtifact;
+
+        #[cfg(any(feature = "glsl", feature = "wgsl", feature = "spirv-validation"))]
         let naga_caps = |inner: &Inner| {
             let mut caps = naga::valid::Capabilities::PUSH_CONSTANT;
 
@@ -1629,29 +2138,22 @@ impl Device {
 
             #[cfg(feature = "glsl")]
             ShaderLanguage::GLSL { stage } => {
-                let stage = match stage {
-                    ShaderStage::Vertex => naga::ShaderStage::Vertex,
-                    ShaderStage::Fragment => naga::ShaderStage::Fragment,
-                    ShaderStage::Compute => naga::ShaderStage::Compute,
-                    _ => {
-                        return Err(CreateShaderModuleError::UnsupportedShaderLanguage {
-                            language: info.language,
-                        })
-                    }
-                };
+                let naga_stage = naga_glsl_stage(stage)
+                    .ok_or(CreateShaderModuleError::UnsupportedGlslStage { stage })?;
 
                 let code = std::str::from_utf8(&info.code)?;
+                let files = DiagnosticFiles::new("source.glsl", code, info.source_map.as_ref());
 
                 let module = naga::front::glsl::Parser::default()
                     .parse(
                         &naga::front::glsl::Options {
-                            stage,
-                            defines: Default::default(),
+                            stage: naga_stage,
+                            defines: info.naga_defines(),
                         },
                         code,
                     )
                     .map_err(|errors| {
-                        emit_glsl_parser_error(&errors, "source.glsl", code);
+                        emit_glsl_parser_error(&errors, &files);
                         CreateShaderModuleError::NagaGlslParseError { errors }
                     })?;
 
@@ -1661,7 +2163,7 @@ impl Device {
                 )
                 .validate(&module)
                 .map_err(|err| {
-                    emit_annotated_error(&err, "source.glsl", code);
+                    emit_annotated_error(&err, &files);
                     err
                 })?;
 
@@ -1677,8 +2179,9 @@ impl Device {
             #[cfg(feature = "wgsl")]
             ShaderLanguage::WGSL => {
                 let code = std::str::from_utf8(&info.code)?;
+                let files = DiagnosticFiles::new("source.wgsl", code, info.source_map.as_ref());
                 let module = naga::front::wgsl::parse_str(code).map_err(|err| {
-                    err.emit_to_stderr("source.wgsl");
+                    emit_wgsl_parser_error(&err, &files);
                     CreateShaderModuleError::NagaWgslParseError {
                         source: Box::from(err.emit_to_string(".")),
                     }
@@ -1689,7 +2192,7 @@ impl Device {
                 )
                 .validate(&module)
                 .map_err(|err| {
-                    emit_annotated_error(&err, "source.wgsl", code);
+                    emit_annotated_error(&err, &files);
                     err
                 })?;
 
@@ -1702,8 +2205,55 @@ impl Device {
 
                 bytemuck::cast_slice(&spv)
             }
-            #[allow(unreachable_patterns)]
-            _ => {
+            #[cfg(feature = "hlsl")]
+            ShaderLanguage::HLSL { stage } => {
+                let code = std::str::from_utf8(&info.code)?;
+
+                let env_version = if self.inner.version >= vk1_0::make_api_version(0, 1, 2, 0) {
+                    shaderc::EnvVersion::Vulkan1_2
+                } else if self.inner.version >= vk1_0::make_api_version(0, 1, 1, 0) {
+                    shaderc::EnvVersion::Vulkan1_1
+                } else {
+                    shaderc::EnvVersion::Vulkan1_0
+                };
+
+                let init_error = || CreateShaderModuleError::HlslCompileError {
+                    message: "Failed to initialize shaderc".to_owned(),
+                };
+
+                let mut compiler = shaderc::Compiler::new().ok_or_else(init_error)?;
+                let mut options = shaderc::CompileOptions::new().ok_or_else(init_error)?;
+                options.set_source_language(shaderc::SourceLanguage::HLSL);
+                options.set_target_env(shaderc::TargetEnv::Vulkan, env_version as u32);
+                for (name, value) in &info.defines {
+                    options.add_macro_definition(name, Some(value));
+                }
+
+                hlsl_artifact = compiler
+                    .compile_into_spirv(
+                        code,
+                        shaderc_shader_kind(stage),
+                        "source.hlsl",
+                        &info.entry,
+                        Some(&options),
+                    )
+                    .map_err(|err| {
+                        let message = err.to_string();
+                        error!("Failed to compile HLSL shader. {}", message);
+                        CreateShaderModuleError::HlslCompileError { message }
+                    })?;
+
+                if hlsl_artifact.get_num_warnings() > 0 {
+                    warn!(
+                        "HLSL shader compiled with warnings. {}",
+                        hlsl_artifact.get_warning_messages()
+                    );
+                }
+
+                hlsl_artifact.as_binary_u8()
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
                 return Err(CreateShaderModuleError::UnsupportedShaderLanguage {
                     language: info.language,
                 })
@@ -1733,6 +2283,11 @@ impl Device {
             });
         }
 
+        #[cfg(feature = "spirv-validation")]
+        if info.language == ShaderLanguage::SPIRV {
+            validate_spirv(code, naga_caps(&self.inner))?;
+        }
+
         let mut aligned_code;
 
         let is_aligned = code.as_ptr() as usize & 3 == 0;
@@ -1766,7 +2321,7 @@ impl Device {
         };
 
         let module = unsafe {
-            // FIXME: It is still required to validate SPIR-V.
+            // SPIR-V is validated only with `spirv-validation` feature.
             // Othewise adheres to valid usage described in spec.
             self.inner.logical.create_shader_module(
                 &vk1_0::ShaderModuleCreateInfoBuilder::new().code(code_slice),
@@ -1800,7 +2355,29 @@ impl Device {
         let display = display.raw_display_handle();
 
         let surface = self.graphics().create_surface(window, display)?;
-        Surface::new(surface, SurfaceInfo { window, display }, self)
+        Surface::new(surface, SurfaceInfo::Window { window, display }, self)
+    }
+
+    /// Creates surface that is not backed by any window.
+    ///
+    /// Frame loops written against [`Surface`] work with it unchanged.
+    /// Swapchain is emulated if `VK_EXT_headless_surface` or `Feature::SurfacePresentation`
+    /// is not available or [`HeadlessSurfaceInfo::readback`] is set.
+    /// Emulated swapchain signals acquire semaphores of its images on the `queue`
+    /// before they are acquired for the first time.
+    #[cfg_attr(feature = "tracing", tracing::instrument)]
+    pub fn create_headless_surface(
+        &self,
+        info: HeadlessSurfaceInfo,
+        queue: &mut Queue,
+    ) -> Result<Surface, CreateSurfaceError> {
+        if !info.readback && self.inner.logical.enabled().khr_swapchain {
+            if let Some(surface) = self.graphics().create_headless_surface()? {
+                return Surface::new(surface, SurfaceInfo::Headless(info), self);
+            }
+        }
+
+        Surface::new_emulated(info, self, queue)
     }
 
     pub(super) fn insert_swapchain(&self, swapchain: vksw::SwapchainKHR) -> usize {
@@ -1940,6 +2517,118 @@ impl Device {
         }
     }
 
+    /// Returns current counter value of the timeline semaphore.
+    #[cfg_attr(feature = "tracing", tracing::instrument)]
+    pub fn get_semaphore_value(&self, semaphore: &TimelineSemaphore) -> Result<u64, DeviceLost> {
+        assert_owner!(semaphore, *self);
+
+        match unsafe {
+            self.inner
+                .logical
+                .get_semaphore_counter_value(semaphore.handle())
+        }
+        .result()
+        {
+            Ok(value) => {
+                for (queue, epoch) in semaphore.reached(value) {
+                    self.inner.epochs.close_epoch(queue, epoch);
+                }
+                Ok(value)
+            }
+            Err(vk1_0::Result::ERROR_DEVICE_LOST) => Err(DeviceLost),
+            Err(result) => unexpected_result(result),
+        }
+    }
+
+    /// Sets counter value of the timeline semaphore from host.
+    /// Value must be greater than current counter value
+    /// and less than values signalled by pending submissions.
+    #[cfg_attr(feature = "tracing", tracing::instrument)]
+    pub fn signal_semaphore(
+        &self,
+        semaphore: &TimelineSemaphore,
+        value: u64,
+    ) -> Result<(), OutOfMemory> {
+        assert_owner!(semaphore, *self);
+
+        unsafe {
+            self.inner.logical.signal_semaphore(
+                &vk1_2::SemaphoreSignalInfoBuilder::new()
+                    .semaphore(semaphore.handle())
+                    .value(value),
+            )
+        }
+        .result()
+        .map_err(oom_error_from_erupt)
+    }
+
+    /// Wait for timeline semaphores to reach specified counter values.
+    /// If `all` is `true` - waits for all specified semaphores.
+    /// Otherwise waits for at least one of specified semaphores.
+    #[cfg_attr(feature = "tracing", tracing::instrument)]
+    pub fn wait_semaphores(
+        &self,
+        semaphores: &[(&TimelineSemaphore, u64)],
+        all: bool,
+    ) -> Result<(), DeviceLost> {
+        if semaphores.is_empty() {
+            assert!(
+                all,
+                "Cannot use empty semaphores array in `Device::wait_semaphores` with `all == false` as it would wait forever."
+            );
+            return Ok(());
+        }
+
+        for (semaphore, _) in semaphores {
+            assert_owner!(semaphore, self);
+        }
+
+        let handles = semaphores
+            .iter()
+            .map(|(semaphore, _)| semaphore.handle())
+            .collect::<SmallVec<[_; 16]>>();
+
+        let values = semaphores
+            .iter()
+            .map(|(_, value)| *value)
+            .collect::<SmallVec<[_; 16]>>();
+
+        let flags = if all {
+            vk1_2::SemaphoreWaitFlags::empty()
+        } else {
+            vk1_2::SemaphoreWaitFlags::ANY
+        };
+
+        match unsafe {
+            self.inner.logical.wait_semaphores(
+                &vk1_2::SemaphoreWaitInfoBuilder::new()
+                    .flags(flags)
+                    .semaphores(&handles)
+                    .values(&values),
+                !0,
+            )
+        }
+        .result()
+        {
+            Ok(()) => {
+                if all || semaphores.len() == 1 {
+                    for (semaphore, value) in semaphores {
+                        for (queue, epoch) in semaphore.reached(*value) {
+                            self.inner.epochs.close_epoch(queue, epoch);
+                        }
+                    }
+                } else {
+                    for (semaphore, _) in semaphores {
+                        self.get_semaphore_value(semaphore)?;
+                    }
+                }
+                Ok(())
+            }
+            Err(vk1_0::Result::ERROR_DEVICE_LOST) => Err(DeviceLost),
+            Err(result) => unexpected_result(result),
+        }
+    }
+
     /// Wait for whole device to become idle. That is, wait for all pending
     /// operations to complete. This is equivalent to calling
     /// `Queue::wait_idle` for all queues. Typically used only before device
@@ -2132,6 +2821,22 @@ impl Device {
             .destroy_acceleration_structure_khr(handle, None);
     }
 
+    /// Returns required alignment of scratch memory address
+    /// for acceleration structure build operations.
+    pub fn get_acceleration_structure_scratch_alignment(&self) -> u64 {
+        assert!(
+            self.inner.logical.enabled().khr_acceleration_structure,
+            "`AccelerationStructure` feature is not enabled"
+        );
+
+        u64::from(
+            self.inner
+                .properties
+                .acc
+                .min_acceleration_structure_scratch_offset_alignment,
+        )
+    }
+
     /// Returns buffers device address.
     #[cfg_attr(feature = "tracing", tracing::instrument)]
     pub fn get_buffer_device_address(&self, buffer: &Buffer) -> Option<DeviceAddress> {
@@ -2158,6 +2863,34 @@ impl Device {
         acceleration_structure.address()
     }
 
+    /// Checks if acceleration structure serialized on another device
+    /// can be deserialized on this device.
+    ///
+    /// `data` must start with `SerializedAccelerationStructureHeader`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(data)))]
+    pub fn is_acceleration_structure_compatible(&self, data: &[u8]) -> bool {
+        assert!(
+            self.inner.logical.enabled().khr_acceleration_structure,
+            "`AccelerationStructure` feature is not enabled"
+        );
+
+        let version_data: &[u8; 32] = match data.get(..32) {
+            Some(version_data) => version_data.try_into().unwrap(),
+            None => return false,
+        };
+
+        let compatibility = unsafe {
+            self.inner
+                .logical
+                .get_device_acceleration_structure_compatibility_khr(
+                    &vkacc::AccelerationStructureVersionInfoKHRBuilder::new()
+                        .version_data(version_data),
+                )
+        };
+
+        compatibility == vkacc::AccelerationStructureCompatibilityKHR::COMPATIBLE_KHR
+    }
+
     /// Creates ray-tracing pipeline.
     #[cfg_attr(feature = "tracing", tracing::instrument)]
     pub fn create_ray_tracing_pipeline(
@@ -2181,16 +2914,30 @@ impl Device {
             .map(|shader| entry_name_to_cstr(shader.entry()))
             .collect();
 
-        let mut entries = entries.iter();
+        let specialization_entries: Vec<_> = info
+            .shaders
+            .iter()
+            .map(|shader| specialization_entries(shader.specialization()))
+            .collect();
+
+        let specializations: Vec<_> = info
+            .shaders
+            .iter()
+            .zip(&specialization_entries)
+            .map(|(shader, entries)| specialization_info(entries, shader.specialization()))
+            .collect();
 
         let stages: Vec<_> = info
             .shaders
             .iter()
-            .map(|shader| {
+            .zip(&entries)
+            .zip(&specializations)
+            .map(|((shader, entry), specialization)| {
                 vk1_0::PipelineShaderStageCreateInfoBuilder::new()
                     .stage(shader.stage().to_erupt())
                     .module(shader.module.handle())
-                    .name(entries.next().unwrap())
+                    .name(entry)
+                    .specialization_info(specialization)
             })
             .collect();
 
@@ -2201,15 +2948,7 @@ impl Device {
                 let builder = vkrt::RayTracingShaderGroupCreateInfoKHRBuilder::new();
                 match *group {
                     RayTracingShaderGroupInfo::Raygen { raygen } => {
-                        assert_ne!(raygen, vkrt::SHADER_UNUSED_KHR);
-                        assert_eq!(
-                            usize::try_from(raygen)
-                                .ok()
-                                .and_then(|raygen| info.shaders.get(raygen))
-                                .expect("raygen shader index out of bounds")
-                                .stage(),
-                            ShaderStage::Raygen
-                        );
+                        check_group_shader(&info.shaders, raygen, ShaderStage::Raygen);
 
                         builder
                             ._type(vkrt::RayTracingShaderGroupTypeKHR::GENERAL_KHR)
@@ -2219,15 +2958,7 @@ impl Device {
                             .intersection_shader(vkrt::SHADER_UNUSED_KHR)
                     }
                     RayTracingShaderGroupInfo::Miss { miss } => {
-                        assert_ne!(miss, vkrt::SHADER_UNUSED_KHR);
-                        assert_eq!(
-                            usize::try_from(miss)
-                                .ok()
-                                .and_then(|miss| info.shaders.get(miss))
-                                .expect("miss shader index out of bounds")
-                                .stage(),
-                            ShaderStage::Miss
-                        );
+                        check_group_shader(&info.shaders, miss, ShaderStage::Miss);
 
                         builder
                             ._type(vkrt::RayTracingShaderGroupTypeKHR::GENERAL_KHR)
@@ -2236,31 +2967,25 @@ impl Device {
                             .closest_hit_shader(vkrt::SHADER_UNUSED_KHR)
                             .intersection_shader(vkrt::SHADER_UNUSED_KHR)
                     }
+                    RayTracingShaderGroupInfo::Callable { callable } => {
+                        check_group_shader(&info.shaders, callable, ShaderStage::Callable);
+
+                        builder
+                            ._type(vkrt::RayTracingShaderGroupTypeKHR::GENERAL_KHR)
+                            .general_shader(callable)
+                            .any_hit_shader(vkrt::SHADER_UNUSED_KHR)
+                            .closest_hit_shader(vkrt::SHADER_UNUSED_KHR)
+                            .intersection_shader(vkrt::SHADER_UNUSED_KHR)
+                    }
                     RayTracingShaderGroupInfo::Triangles {
                         any_hit,
                         closest_hit,
                     } => {
                         if let Some(any_hit) = any_hit {
-                            assert_ne!(any_hit, vkrt::SHADER_UNUSED_KHR);
-                            assert_eq!(
-                                usize::try_from(any_hit)
-                                    .ok()
-                                    .and_then(|any_hit| info.shaders.get(any_hit))
-                                    .expect("any_hit shader index out of bounds")
-                                    .stage(),
-                                ShaderStage::AnyHit
-                            );
+                            check_group_shader(&info.shaders, any_hit, ShaderStage::AnyHit);
                         }
                         if let Some(closest_hit) = closest_hit {
-                            assert_ne!(closest_hit, vkrt::SHADER_UNUSED_KHR);
-                            assert_eq!(
-                                usize::try_from(closest_hit)
-                                    .ok()
-                                    .and_then(|closest_hit| info.shaders.get(closest_hit))
-                                    .expect("closest_hit shader index out of bounds")
-                                    .stage(),
-                                ShaderStage::ClosestHit
-                            );
+                            check_group_shader(&info.shaders, closest_hit, ShaderStage::ClosestHit);
                         }
 
                         builder
@@ -2270,14 +2995,36 @@ impl Device {
                             .closest_hit_shader(closest_hit.unwrap_or(vkrt::SHADER_UNUSED_KHR))
                             .intersection_shader(vkrt::SHADER_UNUSED_KHR)
                     }
+                    RayTracingShaderGroupInfo::Procedural {
+                        intersection,
+                        any_hit,
+                        closest_hit,
+                    } => {
+                        check_group_shader(&info.shaders, intersection, ShaderStage::Intersection);
+                        if let Some(any_hit) = any_hit {
+                            check_group_shader(&info.shaders, any_hit, ShaderStage::AnyHit);
+                        }
+                        if let Some(closest_hit) = closest_hit {
+                            check_group_shader(&info.shaders, closest_hit, ShaderStage::ClosestHit);
+                        }
+
+                        builder
+                            ._type(vkrt::RayTracingShaderGroupTypeKHR::PROCEDURAL_HIT_GROUP_KHR)
+                            .general_shader(vkrt::SHADER_UNUSED_KHR)
+                            .any_hit_shader(any_hit.unwrap_or(vkrt::SHADER_UNUSED_KHR))
+                            .closest_hit_shader(closest_hit.unwrap_or(vkrt::SHADER_UNUSED_KHR))
+                            .intersection_shader(intersection)
+                    }
                 }
             })
             .collect();
 
+        let pipeline_cache = self.inner.pipeline_cache.lock().clone();
+
         let handles = unsafe {
             self.inner.logical.create_ray_tracing_pipelines_khr(
                 vkdho::DeferredOperationKHR::null(),
-                vk1_0::PipelineCache::null(),
+                pipeline_cache_handle(&pipeline_cache),
                 &[vkrt::RayTracingPipelineCreateInfoKHRBuilder::new()
                     .stages(&stages)
                     .groups(&groups)
@@ -2867,110 +3614,129 @@ impl Device {
     ) -> Result<ShaderBindingTable, OutOfMemory> {
         assert_owner!(pipeline, self);
 
-        let group_size = u64::from(self.inner.properties.rt.shader_group_handle_size);
-        let group_align = u64::from(self.inner.properties.rt.shader_group_base_alignment - 1);
+        let groups = &pipeline.info().groups;
+        let group = |index: u32| {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| groups.get(index))
+                .expect("shader group index out of bounds")
+        };
 
-        let group_count_usize =
-            info.raygen.is_some() as usize + info.miss.len() + info.hit.len() + info.callable.len();
+        if let Some(raygen) = &info.raygen {
+            assert!(
+                matches!(
+                    group(raygen.group),
+                    RayTracingShaderGroupInfo::Raygen { .. }
+                ),
+                "raygen region must reference raygen group"
+            );
+        }
+        for miss in info.miss {
+            assert!(
+                matches!(group(miss.group), RayTracingShaderGroupInfo::Miss { .. }),
+                "miss region must reference miss groups"
+            );
+        }
+        for hit in info.hit {
+            assert!(
+                group(hit.group).is_hit(),
+                "hit region must reference hit groups"
+            );
+        }
+        for callable in info.callable {
+            assert!(
+                matches!(
+                    group(callable.group),
+                    RayTracingShaderGroupInfo::Callable { .. }
+                ),
+                "callable region must reference callable groups"
+            );
+        }
 
-        let group_count = u32::try_from(group_count_usize).map_err(|_| OutOfMemory)?;
+        let rt = &self.inner.properties.rt;
+        let group_size = u64::from(rt.shader_group_handle_size);
+        let group_align = u64::from(rt.shader_group_handle_alignment - 1);
+        let base_align = u64::from(rt.shader_group_base_alignment - 1);
+        let max_stride = u64::from(rt.max_shader_group_stride);
+
+        let raygen = info.raygen.as_slice();
+        let regions = [raygen, info.miss, info.hit, info.callable];
+
+        // Each region starts at base alignment and has own stride
+        // large enough for handle and biggest inline data in the region.
+        let mut layouts = [(0, 0); 4];
+        let mut total_size = 0;
+        for (layout, records) in layouts.iter_mut().zip(&regions) {
+            let data_size = records.iter().map(|r| r.data.len()).max().unwrap_or(0);
+            let data_size = u64::try_from(data_size).map_err(|_| OutOfMemory)?;
+            let record_size = group_size.checked_add(data_size).ok_or(OutOfMemory)?;
+            let stride = align_up(group_align, record_size).ok_or(OutOfMemory)?;
 
-        let group_stride = align_up(group_align, group_size).ok_or(OutOfMemory)?;
+            assert!(
+                records.is_empty() || stride <= max_stride,
+                "shader record stride {} exceeds device limit {}",
+                stride,
+                max_stride
+            );
 
-        let group_stride_usize = usize::try_from(group_stride).map_err(|_| OutOfMemory)?;
+            let offset = align_up(base_align, total_size).ok_or(OutOfMemory)?;
+            let count = u64::try_from(records.len()).map_err(|_| OutOfMemory)?;
+            let size = stride.checked_mul(count).ok_or(OutOfMemory)?;
 
-        let total_size = (group_stride.checked_mul(u64::from(group_count))).ok_or(OutOfMemory)?;
+            total_size = offset.checked_add(size).ok_or(OutOfMemory)?;
+            *layout = (offset, stride);
+        }
 
         let total_size_usize = usize::try_from(total_size).unwrap_or_else(|_| out_of_host_memory());
 
         let mut bytes = vec![0; total_size_usize];
 
-        let mut write_offset = 0;
-
         let group_handlers = pipeline.group_handlers();
 
-        let raygen_handlers = copy_group_handlers(
-            group_handlers,
-            &mut bytes,
-            info.raygen.iter().copied(),
-            &mut write_offset,
-            group_size,
-            group_stride_usize,
-        );
-
-        let miss_handlers = copy_group_handlers(
-            group_handlers,
-            &mut bytes,
-            info.miss.iter().copied(),
-            &mut write_offset,
-            group_size,
-            group_stride_usize,
-        );
-
-        let hit_handlers = copy_group_handlers(
-            group_handlers,
-            &mut bytes,
-            info.hit.iter().copied(),
-            &mut write_offset,
-            group_size,
-            group_stride_usize,
-        );
-
-        let callable_handlers = copy_group_handlers(
-            group_handlers,
-            &mut bytes,
-            info.callable.iter().copied(),
-            &mut write_offset,
-            group_size,
-            group_stride_usize,
-        );
+        for (&(offset, stride), records) in layouts.iter().zip(&regions) {
+            write_shader_records(
+                group_handlers,
+                &mut bytes,
+                records,
+                offset,
+                group_size,
+                stride,
+            )
+            .ok_or(OutOfMemory)?;
+        }
 
         let buffer = self.create_buffer_static(
             BufferInfo {
-                align: group_align,
+                align: base_align,
                 size: total_size,
                 usage: BufferUsage::SHADER_BINDING_TABLE | BufferUsage::DEVICE_ADDRESS,
             },
             &bytes,
         )?;
 
+        let region = |index: usize| {
+            let records = regions[index];
+            let (offset, stride) = layouts[index];
+            if records.is_empty() {
+                None
+            } else {
+                Some(StridedBufferRange {
+                    range: BufferRange {
+                        buffer: buffer.clone(),
+                        offset,
+                        size: stride * records.len() as u64,
+                    },
+                    stride,
+                })
+            }
+        };
+
         debug!("ShaderBindingTable created");
         Ok(ShaderBindingTable {
-            raygen: raygen_handlers.map(|range| StridedBufferRange {
-                range: BufferRange {
-                    buffer: buffer.clone(),
-                    offset: range.start,
-                    size: range.end - range.start,
-                },
-                stride: group_stride,
-            }),
-
-            miss: miss_handlers.map(|range| StridedBufferRange {
-                range: BufferRange {
-                    buffer: buffer.clone(),
-                    offset: range.start,
-                    size: range.end - range.start,
-                },
-                stride: group_stride,
-            }),
-
-            hit: hit_handlers.map(|range| StridedBufferRange {
-                range: BufferRange {
-                    buffer: buffer.clone(),
-                    offset: range.start,
-                    size: range.end - range.start,
-                },
-                stride: group_stride,
-            }),
-
-            callable: callable_handlers.map(|range| StridedBufferRange {
-                range: BufferRange {
-                    buffer: buffer.clone(),
-                    offset: range.start,
-                    size: range.end - range.start,
-                },
-                stride: group_stride,
-            }),
+            raygen: region(0),
+            miss: region(1),
+            hit: region(2),
+            callable: region(3),
         })
     }
 
@@ -3058,39 +3824,86 @@ fn check() {
     assert_object::<Device>();
 }
 
+fn specialization_entries(
+    specialization: &Specialization,
+) -> SmallVec<[vk1_0::SpecializationMapEntryBuilder<'static>; 8]> {
+    specialization
+        .entries
+        .iter()
+        .map(|entry| {
+            assert!(
+                entry.offset as usize + entry.size <= specialization.data.len(),
+                "Specialization constant {} is out of data bounds",
+                entry.id
+            );
+
+            vk1_0::SpecializationMapEntryBuilder::new()
+                .constant_id(entry.id)
+                .offset(entry.offset)
+                .size(entry.size)
+        })
+        .collect()
+}
+
+fn specialization_info<'a>(
+    entries: &'a [vk1_0::SpecializationMapEntryBuilder<'static>],
+    specialization: &'a Specialization,
+) -> vk1_0::SpecializationInfoBuilder<'a> {
+    vk1_0::SpecializationInfoBuilder::new()
+        .map_entries(entries)
+        .data_size(specialization.data.len())
+        .data(specialization.data.as_ptr().cast())
+}
+
 fn entry_name_to_cstr(name: &str) -> CString {
     CString::new(name.as_bytes()).expect("Shader names should not contain zero bytes")
 }
 
-fn copy_group_handlers(
+/// Checks that shader group references existing shader of expected stage.
+fn check_group_shader(shaders: &[Shader], index: u32, stage: ShaderStage) {
+    assert_ne!(index, vkrt::SHADER_UNUSED_KHR);
+    assert_eq!(
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| shaders.get(index))
+            .unwrap_or_else(|| panic!("{} shader index out of bounds", stage))
+            .stage(),
+        stage,
+    );
+}
+
+fn pipeline_cache_handle(cache: &Option<PipelineCache>) -> vk1_0::PipelineCache {
+    cache
+        .as_ref()
+        .map_or(vk1_0::PipelineCache::null(), PipelineCache::handle)
+}
+
+fn write_shader_records(
     group_handlers: &[u8],
     write: &mut [u8],
-    group_indices: impl IntoIterator<Item = u32>,
-    write_offset: &mut usize,
+    records: &[ShaderRecord<'_>],
+    offset: u64,
     group_size: u64,
-    group_stride: usize,
-) -> Option<Range<u64>> {
-    let result_start = u64::try_from(*write_offset).ok()?;
-    let group_size_usize = usize::try_from(group_size).ok()?;
-
-    for group_index in group_indices {
-        let group_offset = (group_size_usize.checked_mul(usize::try_from(group_index).ok()?))?;
+    stride: u64,
+) -> Option<()> {
+    let group_size = usize::try_from(group_size).ok()?;
+    let stride = usize::try_from(stride).ok()?;
+    let mut write_offset = usize::try_from(offset).ok()?;
 
-        let group_end = group_offset.checked_add(group_size_usize)?;
-        let write_end = write_offset.checked_add(group_size_usize)?;
+    for record in records {
+        let group_offset = group_size.checked_mul(usize::try_from(record.group).ok()?)?;
+        let handler = group_handlers.get(group_offset..group_offset.checked_add(group_size)?)?;
 
-        let group_range = group_offset..group_end;
-        let write_range = *write_offset..write_end;
+        let data_offset = write_offset.checked_add(group_size)?;
+        let data_end = data_offset.checked_add(record.data.len())?;
 
-        let handler = &group_handlers[group_range];
-        let output = &mut write[write_range];
+        write[write_offset..data_offset].copy_from_slice(handler);
+        write[data_offset..data_end].copy_from_slice(record.data);
 
-        output.copy_from_slice(handler);
-        *write_offset = write_offset.checked_add(group_stride)?;
+        write_offset = write_offset.checked_add(stride)?;
     }
 
-    let result_end = u64::try_from(*write_offset).ok()?;
-    Some(result_start..result_end)
+    Some(())
 }
 
 pub(crate) fn create_render_pass_error_from_erupt(err: vk1_0::Result) -> CreateRenderPassError {
@@ -3159,46 +3972,283 @@ pub(super) fn descriptor_count_from_bindings(
     result
 }
 
-#[cfg(feature = "glsl")]
-fn emit_glsl_parser_error(errors: &[naga::front::glsl::Error], filename: &str, source: &str) {
-    let files = SimpleFile::new(filename, source);
-    let config = codespan_reporting::term::Config::default();
-    let writer = StandardStream::stderr(ColorChoice::Auto);
+/// Source files for diagnostics.
+/// Maps spans in expanded source to included files when source map is available.
+#[cfg(any(feature = "glsl", feature = "wgsl"))]
+struct DiagnosticFiles<'a> {
+    files: SimpleFiles<&'a str, &'a str>,
+    source_map: Option<&'a SourceMap>,
+    expanded: usize,
+}
 
-    for err in errors {
-        let mut diagnostic = Diagnostic::error().with_message(err.kind.to_string());
+#[cfg(any(feature = "glsl", feature = "wgsl"))]
+impl<'a> DiagnosticFiles<'a> {
+    fn new(filename: &'a str, source: &'a str, source_map: Option<&'a SourceMap>) -> Self {
+        let mut files = SimpleFiles::new();
 
-        if let Some(range) = err.meta.to_range() {
-            diagnostic = diagnostic.with_labels(vec![Label::primary((), range)]);
+        if let Some(source_map) = source_map {
+            for file in &source_map.files {
+                files.add(&*file.name, &*file.code);
+            }
+        }
+
+        // Spans not covered by source map are reported in expanded source.
+        let expanded = files.add(filename, source);
+
+        DiagnosticFiles {
+            files,
+            source_map,
+            expanded,
         }
+    }
+
+    fn label(&self, range: Range<usize>) -> Label<usize> {
+        match self.source_map.and_then(|map| map.locate(range.clone())) {
+            Some((file, range)) => Label::primary(file, range),
+            None => Label::primary(self.expanded, range),
+        }
+    }
 
+    fn emit(&self, diagnostic: &Diagnostic<usize>) {
+        let config = codespan_reporting::term::Config::default();
+        let writer = StandardStream::stderr(ColorChoice::Auto);
         let mut writer = writer.lock();
 
-        if let Err(err) = codespan_reporting::term::emit(&mut writer, &config, &files, &diagnostic)
+        if let Err(err) =
+            codespan_reporting::term::emit(&mut writer, &config, &self.files, diagnostic)
         {
             error!("Failed to print annotated error. {:#}", err);
         }
     }
 }
 
-#[cfg(any(feature = "glsl", feature = "wgsl"))]
-fn emit_annotated_error<E: std::error::Error>(ann_err: &WithSpan<E>, filename: &str, source: &str) {
-    let files = SimpleFile::new(filename, source);
-    let config = codespan_reporting::term::Config::default();
-    let writer = StandardStream::stderr(ColorChoice::Auto);
-
-    let diagnostic = Diagnostic::error().with_labels(
-        ann_err
-            .spans()
-            .map(|(span, desc)| {
-                Label::primary((), span.to_range().unwrap()).with_message(desc.to_owned())
-            })
+#[cfg(feature = "glsl")]
+fn emit_glsl_parser_error(errors: &[naga::front::glsl::Error], files: &DiagnosticFiles<'_>) {
+    for err in errors {
+        let mut diagnostic = Diagnostic::error().with_message(err.kind.to_string());
+
+        if let Some(range) = err.meta.to_range() {
+            diagnostic = diagnostic.with_labels(vec![files.label(range)]);
+        }
+
+        files.emit(&diagnostic);
+    }
+}
+
+#[cfg(feature = "wgsl")]
+fn emit_wgsl_parser_error(err: &naga::front::wgsl::ParseError, files: &DiagnosticFiles<'_>) {
+    let diagnostic = Diagnostic::error().with_message(err.message()).with_labels(
+        err.labels()
+            .map(|(range, desc)| files.label(range).with_message(desc.to_owned()))
             .collect(),
     );
 
-    let mut writer = writer.lock();
+    files.emit(&diagnostic);
+}
+
+#[cfg(any(feature = "glsl", feature = "wgsl"))]
+fn emit_annotated_error<E: std::error::Error>(ann_err: &WithSpan<E>, files: &DiagnosticFiles<'_>) {
+    let diagnostic = Diagnostic::error()
+        .with_message(ann_err.as_inner().to_string())
+        .with_labels(
+            ann_err
+                .spans()
+                .filter_map(|(span, desc)| {
+                    Some(files.label(span.to_range()?).with_message(desc.to_owned()))
+                })
+                .collect(),
+        );
+
+    files.emit(&diagnostic);
+}
+
+/// Parses and validates SPIR-V module with naga.
+/// Naga does not support every SPIR-V capability, instruction and stage.
+/// Such modules cannot be validated and are passed to the driver as is.
+#[cfg(feature = "spirv-validation")]
+fn validate_spirv(code: &[u8], caps: naga::valid::Capabilities) -> Result<(), InvalidShader> {
+    let result = naga::front::spv::parse_u8_slice(
+        code,
+        &naga::front::spv::Options {
+            adjust_coordinate_space: false,
+            strict_capabilities: false,
+            block_ctx_dump_prefix: None,
+        },
+    );
+
+    let module = match result {
+        Ok(module) => module,
+        Err(err) if is_unsupported_spirv(&err) => {
+            warn!("SPIR-V module cannot be validated. {}", error_chain(&err));
+            return Ok(());
+        }
+        Err(err) => {
+            let message = error_chain(&err);
+            error!("Failed to parse SPIR-V module. {}", message);
+            return Err(InvalidShader::MalformedSpirv { message });
+        }
+    };
+
+    naga::valid::Validator::new(naga::valid::ValidationFlags::all(), caps)
+        .validate(&module)
+        .map_err(|err| {
+            let message = error_chain(&err);
+            error!("SPIR-V module failed validation. {}", message);
+            InvalidShader::ValidationFailed { message }
+        })?;
+
+    Ok(())
+}
+
+/// Checks if error is caused by SPIR-V feature naga does not support
+/// rather than by malformed module.
+#[cfg(feature = "spirv-validation")]
+fn is_unsupported_spirv(err: &naga::front::spv::Error) -> bool {
+    use naga::front::spv::Error;
+
+    matches!(
+        err,
+        Error::UnknownInstruction(_)
+            | Error::UnknownCapability(_)
+            | Error::UnknownBinaryOperator(_)
+            | Error::UnknownRelationalFunction(_)
+            | Error::UnsupportedInstruction(..)
+            | Error::UnsupportedCapability(_)
+            | Error::UnsupportedExtension(_)
+            | Error::UnsupportedExtSet(_)
+            | Error::UnsupportedExtInstSet(_)
+            | Error::UnsupportedExtInst(_)
+            | Error::UnsupportedType(_)
+            | Error::UnsupportedExecutionModel(_)
+            | Error::UnsupportedExecutionMode(_)
+            | Error::UnsupportedStorageClass(_)
+            | Error::UnsupportedImageDim(_)
+            | Error::UnsupportedImageFormat(_)
+            | Error::UnsupportedBuiltIn(_)
+            | Error::UnsupportedControlFlow(_)
+            | Error::UnsupportedBinaryOperator(_)
+            | Error::UnsupportedRuntimeArrayStorageClass
+            | Error::UnsupportedMatrixStride { .. }
+    )
+}
+
+/// Formats error with all its sources.
+#[cfg(feature = "spirv-validation")]
+fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        message += ": ";
+        message += &err.to_string();
+        source = err.source();
+    }
+    message
+}
+
+/// Checks if cube views can be created for the image.
+fn is_cube_compatible(info: &ImageInfo) -> bool {
+    match info.extent {
+        ImageExtent::D2 { width, height } => {
+            width == height && info.layers >= 6 && info.samples == Samples::Samples1
+        }
+        _ => false,
+    }
+}
+
+#[cfg(all(test, feature = "spirv-validation"))]
+mod tests {
+    use super::*;
+
+    /// Compute shader with empty `main`.
+    fn module() -> Vec<u32> {
+        vec![
+            0x0723_0203,
+            0x0001_0000,
+            0,
+            5,
+            0,
+            // OpCapability Shader
+            (2 << 16) | 17,
+            1,
+            // OpMemoryModel Logical GLSL450
+            (3 << 16) | 14,
+            0,
+            1,
+            // OpEntryPoint GLCompute %3 "main"
+            (5 << 16) | 15,
+            5,
+            3,
+            u32::from_le_bytes(*b"main"),
+            0,
+            // OpExecutionMode %3 LocalSize 1 1 1
+            (6 << 16) | 16,
+            3,
+            17,
+            1,
+            1,
+            1,
+            // %1 = OpTypeVoid
+            (2 << 16) | 19,
+            1,
+            // %2 = OpTypeFunction %1
+            (3 << 16) | 33,
+            2,
+            1,
+            // %3 = OpFunction %1 None %2
+            (5 << 16) | 54,
+            1,
+            3,
+            0,
+            2,
+            // %4 = OpLabel
+            (2 << 16) | 248,
+            4,
+            // OpReturn
+            (1 << 16) | 253,
+            // OpFunctionEnd
+            (1 << 16) | 56,
+        ]
+    }
+
+    fn validate(words: &[u32]) -> Result<(), InvalidShader> {
+        validate_spirv(
+            bytemuck::cast_slice(words),
+            naga::valid::Capabilities::empty(),
+        )
+    }
+
+    #[test]
+    fn valid_module() {
+        assert_eq!(validate(&module()), Ok(()));
+    }
+
+    #[test]
+    fn truncated_module() {
+        let words = module();
+        assert!(matches!(
+            validate(&words[..words.len() - 3]),
+            Err(InvalidShader::MalformedSpirv { .. })
+        ));
+    }
+
+    #[test]
+    fn bad_magic() {
+        let mut words = module();
+        words[0] = 0xdead_beef;
+        assert!(matches!(
+            validate(&words),
+            Err(InvalidShader::MalformedSpirv { .. })
+        ));
+    }
 
-    if let Err(err) = codespan_reporting::term::emit(&mut writer, &config, &files, &diagnostic) {
-        error!("Failed to print annotated error. {:#}", err);
+    #[test]
+    fn zero_workgroup_size() {
+        let mut words = module();
+        // First dimension of `LocalSize`.
+        words[18] = 0;
+        assert!(matches!(
+            validate(&words),
+            Err(InvalidShader::ValidationFailed { .. })
+        ));
     }
 }
@@ -15,6 +15,7 @@ use crate::{
     encode::*,
     format::Format,
     format::{Channels, FormatDescription, Type},
+    query::QueryType,
     queue::QueueId,
     render_pass::{ClearValue, LoadOp},
     IndexType, OutOfMemory,
@@ -157,6 +158,9 @@ impl CommandBuffer {
                     )
                 }
             }
+            Command::NextSubpass => unsafe {
+                logical.cmd_next_subpass(self.handle, vk1_0::SubpassContents::INLINE)
+            },
             Command::EndRenderPass => unsafe { logical.cmd_end_render_pass(self.handle) },
             Command::BindGraphicsPipeline { pipeline } => unsafe {
                 assert_owner!(pipeline, device);
@@ -204,6 +208,98 @@ impl CommandBuffer {
                     instances.start,
                 )
             },
+            Command::DrawIndirect {
+                buffer,
+                offset,
+                draw_count,
+                stride,
+            } => unsafe {
+                assert_owner!(buffer, device);
+                assert!(
+                    draw_count <= 1 || device.features().v10.multi_draw_indirect != 0,
+                    "MultiDrawIndirect feature is not enabled"
+                );
+                references.add_buffer(buffer.clone());
+
+                logical.cmd_draw_indirect(self.handle, buffer.handle(), offset, draw_count, stride)
+            },
+            Command::DrawIndexedIndirect {
+                buffer,
+                offset,
+                draw_count,
+                stride,
+            } => unsafe {
+                assert_owner!(buffer, device);
+                assert!(
+                    draw_count <= 1 || device.features().v10.multi_draw_indirect != 0,
+                    "MultiDrawIndirect feature is not enabled"
+                );
+                references.add_buffer(buffer.clone());
+
+                logical.cmd_draw_indexed_indirect(
+                    self.handle,
+                    buffer.handle(),
+                    offset,
+                    draw_count,
+                    stride,
+                )
+            },
+            Command::DrawIndirectCount {
+                buffer,
+                offset,
+                count_buffer,
+                count_buffer_offset,
+                max_draw_count,
+                stride,
+            } => unsafe {
+                assert_owner!(buffer, device);
+                assert_owner!(count_buffer, device);
+                assert_ne!(
+                    device.features().v12.draw_indirect_count,
+                    0,
+                    "DrawIndirectCount feature is not enabled"
+                );
+                references.add_buffer(buffer.clone());
+                references.add_buffer(count_buffer.clone());
+
+                logical.cmd_draw_indirect_count(
+                    self.handle,
+                    buffer.handle(),
+                    offset,
+                    count_buffer.handle(),
+                    count_buffer_offset,
+                    max_draw_count,
+                    stride,
+                )
+            },
+            Command::DrawIndexedIndirectCount {
+                buffer,
+                offset,
+                count_buffer,
+                count_buffer_offset,
+                max_draw_count,
+                stride,
+            } => unsafe {
+                assert_owner!(buffer, device);
+                assert_owner!(count_buffer, device);
+                assert_ne!(
+                    device.features().v12.draw_indirect_count,
+                    0,
+                    "DrawIndirectCount feature is not enabled"
+                );
+                references.add_buffer(buffer.clone());
+                references.add_buffer(count_buffer.clone());
+
+                logical.cmd_draw_indexed_indirect_count(
+                    self.handle,
+                    buffer.handle(),
+                    offset,
+                    count_buffer.handle(),
+                    count_buffer_offset,
+                    max_draw_count,
+                    stride,
+                )
+            },
             Command::SetViewport { viewport } => unsafe {
                 // FIXME: Check that bound pipeline has dynamic viewport
                 // state.
@@ -508,6 +604,113 @@ impl CommandBuffer {
                 )
             },
 
+            Command::WriteAccelerationStructuresProperties {
+                acceleration_structures,
+                pool,
+                first_query,
+            } => {
+                assert!(
+                    device.logical().enabled().khr_acceleration_structure,
+                    "`AccelerationStructure` feature is not enabled"
+                );
+                assert_owner!(pool, device);
+                references.add_query_pool(pool.clone());
+
+                let handles = scope.to_scope_from_iter(acceleration_structures.iter().map(
+                    |&acceleration_structure| {
+                        assert_owner!(acceleration_structure, device);
+                        references.add_acceleration_strucutre(acceleration_structure.clone());
+                        acceleration_structure.handle()
+                    },
+                ));
+
+                let query_type = match pool.info().ty {
+                    QueryType::AccelerationStructureCompactedSize => {
+                        vk1_0::QueryType::ACCELERATION_STRUCTURE_COMPACTED_SIZE_KHR
+                    }
+                    QueryType::AccelerationStructureSerializationSize => {
+                        vk1_0::QueryType::ACCELERATION_STRUCTURE_SERIALIZATION_SIZE_KHR
+                    }
+                    ty => panic!(
+                        "Query pool of type `AccelerationStructureCompactedSize` or `AccelerationStructureSerializationSize` expected, found `{:?}`",
+                        ty
+                    ),
+                };
+
+                unsafe {
+                    logical.cmd_write_acceleration_structures_properties_khr(
+                        self.handle,
+                        handles,
+                        query_type,
+                        pool.handle(),
+                        first_query,
+                    )
+                }
+            }
+            Command::CopyAccelerationStructure { src, dst, mode } => {
+                assert!(
+                    device.logical().enabled().khr_acceleration_structure,
+                    "`AccelerationStructure` feature is not enabled"
+                );
+                assert_owner!(src, device);
+                assert_owner!(dst, device);
+                references.add_acceleration_strucutre(src.clone());
+                references.add_acceleration_strucutre(dst.clone());
+
+                unsafe {
+                    logical.cmd_copy_acceleration_structure_khr(
+                        self.handle,
+                        &vkacc::CopyAccelerationStructureInfoKHRBuilder::new()
+                            .src(src.handle())
+                            .dst(dst.handle())
+                            .mode(mode.to_erupt()),
+                    )
+                }
+            }
+            Command::SerializeAccelerationStructure { src, dst } => {
+                assert!(
+                    device.logical().enabled().khr_acceleration_structure,
+                    "`AccelerationStructure` feature is not enabled"
+                );
+                assert_owner!(src, device);
+                assert_owner!(dst.buffer, device);
+                references.add_acceleration_strucutre(src.clone());
+
+                let dst = vkacc::DeviceOrHostAddressKHR {
+                    device_address: unsafe {
+                        buffer_range_to_device_address(dst, references).device_address
+                    },
+                };
+
+                unsafe {
+                    logical.cmd_copy_acceleration_structure_to_memory_khr(
+                        self.handle,
+                        &vkacc::CopyAccelerationStructureToMemoryInfoKHRBuilder::new()
+                            .src(src.handle())
+                            .dst(dst)
+                            .mode(vkacc::CopyAccelerationStructureModeKHR::SERIALIZE_KHR),
+                    )
+                }
+            }
+            Command::DeserializeAccelerationStructure { src, dst } => {
+                assert!(
+                    device.logical().enabled().khr_acceleration_structure,
+                    "`AccelerationStructure` feature is not enabled"
+                );
+                assert_owner!(src.buffer, device);
+                assert_owner!(dst, device);
+                references.add_acceleration_strucutre(dst.clone());
+
+                unsafe {
+                    logical.cmd_copy_memory_to_acceleration_structure_khr(
+                        self.handle,
+                        &vkacc::CopyMemoryToAccelerationStructureInfoKHRBuilder::new()
+                            .src(buffer_range_to_device_address(src, references))
+                            .dst(dst.handle())
+                            .mode(vkacc::CopyAccelerationStructureModeKHR::DESERIALIZE_KHR),
+                    )
+                }
+            }
             Command::TraceRays {
                 shader_binding_table,
                 extent,
@@ -626,6 +829,30 @@ impl CommandBuffer {
                     ),
                 );
             },
+            Command::CopyImageBuffer {
+                src_image,
+                src_layout,
+                dst_buffer,
+                regions,
+            } => unsafe {
+                assert_owner!(src_image, device);
+                assert_owner!(dst_buffer, device);
+
+                references.add_image(src_image.clone());
+                references.add_buffer(dst_buffer.clone());
+
+                logical.cmd_copy_image_to_buffer(
+                    self.handle,
+                    src_image.handle(),
+                    src_layout.to_erupt(),
+                    dst_buffer.handle(),
+                    scope.to_scope_from_iter(
+                        regions
+                            .iter()
+                            .map(|region| region.to_erupt().into_builder()),
+                    ),
+                );
+            },
 
             Command::BlitImage {
                 src_image,
@@ -754,6 +981,12 @@ impl CommandBuffer {
                 )
             },
             Command::Dispatch { x, y, z } => unsafe { logical.cmd_dispatch(self.handle, x, y, z) },
+            Command::DispatchIndirect { buffer, offset } => unsafe {
+                assert_owner!(buffer, device);
+                references.add_buffer(buffer.clone());
+
+                logical.cmd_dispatch_indirect(self.handle, buffer.handle(), offset)
+            },
 
             Command::BeginRendering { info } => {
                 assert_ne!(
@@ -869,6 +1102,70 @@ impl CommandBuffer {
                     unsafe { logical.cmd_end_rendering_khr(self.handle) }
                 }
             }
+            Command::ResetQueries { pool, queries } => unsafe {
+                assert_owner!(pool, device);
+                references.add_query_pool(pool.clone());
+
+                logical.cmd_reset_query_pool(
+                    self.handle,
+                    pool.handle(),
+                    queries.start,
+                    queries.end - queries.start,
+                );
+            },
+            Command::WriteTimestamp { pool, query, stage } => unsafe {
+                assert_owner!(pool, device);
+                references.add_query_pool(pool.clone());
+
+                logical.cmd_write_timestamp(
+                    self.handle,
+                    vk1_0::PipelineStageFlagBits(stage.to_erupt().bits()),
+                    pool.handle(),
+                    query,
+                );
+            },
+            Command::BeginQuery { pool, query } => unsafe {
+                assert_owner!(pool, device);
+                references.add_query_pool(pool.clone());
+
+                logical.cmd_begin_query(
+                    self.handle,
+                    pool.handle(),
+                    query,
+                    vk1_0::QueryControlFlags::empty(),
+                );
+            },
+            Command::EndQuery { pool, query } => unsafe {
+                assert_owner!(pool, device);
+                references.add_query_pool(pool.clone());
+
+                logical.cmd_end_query(self.handle, pool.handle(), query);
+            },
+            Command::CopyQueryResults {
+                pool,
+                queries,
+                buffer,
+                offset,
+                stride,
+                flags,
+            } => unsafe {
+                assert_owner!(pool, device);
+                assert_owner!(buffer, device);
+
+                references.add_query_pool(pool.clone());
+                references.add_buffer(buffer.clone());
+
+                logical.cmd_copy_query_pool_results(
+                    self.handle,
+                    pool.handle(),
+                    queries.start,
+                    queries.end - queries.start,
+                    buffer.handle(),
+                    offset,
+                    stride,
+                    flags.to_erupt() | vk1_0::QueryResultFlags::_64,
+                );
+            },
         }
     }
 
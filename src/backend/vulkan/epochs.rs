@@ -10,7 +10,7 @@ use super::{
     encode::CommandBuffer,
     resources::{
         AccelerationStructure, Buffer, ComputePipeline, DescriptorSet, Framebuffer,
-        GraphicsPipeline, Image, ImageView, PipelineLayout, RayTracingPipeline, Sampler,
+        GraphicsPipeline, Image, ImageView, PipelineLayout, QueryPool, RayTracingPipeline, Sampler,
     },
 };
 
@@ -149,6 +149,7 @@ pub(super) struct References {
     acceleration_strucutres: Vec<AccelerationStructure>,
     samplers: Vec<Sampler>,
     descriptor_sets: Vec<DescriptorSet>,
+    query_pools: Vec<QueryPool>,
 }
 
 impl References {
@@ -165,6 +166,7 @@ impl References {
             acceleration_strucutres: Vec::new(),
             samplers: Vec::new(),
             descriptor_sets: Vec::new(),
+            query_pools: Vec::new(),
         }
     }
 
@@ -212,6 +214,10 @@ impl References {
         self.descriptor_sets.push(descriptor_set);
     }
 
+    pub fn add_query_pool(&mut self, query_pool: QueryPool) {
+        self.query_pools.push(query_pool);
+    }
+
     pub fn is_empty(&self) -> bool {
         self.buffers.is_empty()
             && self.images.is_empty()
@@ -224,6 +230,7 @@ impl References {
             && self.acceleration_strucutres.is_empty()
             && self.samplers.is_empty()
             && self.descriptor_sets.is_empty()
+            && self.query_pools.is_empty()
     }
 
     pub fn clear(&mut self) {
@@ -238,6 +245,7 @@ impl References {
         self.acceleration_strucutres.clear();
         self.samplers.clear();
         self.descriptor_sets.clear();
+        self.query_pools.clear();
     }
 }
 
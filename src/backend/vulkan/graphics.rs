@@ -11,6 +11,10 @@ use erupt::{
             EXT_DEBUG_REPORT_EXTENSION_NAME,
         },
         ext_debug_utils::EXT_DEBUG_UTILS_EXTENSION_NAME,
+        ext_headless_surface::{
+            HeadlessSurfaceCreateInfoEXTBuilder, EXT_HEADLESS_SURFACE_EXTENSION_NAME,
+        },
+        ext_swapchain_colorspace::EXT_SWAPCHAIN_COLOR_SPACE_EXTENSION_NAME,
         khr_get_physical_device_properties2::KHR_GET_PHYSICAL_DEVICE_PROPERTIES_2_EXTENSION_NAME,
         khr_surface::KHR_SURFACE_EXTENSION_NAME,
     },
@@ -58,7 +62,8 @@ use erupt::{
 
 use crate::{
     out_of_host_memory,
-    physical::EnumerateDeviceError,
+    physical::{DeviceRequirements, DeviceSelection, EnumerateDeviceError, SelectDeviceError},
+    queue::QueuesQuery,
     surface::{CreateSurfaceError, RawWindowHandleKind},
     OutOfMemory, RawDisplayHandleKind,
 };
@@ -214,6 +219,9 @@ impl Graphics {
         }
 
         if push_ext(KHR_SURFACE_EXTENSION_NAME) {
+            push_ext(EXT_HEADLESS_SURFACE_EXTENSION_NAME);
+            push_ext(EXT_SWAPCHAIN_COLOR_SPACE_EXTENSION_NAME);
+
             #[cfg(target_os = "android")]
             {
                 push_ext(KHR_ANDROID_SURFACE_EXTENSION_NAME);
@@ -327,6 +335,65 @@ impl Graphics {
             .collect())
     }
 
+    /// Picks device that satisfies requirements best.
+    /// Returned selection reports enabled optional features and rejected devices.
+    pub fn select_device<Q>(
+        &self,
+        requirements: DeviceRequirements<Q>,
+    ) -> Result<DeviceSelection<Q>, SelectDeviceError>
+    where
+        Q: QueuesQuery + Clone,
+    {
+        let mut devices = self.devices()?;
+        let mut infos = devices.iter().map(PhysicalDevice::info).collect::<Vec<_>>();
+
+        let ranking = requirements.rank(&infos);
+
+        for rejected in &ranking.rejected {
+            debug!("Device '{}' rejected: {}", rejected.name, rejected.reason);
+        }
+
+        match ranking.best {
+            None => Err(SelectDeviceError::NoSuitableDevice {
+                rejected: ranking.rejected,
+            }),
+            Some((index, candidate)) => {
+                let features = requirements.enabled_features(&candidate);
+
+                Ok(DeviceSelection {
+                    physical: devices.swap_remove(index),
+                    info: infos.swap_remove(index),
+                    features,
+                    enabled_optional: candidate.enabled_optional,
+                    rejected: ranking.rejected,
+                    queues: requirements.queues,
+                })
+            }
+        }
+    }
+
+    /// Creates surface with `VK_EXT_headless_surface`.
+    /// Returns `None` if extension is not available.
+    pub(super) fn create_headless_surface(&self) -> Result<Option<SurfaceKHR>, CreateSurfaceError> {
+        if !self.instance.enabled().ext_headless_surface {
+            return Ok(None);
+        }
+
+        let surface = unsafe {
+            self.instance
+                .create_headless_surface_ext(&HeadlessSurfaceCreateInfoEXTBuilder::new(), None)
+        }
+        .result()
+        .map_err(|err| match err {
+            vk1_0::Result::ERROR_OUT_OF_HOST_MEMORY => out_of_host_memory(),
+            vk1_0::Result::ERROR_OUT_OF_DEVICE_MEMORY => CreateSurfaceError::from(OutOfMemory),
+            _ => unexpected_result(err),
+        })?;
+
+        trace!("Headless surface created");
+        Ok(Some(surface))
+    }
+
     pub(super) fn create_surface(
         &self,
         window: RawWindowHandle,
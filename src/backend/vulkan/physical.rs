@@ -3,6 +3,7 @@ use std::{convert::TryInto as _, ffi::CStr};
 use erupt::{
     extensions::{
         ext_descriptor_indexing::EXT_DESCRIPTOR_INDEXING_EXTENSION_NAME,
+        ext_hdr_metadata::EXT_HDR_METADATA_EXTENSION_NAME,
         ext_scalar_block_layout::EXT_SCALAR_BLOCK_LAYOUT_EXTENSION_NAME,
         google_display_timing::GOOGLE_DISPLAY_TIMING_EXTENSION_NAME,
         // khr_16bit_storage::KHR_16BIT_STORAGE_EXTENSION_NAME,
@@ -22,13 +23,20 @@ use hashbrown::HashMap;
 use smallvec::SmallVec;
 
 use crate::{
-    arith_gt, assert_object, out_of_host_memory,
+    arith_gt, assert_object,
+    format::{Format, FormatFeatures},
+    out_of_host_memory,
     physical::*,
     queue::{Family, FamilyInfo, Queue, QueueId, QueuesQuery},
     CreateDeviceError, OutOfMemory,
 };
 
-use super::{convert::from_erupt, device::Device, graphics::Graphics, unexpected_result};
+use super::{
+    convert::{from_erupt, ToErupt as _},
+    device::Device,
+    graphics::Graphics,
+    unexpected_result,
+};
 
 #[derive(Clone, Debug)]
 pub(super) struct Properties {
@@ -439,6 +447,26 @@ impl PhysicalDevice {
             features.push(Feature::DynamicRendering);
         }
 
+        if self.features.v10.pipeline_statistics_query != 0 {
+            features.push(Feature::PipelineStatisticsQuery);
+        }
+
+        if self.features.v10.multi_draw_indirect != 0 {
+            features.push(Feature::MultiDrawIndirect);
+        }
+
+        if self.features.v12.draw_indirect_count != 0 {
+            features.push(Feature::DrawIndirectCount);
+        }
+
+        if self.features.v12.timeline_semaphore != 0 {
+            features.push(Feature::TimelineSemaphore);
+        }
+
+        if self.features.v10.image_cube_array != 0 {
+            features.push(Feature::ImageCubeArray);
+        }
+
         if self.features.acc.acceleration_structure != 0 {
             assert!(features.contains(&Feature::BufferDeviceAddress));
             features.push(Feature::AccelerationStructure);
@@ -462,6 +490,13 @@ impl PhysicalDevice {
             {
                 features.push(Feature::DisplayTiming);
             }
+
+            if self
+                .properties
+                .has_extension(unsafe { CStr::from_ptr(EXT_HDR_METADATA_EXTENSION_NAME) })
+            {
+                features.push(Feature::HdrMetadata);
+            }
         }
 
         DeviceInfo {
@@ -488,6 +523,9 @@ impl PhysicalDevice {
             }
             .to_string_lossy()
             .into_owned(),
+            vendor_id: self.properties.v10.vendor_id,
+            device_id: self.properties.v10.device_id,
+            pipeline_cache_uuid: self.properties.v10.pipeline_cache_uuid,
             features,
             families: self
                 .properties
@@ -501,9 +539,99 @@ impl PhysicalDevice {
                     capabilities: from_erupt(f.queue_flags),
                 })
                 .collect(),
+            limits: self.limits(),
+        }
+    }
+
+    /// Returns limits of this device.
+    pub fn limits(&self) -> DeviceLimits {
+        let limits = &self.properties.v10.limits;
+        let rt = &self.properties.rt;
+
+        DeviceLimits {
+            max_image_dimension_1d: limits.max_image_dimension1_d,
+            max_image_dimension_2d: limits.max_image_dimension2_d,
+            max_image_dimension_3d: limits.max_image_dimension3_d,
+            max_image_dimension_cube: limits.max_image_dimension_cube,
+            max_image_array_layers: limits.max_image_array_layers,
+            max_texel_buffer_elements: limits.max_texel_buffer_elements,
+            max_uniform_buffer_range: limits.max_uniform_buffer_range,
+            max_storage_buffer_range: limits.max_storage_buffer_range,
+            max_push_constants_size: limits.max_push_constants_size,
+            max_memory_allocation_count: limits.max_memory_allocation_count,
+            max_sampler_allocation_count: limits.max_sampler_allocation_count,
+            max_bound_descriptor_sets: limits.max_bound_descriptor_sets,
+            max_per_stage_descriptor_samplers: limits.max_per_stage_descriptor_samplers,
+            max_per_stage_descriptor_uniform_buffers: limits
+                .max_per_stage_descriptor_uniform_buffers,
+            max_per_stage_descriptor_storage_buffers: limits
+                .max_per_stage_descriptor_storage_buffers,
+            max_per_stage_descriptor_sampled_images: limits.max_per_stage_descriptor_sampled_images,
+            max_per_stage_descriptor_storage_images: limits.max_per_stage_descriptor_storage_images,
+            max_per_stage_descriptor_input_attachments: limits
+                .max_per_stage_descriptor_input_attachments,
+            max_per_stage_resources: limits.max_per_stage_resources,
+            max_descriptor_set_samplers: limits.max_descriptor_set_samplers,
+            max_descriptor_set_uniform_buffers: limits.max_descriptor_set_uniform_buffers,
+            max_descriptor_set_uniform_buffers_dynamic: limits
+                .max_descriptor_set_uniform_buffers_dynamic,
+            max_descriptor_set_storage_buffers: limits.max_descriptor_set_storage_buffers,
+            max_descriptor_set_storage_buffers_dynamic: limits
+                .max_descriptor_set_storage_buffers_dynamic,
+            max_descriptor_set_sampled_images: limits.max_descriptor_set_sampled_images,
+            max_descriptor_set_storage_images: limits.max_descriptor_set_storage_images,
+            max_descriptor_set_input_attachments: limits.max_descriptor_set_input_attachments,
+            max_vertex_input_attributes: limits.max_vertex_input_attributes,
+            max_vertex_input_bindings: limits.max_vertex_input_bindings,
+            max_vertex_input_attribute_offset: limits.max_vertex_input_attribute_offset,
+            max_vertex_input_binding_stride: limits.max_vertex_input_binding_stride,
+            max_compute_shared_memory_size: limits.max_compute_shared_memory_size,
+            max_compute_work_group_count: limits.max_compute_work_group_count,
+            max_compute_work_group_invocations: limits.max_compute_work_group_invocations,
+            max_compute_work_group_size: limits.max_compute_work_group_size,
+            max_sampler_anisotropy: limits.max_sampler_anisotropy,
+            max_viewports: limits.max_viewports,
+            max_viewport_dimensions: limits.max_viewport_dimensions,
+            max_framebuffer_width: limits.max_framebuffer_width,
+            max_framebuffer_height: limits.max_framebuffer_height,
+            max_framebuffer_layers: limits.max_framebuffer_layers,
+            max_color_attachments: limits.max_color_attachments,
+            min_texel_buffer_offset_alignment: limits.min_texel_buffer_offset_alignment,
+            min_uniform_buffer_offset_alignment: limits.min_uniform_buffer_offset_alignment,
+            min_storage_buffer_offset_alignment: limits.min_storage_buffer_offset_alignment,
+            optimal_buffer_copy_offset_alignment: limits.optimal_buffer_copy_offset_alignment,
+            optimal_buffer_copy_row_pitch_alignment: limits.optimal_buffer_copy_row_pitch_alignment,
+            non_coherent_atom_size: limits.non_coherent_atom_size,
+            timestamp_compute_and_graphics: limits.timestamp_compute_and_graphics != 0,
+            timestamp_period: limits.timestamp_period,
+            // Filled only when Vulkan 1.1 properties are queried.
+            subgroup_size: Some(self.properties.v11.subgroup_size).filter(|&size| size > 0),
+            ray_tracing: if self.features.rt.ray_tracing_pipeline != 0 {
+                Some(RayTracingLimits {
+                    shader_group_handle_size: rt.shader_group_handle_size,
+                    shader_group_handle_alignment: rt.shader_group_handle_alignment,
+                    shader_group_base_alignment: rt.shader_group_base_alignment,
+                    max_shader_group_stride: rt.max_shader_group_stride,
+                    max_ray_recursion_depth: rt.max_ray_recursion_depth,
+                    max_ray_hit_attribute_size: rt.max_ray_hit_attribute_size,
+                })
+            } else {
+                None
+            },
         }
     }
 
+    /// Returns features supported by this device for the format.
+    pub fn format_properties(&self, format: Format) -> FormatFeatures {
+        let properties = unsafe {
+            self.graphics()
+                .instance
+                .get_physical_device_format_properties(self.physical, format.to_erupt())
+        };
+
+        from_erupt(properties)
+    }
+
     /// Create graphics API device.
     ///
     /// `features` - device will enable specifeid features.
@@ -919,6 +1047,9 @@ impl PhysicalDevice {
         if requested_features.take(Feature::DisplayTiming) {
             push_ext(GOOGLE_DISPLAY_TIMING_EXTENSION_NAME);
         }
+        if requested_features.take(Feature::HdrMetadata) {
+            push_ext(EXT_HDR_METADATA_EXTENSION_NAME);
+        }
         if requested_features.take(Feature::DynamicRendering) {
             assert_ne!(
                 self.features.v13.dynamic_rendering, 0,
@@ -930,6 +1061,48 @@ impl PhysicalDevice {
             include_features_dr = true;
         }
 
+        if requested_features.take(Feature::PipelineStatisticsQuery) {
+            assert_ne!(
+                self.features.v10.pipeline_statistics_query, 0,
+                "Attempt to enable unsupported feature `PipelineStatisticsQuery`"
+            );
+            features2.features.pipeline_statistics_query = 1;
+        }
+
+        if requested_features.take(Feature::MultiDrawIndirect) {
+            assert_ne!(
+                self.features.v10.multi_draw_indirect, 0,
+                "Attempt to enable unsupported feature `MultiDrawIndirect`"
+            );
+            features2.features.multi_draw_indirect = 1;
+        }
+
+        if requested_features.take(Feature::DrawIndirectCount) {
+            assert_ne!(
+                self.features.v12.draw_indirect_count, 0,
+                "Attempt to enable unsupported feature `DrawIndirectCount`"
+            );
+            features12.draw_indirect_count = 1;
+            include_features12 = true;
+        }
+
+        if requested_features.take(Feature::TimelineSemaphore) {
+            assert_ne!(
+                self.features.v12.timeline_semaphore, 0,
+                "Attempt to enable unsupported feature `TimelineSemaphore`"
+            );
+            features12.timeline_semaphore = 1;
+            include_features12 = true;
+        }
+
+        if requested_features.take(Feature::ImageCubeArray) {
+            assert_ne!(
+                self.features.v10.image_cube_array, 0,
+                "Attempt to enable unsupported feature `ImageCubeArray`"
+            );
+            features2.features.image_cube_array = 1;
+        }
+
         device_create_info = device_create_info.enabled_features(&features2.features);
 
         if self.graphics().instance.enabled().vk1_1 {
@@ -1021,10 +1194,13 @@ impl PhysicalDevice {
 
         let version = self.graphics().version;
 
+        let info = self.info();
+
         // Wrap device.
         let device = Device::new(
             logical,
             self.physical,
+            info,
             self.properties,
             Features {
                 v10: features2.features,
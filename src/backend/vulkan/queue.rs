@@ -6,16 +6,24 @@ use erupt::{
         google_display_timing::{PresentTimeGOOGLEBuilder, PresentTimesInfoGOOGLEBuilder},
         khr_swapchain::PresentInfoKHRBuilder,
     },
-    vk1_0, ExtendableFrom, ObjectHandle,
+    vk1_0, vk1_2, ExtendableFrom, ObjectHandle,
 };
 use scoped_arena::Scope;
 
 use crate::{
+    accel::{
+        AccelerationStructure, AccelerationStructureBuildFlags,
+        AccelerationStructureBuildGeometryInfo, AccelerationStructureCopyMode,
+        AccelerationStructureInfo,
+    },
+    access::Access,
+    buffer::{BufferInfo, BufferRange},
     encode::{CommandBuffer, Encoder},
     fence::Fence,
     out_of_host_memory,
+    query::{QueryError, QueryPoolInfo, QueryType},
     queue::*,
-    semaphore::Semaphore,
+    semaphore::SubmitSemaphore,
     stage::PipelineStages,
     DeviceLost, OutOfMemory,
 };
@@ -119,9 +127,9 @@ impl Queue {
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(cbufs)))]
     pub fn submit(
         &mut self,
-        wait: &mut [(PipelineStages, &mut Semaphore)],
+        wait: &mut [(PipelineStages, SubmitSemaphore<'_>)],
         cbufs: impl IntoIterator<Item = CommandBuffer>,
-        signal: &mut [&mut Semaphore],
+        signal: &mut [SubmitSemaphore<'_>],
         mut fence: Option<&mut Fence>,
         scope: &Scope<'_>,
     ) -> Result<(), QueueError> {
@@ -135,33 +143,78 @@ impl Queue {
             handle
         }));
 
-        for (_, semaphore) in wait.iter_mut() {
-            assert_owner!(semaphore, self.device);
+        let mut timeline = false;
+        for semaphore in wait
+            .iter()
+            .map(|(_, semaphore)| semaphore)
+            .chain(signal.iter())
+        {
+            match semaphore {
+                SubmitSemaphore::Binary(semaphore) => {
+                    assert_owner!(semaphore, self.device);
+                }
+                SubmitSemaphore::Timeline(semaphore, _) => {
+                    assert_owner!(semaphore, self.device);
+                    timeline = true;
+                }
+            }
         }
 
-        for semaphore in signal.iter_mut() {
-            assert_owner!(semaphore, self.device);
+        if timeline {
+            assert_ne!(
+                self.device.features().v12.timeline_semaphore,
+                0,
+                "`TimelineSemaphore` feature is not enabled"
+            );
         }
 
-        if let Some(fence) = fence.as_mut() {
-            assert_owner!(fence, self.device);
+        let signal_timeline = signal
+            .iter()
+            .any(|semaphore| matches!(semaphore, SubmitSemaphore::Timeline(..)));
+
+        if fence.is_some() || signal_timeline {
             let epoch = self.device.epochs().next_epoch(self.id);
-            fence.arm(self.id, epoch, &self.device)?;
+
+            if let Some(fence) = fence.as_mut() {
+                assert_owner!(fence, self.device);
+                fence.arm(self.id, epoch, &self.device)?;
+            }
+
+            for semaphore in signal.iter_mut() {
+                if let SubmitSemaphore::Timeline(semaphore, value) = semaphore {
+                    semaphore.arm(*value, self.id, epoch);
+                }
+            }
         }
 
-        // FIXME: Check semaphore states.
+        // FIXME: Check binary semaphore states.
         let wait_stages = scope.to_scope_from_iter(wait.iter().map(|(ps, _)| ps.to_erupt()));
-        let wait_semaphores = scope.to_scope_from_iter(wait.iter().map(|(_, sem)| sem.handle()));
-        let signal_semaphores = scope.to_scope_from_iter(signal.iter().map(|sem| sem.handle()));
+        let wait_semaphores =
+            scope.to_scope_from_iter(wait.iter().map(|(_, sem)| submit_semaphore_handle(sem)));
+        let wait_values =
+            scope.to_scope_from_iter(wait.iter().map(|(_, sem)| submit_semaphore_value(sem)));
+        let signal_semaphores =
+            scope.to_scope_from_iter(signal.iter().map(submit_semaphore_handle));
+        let signal_values = scope.to_scope_from_iter(signal.iter().map(submit_semaphore_value));
+
+        let mut timeline_info = vk1_2::TimelineSemaphoreSubmitInfoBuilder::new()
+            .wait_semaphore_values(&*wait_values)
+            .signal_semaphore_values(&*signal_values);
+
+        let mut info = vk1_0::SubmitInfoBuilder::new()
+            .wait_semaphores(&*wait_semaphores)
+            .wait_dst_stage_mask(&*wait_stages)
+            .signal_semaphores(&*signal_semaphores)
+            .command_buffers(&*handles);
+
+        if timeline {
+            info = info.extend_from(&mut timeline_info);
+        }
 
         let result = unsafe {
             self.device.logical().queue_submit(
                 self.handle,
-                &[vk1_0::SubmitInfoBuilder::new()
-                    .wait_semaphores(&*wait_semaphores)
-                    .wait_dst_stage_mask(&*wait_stages)
-                    .signal_semaphores(&*signal_semaphores)
-                    .command_buffers(&*handles)],
+                &[info],
                 fence.map_or(vk1_0::Fence::null(), |f| f.handle()),
             )
         }
@@ -226,6 +279,12 @@ impl Queue {
     ) -> Result<PresentOk, PresentError> {
         assert_owner!(image, self.device);
 
+        if image.is_emulated() {
+            image.present_emulated(self)?;
+            self.drain_ready_cbufs()?;
+            return Ok(PresentOk::Success);
+        }
+
         if !self.device.logical().enabled().google_display_timing {
             assert!(timing.is_none(), "`DisplayTiming` feature is not enabled");
         }
@@ -290,6 +349,92 @@ impl Queue {
             .map_err(queue_error_from_erupt)
     }
 
+    /// Builds acceleration structure and compacts it.
+    ///
+    /// `info.flags` must contain `AccelerationStructureBuildFlags::ALLOW_COMPACTION`.
+    /// Acceleration structure is built into `info.dst`, then compacted size is queried
+    /// and acceleration structure is copied into new buffer of that size.
+    /// `info.dst` is not needed afterwards and can be dropped.
+    ///
+    /// This function blocks until all submitted work is complete.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(info)))]
+    pub fn build_compacted_acceleration_structure(
+        &mut self,
+        info: &AccelerationStructureBuildGeometryInfo<'_>,
+    ) -> Result<AccelerationStructure, QueueError> {
+        assert!(
+            info.flags
+                .contains(AccelerationStructureBuildFlags::ALLOW_COMPACTION),
+            "`ALLOW_COMPACTION` flag is required to compact acceleration structure"
+        );
+
+        let pool = self.device.create_query_pool(QueryPoolInfo {
+            ty: QueryType::AccelerationStructureCompactedSize,
+            count: 1,
+        })?;
+
+        let mut fence = self.device.create_fence()?;
+
+        let scope = Scope::new();
+        let mut encoder = self.create_encoder(&scope)?;
+        encoder.reset_queries(&pool, 0..1);
+        encoder.build_acceleration_structure(std::slice::from_ref(info));
+        encoder.memory_barrier(
+            PipelineStages::ACCELERATION_STRUCTURE_BUILD,
+            Access::ACCELERATION_STRUCTURE_WRITE,
+            PipelineStages::ACCELERATION_STRUCTURE_BUILD,
+            Access::ACCELERATION_STRUCTURE_READ,
+        );
+        encoder.write_acceleration_structures_properties(&[info.dst], &pool, 0);
+        self.submit(
+            &mut [],
+            Some(encoder.finish()),
+            &mut [],
+            Some(&mut fence),
+            &scope,
+        )?;
+        self.device.wait_fences(&mut [&mut fence], true)?;
+
+        let compacted_size = match self.device.get_query_results(&pool, 0..1, true) {
+            Ok(results) => results.expect("Results must be available after wait")[0],
+            Err(QueryError::OutOfMemory { source }) => return Err(source.into()),
+            Err(QueryError::DeviceLost { source }) => return Err(source.into()),
+        };
+
+        let src_info = info.dst.info();
+        let buffer = self.device.create_buffer(BufferInfo {
+            align: 255,
+            size: compacted_size,
+            usage: src_info.region.buffer.info().usage,
+        })?;
+
+        let compacted = self
+            .device
+            .create_acceleration_structure(AccelerationStructureInfo {
+                level: src_info.level,
+                region: BufferRange::whole(buffer),
+            })?;
+
+        self.device.reset_fences(&mut [&mut fence])?;
+
+        let mut encoder = self.create_encoder(&scope)?;
+        encoder.copy_acceleration_structure(
+            info.dst,
+            &compacted,
+            AccelerationStructureCopyMode::Compact,
+        );
+        self.submit(
+            &mut [],
+            Some(encoder.finish()),
+            &mut [],
+            Some(&mut fence),
+            &scope,
+        )?;
+        self.device.wait_fences(&mut [&mut fence], true)?;
+
+        Ok(compacted)
+    }
+
     fn drain_ready_cbufs(&mut self) -> Result<(), QueueError> {
         let offset = self.cbufs.len();
         self.device.epochs().drain_cbuf(self.id, &mut self.cbufs);
@@ -321,3 +466,17 @@ fn queue_error_from_erupt(result: vk1_0::Result) -> QueueError {
         result => unexpected_result(result),
     }
 }
+
+fn submit_semaphore_handle(semaphore: &SubmitSemaphore<'_>) -> vk1_0::Semaphore {
+    match semaphore {
+        SubmitSemaphore::Binary(semaphore) => semaphore.handle(),
+        SubmitSemaphore::Timeline(semaphore, _) => semaphore.handle(),
+    }
+}
+
+fn submit_semaphore_value(semaphore: &SubmitSemaphore<'_>) -> u64 {
+    match semaphore {
+        SubmitSemaphore::Binary(_) => 0,
+        SubmitSemaphore::Timeline(_, value) => *value,
+    }
+}
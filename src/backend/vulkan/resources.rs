@@ -11,6 +11,7 @@ use std::{
 use erupt::{extensions::khr_acceleration_structure as vkacc, vk1_0, ObjectHandle};
 use gpu_alloc::MemoryBlock;
 use gpu_descriptor::DescriptorTotalCount;
+use parking_lot::Mutex;
 
 use super::device::{Device, WeakDevice};
 
@@ -24,6 +25,7 @@ use crate::{
     pipeline::{
         ComputePipelineInfo, GraphicsPipelineInfo, PipelineLayoutInfo, RayTracingPipelineInfo,
     },
+    query::QueryPoolInfo,
     queue::QueueId,
     render_pass::RenderPassInfo,
     sampler::SamplerInfo,
@@ -31,7 +33,7 @@ use crate::{
     shader::ShaderModuleInfo,
     view::ImageViewInfo,
     BufferRange, BufferViewInfo, CombinedImageSampler, DescriptorSlice, DescriptorType,
-    DeviceAddress, DeviceLost,
+    DeviceAddress, DeviceLost, OutOfMemory,
 };
 
 use self::resource_counting::{resource_allocated, resource_freed};
@@ -441,6 +443,9 @@ enum ImageFlavor {
     SurfaceImage {
         uid: NonZeroU64,
     },
+    EmulatedSurface {
+        image: Image,
+    },
 }
 
 impl ImageFlavor {
@@ -525,6 +530,10 @@ impl Debug for Image {
                     .field("index", index);
             }
 
+            if let ImageFlavor::EmulatedSurface { image } = &self.inner.flavor {
+                fmt.field("image", image);
+            }
+
             fmt.finish()
         } else {
             write!(fmt, "Image({:p})", self.handle)
@@ -578,6 +587,21 @@ impl Image {
         }
     }
 
+    /// Wraps device image to be used in emulated swapchain.
+    #[inline]
+    pub(super) fn new_emulated_surface(image: Image) -> Self {
+        resource_allocated();
+
+        Image {
+            info: image.info,
+            handle: image.handle,
+            inner: Arc::new(ImageInner {
+                owner: image.inner.owner.clone(),
+                flavor: ImageFlavor::EmulatedSurface { image },
+            }),
+        }
+    }
+
     #[inline]
     pub(super) fn is_owned_by(&self, owner: &impl PartialEq<WeakDevice>) -> bool {
         *owner == self.inner.owner
@@ -589,12 +613,18 @@ impl Image {
         self.handle
     }
 
+    /// Returns true if image belongs to emulated swapchain.
+    #[inline]
+    pub(super) fn is_emulated_surface(&self) -> bool {
+        matches!(self.inner.flavor, ImageFlavor::EmulatedSurface { .. })
+    }
+
     /// Must be called only for retired swapchain.
     #[inline]
     pub(super) fn try_dispose(mut self) -> Result<(), Self> {
         assert!(matches!(
             self.inner.flavor,
-            ImageFlavor::SurfaceImage { .. }
+            ImageFlavor::SurfaceImage { .. } | ImageFlavor::EmulatedSurface { .. }
         ));
         match Arc::try_unwrap(self.inner) {
             Ok(_) => Ok(()),
@@ -958,6 +988,121 @@ impl Semaphore {
     }
 }
 
+/// Handle for GPU timeline semaphore object.
+///
+/// Timeline semaphore has monotonically increasing counter value
+/// that can be signalled and waited upon by both GPU queues and host.
+///
+/// Requires [`Feature::TimelineSemaphore`].
+///
+/// [`Feature::TimelineSemaphore`]: crate::Feature::TimelineSemaphore
+pub struct TimelineSemaphore {
+    handle: vk1_0::Semaphore,
+    owner: WeakDevice,
+    index: usize,
+
+    /// Counter values signalled by pending submissions
+    /// along with epochs of those submissions.
+    /// Guarded so that one submission can wait and signal the same semaphore.
+    pending: Mutex<Vec<(u64, QueueId, u64)>>,
+}
+
+impl Drop for TimelineSemaphore {
+    #[inline]
+    fn drop(&mut self) {
+        resource_freed();
+
+        // TODO: Check there's no pending signal operations.
+        if let Some(device) = self.owner.upgrade() {
+            unsafe { device.destroy_semaphore(self.index) }
+        }
+    }
+}
+
+impl Debug for TimelineSemaphore {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if fmt.alternate() {
+            fmt.debug_struct("TimelineSemaphore")
+                .field("handle", &self.handle)
+                .field("owner", &self.owner)
+                .field("pending", &self.pending.lock().len())
+                .finish()
+        } else {
+            write!(fmt, "TimelineSemaphore({:p})", self.handle)
+        }
+    }
+}
+
+impl PartialEq for TimelineSemaphore {
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        self.handle == rhs.handle
+    }
+}
+
+impl Eq for TimelineSemaphore {}
+
+impl Hash for TimelineSemaphore {
+    #[inline]
+    fn hash<H>(&self, hasher: &mut H)
+    where
+        H: Hasher,
+    {
+        self.handle.hash(hasher)
+    }
+}
+
+impl TimelineSemaphore {
+    #[inline]
+    pub(super) fn new(owner: WeakDevice, handle: vk1_0::Semaphore, index: usize) -> Self {
+        resource_allocated();
+
+        TimelineSemaphore {
+            owner,
+            handle,
+            index,
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    #[inline]
+    pub(super) fn is_owned_by(&self, owner: &impl PartialEq<WeakDevice>) -> bool {
+        *owner == self.owner
+    }
+
+    #[inline]
+    pub(super) fn handle(&self) -> vk1_0::Semaphore {
+        debug_assert!(!self.handle.is_null());
+        self.handle
+    }
+
+    /// Called when submission that signals `value` is made.
+    #[inline]
+    pub(super) fn arm(&self, value: u64, queue: QueueId, epoch: u64) {
+        let mut pending = self.pending.lock();
+        debug_assert!(
+            pending.iter().all(|&(v, _, _)| v < value),
+            "Timeline semaphore must be signalled with increasing values"
+        );
+        pending.push((value, queue, epoch));
+    }
+
+    /// Called when counter value is observed to reach `value`.
+    /// Returns epochs of submissions that are now complete.
+    pub(super) fn reached(&self, value: u64) -> Vec<(QueueId, u64)> {
+        let mut epochs = Vec::new();
+        self.pending.lock().retain(|&(v, queue, epoch)| {
+            if v <= value {
+                epochs.push((queue, epoch));
+                false
+            } else {
+                true
+            }
+        });
+        epochs
+    }
+}
+
 /// Handle to GPU render pass object.
 ///
 /// Render pass defines collection of abstract attachments,
@@ -1233,6 +1378,191 @@ impl Framebuffer {
     }
 }
 
+/// Handle to GPU query pool object.
+///
+/// Query pool holds a fixed number of queries of single [`QueryType`].
+///
+/// [`QueryType`]: crate::QueryType
+#[derive(Clone)]
+pub struct QueryPool {
+    handle: vk1_0::QueryPool,
+    inner: Arc<QueryPoolInner>,
+}
+
+struct QueryPoolInner {
+    info: QueryPoolInfo,
+    owner: WeakDevice,
+    index: usize,
+}
+
+impl Drop for QueryPoolInner {
+    #[inline]
+    fn drop(&mut self) {
+        resource_freed();
+
+        if let Some(device) = self.owner.upgrade() {
+            unsafe { device.destroy_query_pool(self.index) }
+        }
+    }
+}
+
+impl Debug for QueryPool {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if fmt.alternate() {
+            fmt.debug_struct("QueryPool")
+                .field("handle", &self.handle)
+                .field("owner", &self.inner.owner)
+                .finish()
+        } else {
+            write!(fmt, "QueryPool({:p})", self.handle)
+        }
+    }
+}
+
+impl PartialEq for QueryPool {
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        self.handle == rhs.handle
+    }
+}
+
+impl Eq for QueryPool {}
+
+impl Hash for QueryPool {
+    #[inline]
+    fn hash<H>(&self, hasher: &mut H)
+    where
+        H: Hasher,
+    {
+        self.handle.hash(hasher)
+    }
+}
+
+impl QueryPool {
+    #[inline]
+    pub fn info(&self) -> &QueryPoolInfo {
+        &self.inner.info
+    }
+
+    #[inline]
+    pub(super) fn new(
+        info: QueryPoolInfo,
+        owner: WeakDevice,
+        handle: vk1_0::QueryPool,
+        index: usize,
+    ) -> Self {
+        resource_allocated();
+
+        QueryPool {
+            handle,
+            inner: Arc::new(QueryPoolInner { info, owner, index }),
+        }
+    }
+
+    #[inline]
+    pub(super) fn is_owned_by(&self, owner: &impl PartialEq<WeakDevice>) -> bool {
+        *owner == self.inner.owner
+    }
+
+    #[inline]
+    pub(super) fn handle(&self) -> vk1_0::QueryPool {
+        debug_assert!(!self.handle.is_null());
+        self.handle
+    }
+}
+
+/// Handle for pipeline cache object.
+///
+/// Pipeline cache allows reusing results of pipeline compilation
+/// between pipelines and, when saved, between application runs.
+#[derive(Clone)]
+pub struct PipelineCache {
+    handle: vk1_0::PipelineCache,
+    inner: Arc<PipelineCacheInner>,
+}
+
+struct PipelineCacheInner {
+    owner: WeakDevice,
+    index: usize,
+}
+
+impl Drop for PipelineCacheInner {
+    #[inline]
+    fn drop(&mut self) {
+        resource_freed();
+
+        if let Some(device) = self.owner.upgrade() {
+            unsafe { device.destroy_pipeline_cache(self.index) }
+        }
+    }
+}
+
+impl Debug for PipelineCache {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if fmt.alternate() {
+            fmt.debug_struct("PipelineCache")
+                .field("handle", &self.handle)
+                .field("owner", &self.inner.owner)
+                .finish()
+        } else {
+            write!(fmt, "PipelineCache({:p})", self.handle)
+        }
+    }
+}
+
+impl PartialEq for PipelineCache {
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        self.handle == rhs.handle
+    }
+}
+
+impl Eq for PipelineCache {}
+
+impl Hash for PipelineCache {
+    #[inline]
+    fn hash<H>(&self, hasher: &mut H)
+    where
+        H: Hasher,
+    {
+        self.handle.hash(hasher)
+    }
+}
+
+impl PipelineCache {
+    /// Returns data of the cache to save and pass to
+    /// `Device::create_pipeline_cache` later.
+    ///
+    /// Returns empty data if device was already destroyed.
+    pub fn data(&self) -> Result<Vec<u8>, OutOfMemory> {
+        match self.inner.owner.upgrade() {
+            Some(device) => device.get_pipeline_cache_data(self),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    #[inline]
+    pub(super) fn new(owner: WeakDevice, handle: vk1_0::PipelineCache, index: usize) -> Self {
+        resource_allocated();
+
+        PipelineCache {
+            handle,
+            inner: Arc::new(PipelineCacheInner { owner, index }),
+        }
+    }
+
+    #[inline]
+    pub(super) fn is_owned_by(&self, owner: &impl PartialEq<WeakDevice>) -> bool {
+        *owner == self.inner.owner
+    }
+
+    #[inline]
+    pub(super) fn handle(&self) -> vk1_0::PipelineCache {
+        debug_assert!(!self.handle.is_null());
+        self.handle
+    }
+}
+
 /// Handle fot GPU shader module object.
 ///
 /// Shader module is pre-compiled shader program,
@@ -1301,6 +1631,13 @@ impl ShaderModule {
         &self.inner.info
     }
 
+    /// Reflects interface of all entry points in the shader module.
+    #[cfg(feature = "reflect")]
+    #[inline]
+    pub fn reflect(&self) -> Result<crate::ShaderReflection, crate::ReflectError> {
+        self.inner.info.reflect()
+    }
+
     #[inline]
     pub(super) fn new(
         info: ShaderModuleInfo,
@@ -16,6 +16,7 @@ use erupt::{
     vk::SurfaceKHR,
     vk1_0, ObjectHandle,
 };
+use scoped_arena::Scope;
 use smallvec::SmallVec;
 
 use crate::{
@@ -23,14 +24,21 @@ use crate::{
     format::Format,
     image::{Image, ImageInfo, ImageUsage, Samples},
     out_of_host_memory,
-    semaphore::Semaphore,
-    surface::{PresentMode, SurfaceCapabilities, SurfaceError},
-    CreateSurfaceError, DeviceLost, OutOfMemory, PresentationTiming, SurfaceInfo,
+    queue::QueueError,
+    semaphore::{Semaphore, SubmitSemaphore},
+    stage::PipelineStages,
+    surface::{
+        ColorSpace, CompositeAlphaFlags, HdrMetadata, PresentMode, SurfaceCapabilities,
+        SurfaceError, SurfaceTransformFlags,
+    },
+    CreateSurfaceError, DeviceLost, Extent2, HeadlessSurfaceInfo, OutOfMemory, PresentationTiming,
+    SurfaceInfo,
 };
 
 use super::{
     convert::ToErupt as _,
     device::{Device, WeakDevice},
+    queue::Queue,
     unexpected_result,
 };
 static UID: AtomicU64 = AtomicU64::new(1);
@@ -44,6 +52,7 @@ pub struct SurfaceImage<'a> {
     handle: SwapchainKHR,
     supported_families: Arc<[bool]>,
     acquired_counter: &'a AtomicU32,
+    last_presented: &'a AtomicU32,
     index: u32,
     optimal: bool,
 }
@@ -90,8 +99,39 @@ impl SurfaceImage<'_> {
         self.handle
     }
 
+    /// Returns true if image belongs to emulated swapchain.
+    #[inline]
+    pub(super) fn is_emulated(&self) -> bool {
+        self.image.is_emulated_surface()
+    }
+
+    /// Presents image of emulated swapchain.
+    /// Submits empty batch that waits for rendering to finish and signals acquire semaphore
+    /// for the next time image is acquired.
+    pub(super) fn present_emulated(mut self, queue: &mut Queue) -> Result<(), QueueError> {
+        debug_assert!(self.is_emulated());
+
+        let scope = Scope::new();
+        let [acquire, release] = self.wait_signal();
+
+        let result = queue.submit(
+            &mut [(
+                PipelineStages::ALL_COMMANDS,
+                SubmitSemaphore::Binary(release),
+            )],
+            None,
+            &mut [SubmitSemaphore::Binary(acquire)],
+            None,
+            &scope,
+        );
+
+        self.presented();
+        result
+    }
+
     #[inline]
     pub(super) fn presented(self) {
+        self.last_presented.store(self.index, Release);
         self.acquired_counter.fetch_sub(1, Release);
         std::mem::forget(self);
     }
@@ -120,7 +160,10 @@ struct Swapchain {
     index: usize,
     images: Vec<SwapchainImageAndSemaphores>,
     acquired_counter: AtomicU32,
+    last_presented: AtomicU32,
+    emulated: Option<EmulatedRing>,
     format: Format,
+    color_space: ColorSpace,
     usage: ImageUsage,
     mode: PresentMode,
     optimal: bool,
@@ -133,8 +176,13 @@ pub struct Surface {
     swapchain: Option<Swapchain>,
     retired: VecDeque<Swapchain>,
     free_semaphore: Semaphore,
+
+    /// Signalled acquire semaphores for the first emulated swapchain.
+    /// Following emulated swapchains take them from the retired one.
+    emulated_semaphores: Vec<Semaphore>,
     device: WeakDevice,
     surface_capabilities: SurfaceCapabilities,
+    hdr_metadata: Option<HdrMetadata>,
 }
 
 impl Surface {
@@ -165,8 +213,57 @@ impl Surface {
             swapchain: None,
             retired: VecDeque::new(),
             free_semaphore,
+            emulated_semaphores: Vec::new(),
             device: device.downgrade(),
             surface_capabilities,
+            hdr_metadata: None,
+        })
+    }
+
+    /// Creates headless surface with emulated swapchain.
+    /// Acquire semaphores are signalled on the `queue` before first use,
+    /// later they are signalled when image is presented.
+    pub(super) fn new_emulated(
+        info: HeadlessSurfaceInfo,
+        device: &Device,
+        queue: &mut Queue,
+    ) -> Result<Self, CreateSurfaceError> {
+        let surface_capabilities = emulated_capabilities(device, info.extent);
+
+        info!("{:#?}", surface_capabilities);
+
+        let mut emulated_semaphores = (0..EMULATED_IMAGE_COUNT)
+            .map(|_| device.create_semaphore())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let scope = Scope::new();
+        queue
+            .submit(
+                &mut [],
+                None,
+                scope.to_scope_from_iter(
+                    emulated_semaphores.iter_mut().map(SubmitSemaphore::Binary),
+                ),
+                None,
+                &scope,
+            )
+            .map_err(|err| match err {
+                QueueError::OutOfMemory { source } => CreateSurfaceError::from(source),
+                QueueError::DeviceLost { source } => SurfaceError::from(source).into(),
+            })?;
+
+        let free_semaphore = device.create_semaphore()?;
+
+        Ok(Surface {
+            handle: SurfaceKHR::null(),
+            info: SurfaceInfo::Headless(info),
+            swapchain: None,
+            retired: VecDeque::new(),
+            free_semaphore,
+            emulated_semaphores,
+            device: device.downgrade(),
+            surface_capabilities,
+            hdr_metadata: None,
         })
     }
 
@@ -180,6 +277,81 @@ impl Surface {
         &self.surface_capabilities
     }
 
+    /// Returns true if swapchain of this surface is emulated.
+    #[inline]
+    pub fn is_emulated(&self) -> bool {
+        self.handle.is_null()
+    }
+
+    /// Changes extent of headless surface.
+    /// New extent is used after [`Surface::update`] or [`Surface::configure`] call.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if surface is not headless.
+    pub fn resize_headless(&mut self, extent: Extent2) {
+        match &mut self.info {
+            SurfaceInfo::Headless(info) => info.extent = extent,
+            SurfaceInfo::Window { .. } => panic!("Only headless surface can be resized"),
+        }
+
+        if self.is_emulated() {
+            self.surface_capabilities.current_extent = extent;
+        }
+    }
+
+    /// Returns image presented last.
+    ///
+    /// Available only for emulated surfaces.
+    /// Image must not be accessed until presentation submitted to the queue completes
+    /// and stays unchanged until it is acquired again.
+    /// Surface images are created with [`ImageUsage::TRANSFER_SRC`]
+    /// so they can be copied into a buffer.
+    pub fn presented_image(&self) -> Option<&Image> {
+        if !self.is_emulated() {
+            return None;
+        }
+
+        let swapchain = self.swapchain.as_ref()?;
+        let index = swapchain.last_presented.load(Acquire);
+        swapchain
+            .images
+            .get(index as usize)
+            .map(|image_and_semaphores| &image_and_semaphores.image)
+    }
+
+    /// Sets HDR metadata for surface images.
+    /// Metadata is kept and applied again when swapchain is reconfigured.
+    ///
+    /// Requires [`Feature::HdrMetadata`].
+    ///
+    /// [`Feature::HdrMetadata`]: crate::Feature::HdrMetadata
+    pub fn set_hdr_metadata(&mut self, metadata: HdrMetadata) -> Result<(), SurfaceError> {
+        let device = self.device.upgrade().ok_or(SurfaceError::SurfaceLost)?;
+
+        assert!(
+            device.logical().enabled().ext_hdr_metadata,
+            "`HdrMetadata` feature is not enabled"
+        );
+
+        if self.is_emulated() {
+            return Err(SurfaceError::Emulated);
+        }
+
+        self.hdr_metadata = Some(metadata);
+
+        if let Some(swapchain) = &self.swapchain {
+            unsafe {
+                device.logical().set_hdr_metadata_ext(
+                    &[swapchain.handle],
+                    &[metadata.to_erupt().into_builder()],
+                )
+            }
+        }
+
+        Ok(())
+    }
+
     /// Update surface images.
     /// Does nothing if not configured.
     #[cfg_attr(feature = "tracing", tracing::instrument)]
@@ -188,8 +360,9 @@ impl Surface {
         if let Some(swapchain) = &mut self.swapchain {
             let usage = swapchain.usage;
             let format = swapchain.format;
+            let color_space = swapchain.color_space;
             let mode = swapchain.mode;
-            self.configure(usage, format, mode)
+            self.configure(usage, format, color_space, mode)
         } else {
             Ok(())
         }
@@ -200,10 +373,15 @@ impl Surface {
         &mut self,
         usage: ImageUsage,
         format: Format,
+        color_space: ColorSpace,
         mode: PresentMode,
     ) -> Result<(), SurfaceError> {
         let device = self.device.upgrade().ok_or(SurfaceError::SurfaceLost)?;
 
+        if self.is_emulated() {
+            return self.configure_emulated(&device, usage, format, color_space, mode);
+        }
+
         debug_assert!(
             device.logical().enabled().khr_swapchain,
             "Should be enabled given that there is a Swapchain"
@@ -242,6 +420,19 @@ impl Surface {
         self.surface_capabilities = surface_capabilities(instance, device.physical(), surface)?;
         let caps = &self.surface_capabilities;
 
+        // Headless surface may leave extent to be defined by swapchain.
+        let extent = match self.info {
+            SurfaceInfo::Headless(info) if caps.current_extent.width == u32::MAX => Extent2::new(
+                info.extent
+                    .width
+                    .clamp(caps.min_image_extent.width, caps.max_image_extent.width),
+                info.extent
+                    .height
+                    .clamp(caps.min_image_extent.height, caps.max_image_extent.height),
+            ),
+            _ => caps.current_extent,
+        };
+
         if !caps.supported_usage.contains(usage) {
             return Err(SurfaceError::UsageNotSupported { usage });
         }
@@ -253,11 +444,21 @@ impl Surface {
         .map_err(surface_error_from_erupt)?;
 
         let erupt_format = format.to_erupt();
+        let erupt_color_space = color_space.to_erupt();
 
-        let sf = formats
+        let sf = match formats
             .iter()
-            .find(|sf| sf.format == erupt_format)
-            .ok_or(SurfaceError::FormatUnsupported { format })?;
+            .find(|sf| sf.format == erupt_format && sf.color_space == erupt_color_space)
+        {
+            Some(sf) => sf,
+            None if formats.iter().any(|sf| sf.format == erupt_format) => {
+                return Err(SurfaceError::ColorSpaceUnsupported {
+                    format,
+                    color_space,
+                });
+            }
+            None => return Err(SurfaceError::FormatUnsupported { format }),
+        };
 
         let composite_alpha = {
             let raw = caps.supported_composite_alpha.to_erupt().bits();
@@ -304,7 +505,7 @@ impl Surface {
                     .min_image_count(image_count)
                     .image_format(sf.format)
                     .image_color_space(sf.color_space)
-                    .image_extent(caps.current_extent.to_erupt())
+                    .image_extent(extent.to_erupt())
                     .image_array_layers(1)
                     .image_usage(usage.to_erupt())
                     .image_sharing_mode(vk1_0::SharingMode::EXCLUSIVE)
@@ -344,6 +545,15 @@ impl Surface {
                 SurfaceError::OutOfMemory { source: err }
             })?;
 
+        if let Some(metadata) = &self.hdr_metadata {
+            if logical.enabled().ext_hdr_metadata {
+                // Metadata is not inherited from retired swapchain.
+                unsafe {
+                    logical.set_hdr_metadata_ext(&[handle], &[metadata.to_erupt().into_builder()])
+                }
+            }
+        }
+
         let index = device.insert_swapchain(handle);
 
         self.swapchain = Some(Swapchain {
@@ -355,7 +565,7 @@ impl Surface {
                 .map(|(i, (a, r))| SwapchainImageAndSemaphores {
                     image: Image::new_surface(
                         ImageInfo {
-                            extent: caps.current_extent.into(),
+                            extent: extent.into(),
                             format: format,
                             levels: 1,
                             layers: 1,
@@ -373,7 +583,10 @@ impl Surface {
                 })
                 .collect(),
             acquired_counter: AtomicU32::new(0),
+            last_presented: AtomicU32::new(u32::MAX),
+            emulated: None,
             format: format,
+            color_space,
             usage: usage,
             mode: mode,
             optimal: true,
@@ -383,74 +596,179 @@ impl Surface {
         Ok(())
     }
 
-    pub fn acquire_image(&mut self) -> Result<SurfaceImage<'_>, SurfaceError> {
-        let device = self.device.upgrade().ok_or(SurfaceError::SurfaceLost)?;
-
-        debug_assert!(
-            device.logical().enabled().khr_swapchain,
-            "Should be enabled given that there is a Swapchain"
-        );
+    /// Configures ring of images that emulates swapchain.
+    fn configure_emulated(
+        &mut self,
+        device: &Device,
+        usage: ImageUsage,
+        format: Format,
+        color_space: ColorSpace,
+        mode: PresentMode,
+    ) -> Result<(), SurfaceError> {
+        self.try_dispose_retired_swapchains(device);
 
-        self.try_dispose_retired_swapchains(&device);
+        let caps = &self.surface_capabilities;
 
-        let index = loop {
-            let swapchain = self.swapchain.as_mut().ok_or(SurfaceError::NotConfigured)?;
+        if !caps.supported_usage.contains(usage) {
+            return Err(SurfaceError::UsageNotSupported { usage });
+        }
 
-            if swapchain.acquired_counter.load(Acquire)
-                > (swapchain.images.len() as u32 - self.surface_capabilities.min_image_count.get())
-            {
-                return Err(SurfaceError::TooManyAcquired);
+        if !caps.supports_format(format, color_space) {
+            if caps.formats.iter().any(|(f, _)| *f == format) {
+                return Err(SurfaceError::ColorSpaceUnsupported {
+                    format,
+                    color_space,
+                });
             }
+            return Err(SurfaceError::FormatUnsupported { format });
+        }
 
-            // FIXME: Use fences to know that acquire semaphore is unused.
-            let wait = &self.free_semaphore;
+        if !caps.present_modes.contains(&mode) {
+            return Err(SurfaceError::PresentModeUnsupported { mode });
+        }
 
-            let result = unsafe {
-                device.logical().acquire_next_image_khr(
-                    swapchain.handle,
-                    !0, /* wait indefinitely. This is OK as we never try to
-                         * acquire more images than there is in swapchain. */
-                    wait.handle(),
-                    vk1_0::Fence::null(),
-                )
-            };
+        let extent = caps.current_extent;
 
-            match result.raw {
-                vk1_0::Result::SUCCESS => {}
-                vk1_0::Result::ERROR_OUT_OF_HOST_MEMORY => out_of_host_memory(),
-                vk1_0::Result::ERROR_OUT_OF_DEVICE_MEMORY => {
-                    return Err(SurfaceError::OutOfMemory {
-                        source: OutOfMemory,
-                    });
-                }
-                vk1_0::Result::ERROR_SURFACE_LOST_KHR => {
-                    return Err(SurfaceError::SurfaceLost);
-                }
-                vk1_0::Result::SUBOPTIMAL_KHR => {
-                    // Image acquired, but it is suboptimal.
-                    // It must be presented either way.
-                    swapchain.optimal = false;
+        let mut images = (0..EMULATED_IMAGE_COUNT)
+            .map(|_| {
+                let image = device.create_image(ImageInfo {
+                    extent: extent.into(),
+                    format,
+                    levels: 1,
+                    layers: 1,
+                    samples: Samples::Samples1,
+                    usage: usage | ImageUsage::TRANSFER_SRC,
+                })?;
+
+                Ok(SwapchainImageAndSemaphores {
+                    image: Image::new_emulated_surface(image),
+                    acquire: device.create_semaphore()?,
+                    release: device.create_semaphore()?,
+                })
+            })
+            .collect::<Result<Vec<_>, OutOfMemory>>()?;
+
+        // Acquire semaphores must be signalled when image is acquired.
+        // Signalled semaphores are moved from the swapchain being retired,
+        // no image of which is acquired at this point.
+        match &mut self.swapchain {
+            Some(retired) => {
+                for (new, old) in images.iter_mut().zip(&mut retired.images) {
+                    std::mem::swap(&mut new.acquire, &mut old.acquire);
                 }
-                vk1_0::Result::ERROR_OUT_OF_DATE_KHR => {
-                    // No image acquired. Reconfigure.
-                    let usage = swapchain.usage;
-                    let format = swapchain.format;
-                    let mode = swapchain.mode;
-
-                    self.configure(usage, format, mode)?;
-                    continue;
+            }
+            None => {
+                for (new, signalled) in images.iter_mut().zip(self.emulated_semaphores.drain(..)) {
+                    new.acquire = signalled;
                 }
-                raw => unexpected_result(raw),
             }
+        }
 
-            let index = result.unwrap();
-            let image_and_semaphores = &mut swapchain.images[index as usize];
+        if let Some(swapchain) = self.swapchain.take() {
+            self.retired.push_back(swapchain);
+        }
 
-            std::mem::swap(&mut image_and_semaphores.acquire, &mut self.free_semaphore);
+        self.swapchain = Some(Swapchain {
+            handle: vksw::SwapchainKHR::null(),
+            index: usize::MAX,
+            images,
+            acquired_counter: AtomicU32::new(0),
+            last_presented: AtomicU32::new(u32::MAX),
+            emulated: Some(EmulatedRing::new(EMULATED_IMAGE_COUNT)),
+            format,
+            color_space,
+            usage,
+            mode,
+            optimal: true,
+        });
 
-            swapchain.acquired_counter.fetch_add(1, Acquire);
+        debug!("Emulated swapchain configured");
+        Ok(())
+    }
 
-            break index;
+    pub fn acquire_image(&mut self) -> Result<SurfaceImage<'_>, SurfaceError> {
+        let device = self.device.upgrade().ok_or(SurfaceError::SurfaceLost)?;
+
+        self.try_dispose_retired_swapchains(&device);
+
+        let index = match &mut self.swapchain {
+            Some(Swapchain {
+                emulated: Some(ring),
+                acquired_counter,
+                ..
+            }) => {
+                let index = ring.acquire(acquired_counter.load(Acquire))?;
+                acquired_counter.fetch_add(1, Acquire);
+                index
+            }
+            _ => {
+                debug_assert!(
+                    device.logical().enabled().khr_swapchain,
+                    "Should be enabled given that there is a Swapchain"
+                );
+
+                loop {
+                    let swapchain = self.swapchain.as_mut().ok_or(SurfaceError::NotConfigured)?;
+
+                    if swapchain.acquired_counter.load(Acquire)
+                        > (swapchain.images.len() as u32
+                            - self.surface_capabilities.min_image_count.get())
+                    {
+                        return Err(SurfaceError::TooManyAcquired);
+                    }
+
+                    // FIXME: Use fences to know that acquire semaphore is unused.
+                    let wait = &self.free_semaphore;
+
+                    let result = unsafe {
+                        device.logical().acquire_next_image_khr(
+                            swapchain.handle,
+                            !0, /* wait indefinitely. This is OK as we never try to
+                                 * acquire more images than there is in swapchain. */
+                            wait.handle(),
+                            vk1_0::Fence::null(),
+                        )
+                    };
+
+                    match result.raw {
+                        vk1_0::Result::SUCCESS => {}
+                        vk1_0::Result::ERROR_OUT_OF_HOST_MEMORY => out_of_host_memory(),
+                        vk1_0::Result::ERROR_OUT_OF_DEVICE_MEMORY => {
+                            return Err(SurfaceError::OutOfMemory {
+                                source: OutOfMemory,
+                            });
+                        }
+                        vk1_0::Result::ERROR_SURFACE_LOST_KHR => {
+                            return Err(SurfaceError::SurfaceLost);
+                        }
+                        vk1_0::Result::SUBOPTIMAL_KHR => {
+                            // Image acquired, but it is suboptimal.
+                            // It must be presented either way.
+                            swapchain.optimal = false;
+                        }
+                        vk1_0::Result::ERROR_OUT_OF_DATE_KHR => {
+                            // No image acquired. Reconfigure.
+                            let usage = swapchain.usage;
+                            let format = swapchain.format;
+                            let color_space = swapchain.color_space;
+                            let mode = swapchain.mode;
+
+                            self.configure(usage, format, color_space, mode)?;
+                            continue;
+                        }
+                        raw => unexpected_result(raw),
+                    }
+
+                    let index = result.unwrap();
+                    let image_and_semaphores = &mut swapchain.images[index as usize];
+
+                    std::mem::swap(&mut image_and_semaphores.acquire, &mut self.free_semaphore);
+
+                    swapchain.acquired_counter.fetch_add(1, Acquire);
+
+                    break index;
+                }
+            }
         };
 
         let swapchain = self.swapchain.as_mut().unwrap();
@@ -466,6 +784,7 @@ impl Surface {
             handle: swapchain.handle,
             supported_families: self.surface_capabilities.supported_families.clone(),
             acquired_counter: &swapchain.acquired_counter,
+            last_presented: &swapchain.last_presented,
             index,
             optimal: swapchain.optimal,
         })
@@ -488,6 +807,10 @@ impl Surface {
 
         let swapchain = self.swapchain.as_ref().ok_or(SurfaceError::NotConfigured)?;
 
+        if self.is_emulated() {
+            return Err(SurfaceError::Emulated);
+        }
+
         let refresh_cycle_duration = unsafe {
             device
                 .logical()
@@ -516,6 +839,10 @@ impl Surface {
 
         let swapchain = self.swapchain.as_ref().ok_or(SurfaceError::NotConfigured)?;
 
+        if self.is_emulated() {
+            return Err(SurfaceError::Emulated);
+        }
+
         let f = device
             .logical()
             .get_past_presentation_timing_google
@@ -576,14 +903,83 @@ impl Surface {
             }
 
             debug!("Destroying retired swapchain. {} left", self.retired.len());
-            unsafe {
-                // This swapchain and its images are no longer in use.
-                device.destroy_swapchain(swapchain.index)
+            if swapchain.emulated.is_none() {
+                unsafe {
+                    // This swapchain and its images are no longer in use.
+                    device.destroy_swapchain(swapchain.index)
+                }
             }
         }
     }
 }
 
+/// Number of images in emulated swapchain.
+const EMULATED_IMAGE_COUNT: u32 = 3;
+
+/// Order of images in emulated swapchain.
+/// Images are acquired and presented in ring order.
+#[derive(Debug)]
+struct EmulatedRing {
+    len: u32,
+    next: u32,
+}
+
+impl EmulatedRing {
+    fn new(len: u32) -> Self {
+        EmulatedRing { len, next: 0 }
+    }
+
+    /// Returns index of the image to acquire.
+    /// Fails if all images are already acquired.
+    fn acquire(&mut self, acquired: u32) -> Result<u32, SurfaceError> {
+        if acquired >= self.len {
+            return Err(SurfaceError::TooManyAcquired);
+        }
+
+        let index = self.next;
+        self.next = (index + 1) % self.len;
+        Ok(index)
+    }
+}
+
+/// Capabilities of surface with emulated swapchain.
+fn emulated_capabilities(device: &Device, extent: Extent2) -> SurfaceCapabilities {
+    let families = unsafe {
+        device
+            .graphics()
+            .instance
+            .get_physical_device_queue_family_properties(device.physical(), None)
+    };
+
+    let max_extent = device.properties().v10.limits.max_image_dimension2_d;
+
+    SurfaceCapabilities {
+        supported_families: families.iter().map(|_| true).collect(),
+        min_image_count: NonZeroU32::new(1).unwrap(),
+        max_image_count: NonZeroU32::new(EMULATED_IMAGE_COUNT),
+        current_extent: extent,
+        current_transform: SurfaceTransformFlags::IDENTITY,
+        min_image_extent: Extent2::new(1, 1),
+        max_image_extent: Extent2::new(max_extent, max_extent),
+        supported_usage: ImageUsage::TRANSFER_SRC
+            | ImageUsage::TRANSFER_DST
+            | ImageUsage::SAMPLED
+            | ImageUsage::COLOR_ATTACHMENT,
+        present_modes: vec![
+            PresentMode::Fifo,
+            PresentMode::Mailbox,
+            PresentMode::Immediate,
+        ],
+        formats: vec![
+            (Format::BGRA8Srgb, ColorSpace::SrgbNonlinear),
+            (Format::RGBA8Srgb, ColorSpace::SrgbNonlinear),
+            (Format::BGRA8Unorm, ColorSpace::SrgbNonlinear),
+            (Format::RGBA8Unorm, ColorSpace::SrgbNonlinear),
+        ],
+        supported_composite_alpha: CompositeAlphaFlags::OPAQUE,
+    }
+}
+
 #[track_caller]
 pub(crate) fn surface_error_from_erupt(err: vk1_0::Result) -> SurfaceError {
     match err {
@@ -654,7 +1050,11 @@ pub fn surface_capabilities(
 
     let formats = formats
         .iter()
-        .filter_map(|sf| from_erupt(sf.format))
+        .filter_map(|sf| {
+            let format: Option<Format> = from_erupt(sf.format);
+            let color_space: Option<ColorSpace> = from_erupt(sf.color_space);
+            Some((format?, color_space?))
+        })
         .collect::<Vec<_>>();
 
     assert_ne!(
@@ -681,3 +1081,35 @@ pub fn surface_capabilities(
         formats,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::EmulatedRing;
+    use crate::surface::SurfaceError;
+
+    #[test]
+    fn emulated_acquire_present_cycle() {
+        let mut ring = EmulatedRing::new(3);
+
+        // Each image is presented before the next one is acquired.
+        let order = (0..7).map(|_| ring.acquire(0).unwrap()).collect::<Vec<_>>();
+
+        assert_eq!(order, [0, 1, 2, 0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn emulated_too_many_acquired() {
+        let mut ring = EmulatedRing::new(3);
+
+        assert_eq!(ring.acquire(0).unwrap(), 0);
+        assert_eq!(ring.acquire(1).unwrap(), 1);
+        assert_eq!(ring.acquire(2).unwrap(), 2);
+        assert!(matches!(
+            ring.acquire(3),
+            Err(SurfaceError::TooManyAcquired)
+        ));
+
+        // Failed acquisition does not skip images.
+        assert_eq!(ring.acquire(2).unwrap(), 0);
+    }
+}
@@ -1,10 +1,13 @@
 use std::{
     borrow::Borrow,
+    collections::hash_map::DefaultHasher,
     convert::Infallible,
-    hash::Hash,
+    hash::{BuildHasherDefault, Hash},
     ops::{Deref, DerefMut},
 };
 
+use hashbrown::{hash_map::RawEntryMut, HashMap};
+
 use crate::{
     image::Image,
     view::{ImageView, ImageViewInfo},
@@ -16,33 +19,163 @@ use crate::{
 /// Each time a resource is used, its last used epoch is updated to the current.
 /// The cache evicts all resources that have not been used for a certain amount of epochs.
 ///
+/// Resources may be grouped by key `K` so that lookup only scans resources with the same key.
+/// By default there is a single group and every lookup scans all resources.
+///
 /// This strategy works best for resources that may become obsolete and require substantial amount of memory
 /// while only few resources are in use so cache size is always low.
 #[derive(Debug)]
-pub struct ResourceCache<T> {
-    /// Resources in the cache.
-    /// Key is a resource.
+pub struct ResourceCache<T, K = ()> {
+    /// Resources in the cache grouped by key.
     ///
-    /// Value is a last used time.
-    resources: Vec<(T, u64)>,
+    /// Each resource is paired with its last used epoch.
+    resources: HashMap<K, Vec<(T, u64)>, BuildHasherDefault<DefaultHasher>>,
 
     /// Current epoch of the cache.
     current_epoch: u64,
 }
 
-impl<T> ResourceCache<T> {
+impl<T, K> Default for ResourceCache<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, K> ResourceCache<T, K> {
     /// Creates a new empty cache.
     pub const fn new() -> Self {
         Self {
-            resources: Vec::new(),
+            resources: HashMap::with_hasher(BuildHasherDefault::new()),
             current_epoch: 0,
         }
     }
 
+    /// Moves to the next epoch.
+    pub fn next_epoch(&mut self) {
+        self.current_epoch += 1;
+    }
+
+    /// Evicts resources that have not been used since specified epoch.
+    pub fn evict(&mut self, epoch: u64) {
+        self.resources.retain(|_, resources| {
+            resources.retain(|(_, e)| *e >= epoch);
+            !resources.is_empty()
+        });
+    }
+
+    /// Returns current epoch of the cache.
+    pub fn current_epoch(&self) -> u64 {
+        self.current_epoch
+    }
+
+    /// Evicts resources that have not been used during last `epochs` epochs.
+    pub fn evict_unused(&mut self, epochs: u64) {
+        self.evict(self.current_epoch.saturating_sub(epochs));
+    }
+}
+
+impl<T, K> ResourceCache<T, K>
+where
+    K: Hash + Eq,
+{
+    /// Fetches resource with specified key from cache.
+    /// Resource last used epoch is updated to the current epoch.
+    /// Returns `None` if resource is not in cache.
+    /// Returns `Some` if resource is in cache.
+    pub fn fetch_keyed<Q, F>(&mut self, key: &Q, eq: F) -> Option<&T>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+        F: Fn(&T) -> bool,
+    {
+        let (r, e) = self
+            .resources
+            .get_mut(key)?
+            .iter_mut()
+            .find(|(r, _)| eq(r))?;
+        *e = self.current_epoch;
+        Some(&*r)
+    }
+
+    /// Fetches resource with specified key from cache.
+    /// Resource last used epoch is not updated.
+    /// This is useful when only shared reference to cache is available.
+    /// Returns `None` if resource is not in cache.
+    /// Returns `Some` if resource is in cache.
+    pub fn fetch_keyed_no_update<Q, F>(&self, key: &Q, eq: F) -> Option<&T>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+        F: Fn(&T) -> bool,
+    {
+        self.resources
+            .get(key)?
+            .iter()
+            .find(|(r, _)| eq(r))
+            .map(|(r, _)| r)
+    }
+
+    /// Fetches resource with specified key from cache.
+    /// Resource last used epoch is updated to the current epoch.
+    /// If resource is not in cache, it is created and added to the cache.
+    /// Returns a reference to the resource.
+    /// Returns error if resource is not in cache and create function fails.
+    pub fn try_fetch_keyed_or_create<Q, M, F, E>(
+        &mut self,
+        key: &Q,
+        eq: M,
+        create: F,
+    ) -> Result<&T, E>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq + ToOwned<Owned = K>,
+        M: Fn(&T) -> bool,
+        F: FnOnce() -> Result<T, E>,
+    {
+        let resources = match self.resources.raw_entry_mut().from_key(key) {
+            RawEntryMut::Occupied(entry) => entry.into_mut(),
+            RawEntryMut::Vacant(entry) => entry.insert(key.to_owned(), Vec::new()).1,
+        };
+
+        let idx = match resources.iter().position(|(r, _)| eq(r)) {
+            None => {
+                let r = create()?;
+                resources.push((r, self.current_epoch));
+                resources.len() - 1
+            }
+            Some(idx) => idx,
+        };
+
+        let (r, e) = &mut resources[idx];
+        *e = self.current_epoch;
+        Ok(r)
+    }
+
+    /// Fetches resource with specified key from cache.
+    /// Resource last used epoch is updated to the current epoch.
+    /// If resource is not in cache, it is created and added to the cache.
+    /// Returns a reference to the resource.
+    pub fn fetch_keyed_or_create<Q, M, F>(&mut self, key: &Q, eq: M, create: F) -> &T
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq + ToOwned<Owned = K>,
+        M: Fn(&T) -> bool,
+        F: FnOnce() -> T,
+    {
+        match self.try_fetch_keyed_or_create(key, eq, || Ok::<_, Infallible>(create())) {
+            Ok(r) => r,
+            Err(infallible) => match infallible {},
+        }
+    }
+}
+
+impl<T> ResourceCache<T> {
     /// Creates a new cache with preallocated resource capacity.
     pub fn with_capacity(cap: usize) -> Self {
+        let mut resources = HashMap::with_capacity_and_hasher(1, BuildHasherDefault::default());
+        resources.insert((), Vec::with_capacity(cap));
         Self {
-            resources: Vec::with_capacity(cap),
+            resources,
             current_epoch: 0,
         }
     }
@@ -55,9 +188,7 @@ impl<T> ResourceCache<T> {
     where
         F: Fn(&T) -> bool,
     {
-        let (r, e) = self.resources.iter_mut().find(|(r, _)| eq(r))?;
-        *e = self.current_epoch;
-        Some(&*r)
+        self.fetch_keyed(&(), eq)
     }
 
     /// Fetches resource from cache.
@@ -81,7 +212,7 @@ impl<T> ResourceCache<T> {
     where
         F: Fn(&T) -> bool,
     {
-        self.resources.iter().find(|(r, _)| eq(r)).map(|(r, _)| r)
+        self.fetch_keyed_no_update(&(), eq)
     }
 
     /// Fetches resource from cache.
@@ -107,18 +238,7 @@ impl<T> ResourceCache<T> {
         K: Fn(&T) -> bool,
         F: FnOnce() -> Result<T, E>,
     {
-        let idx = match self.resources.iter().position(|(r, _)| eq(r)) {
-            None => {
-                let r = create()?;
-                self.resources.push((r, self.current_epoch));
-                self.resources.len() - 1
-            }
-            Some(idx) => idx,
-        };
-
-        let (r, e) = &mut self.resources[idx];
-        *e = self.current_epoch;
-        Ok(r)
+        self.try_fetch_keyed_or_create(&(), eq, create)
     }
 
     /// Fetches resource from cache.
@@ -130,10 +250,7 @@ impl<T> ResourceCache<T> {
         E: Fn(&T) -> bool,
         F: FnOnce() -> T,
     {
-        match self.try_fetch_or_create(eq, || Ok::<_, Infallible>(create())) {
-            Ok(r) => r,
-            Err(infallible) => match infallible {},
-        }
+        self.fetch_keyed_or_create(&(), eq, create)
     }
 
     /// Fetches resource from cache.
@@ -161,16 +278,6 @@ impl<T> ResourceCache<T> {
     {
         self.fetch_or_create(|r| r.borrow() == key, create)
     }
-
-    /// Moves to the next epoch.
-    pub fn next_epoch(&mut self) {
-        self.current_epoch += 1;
-    }
-
-    /// Evicts resources that have not been used since specified epoch.
-    pub fn evict(&mut self, epoch: u64) {
-        self.resources.retain(|(_, e)| *e >= epoch);
-    }
 }
 
 /// Cache for image views
@@ -255,3 +362,84 @@ impl ImageViewCache {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ResourceCache;
+
+    #[test]
+    fn keyed_variant_reuse() {
+        let mut cache = ResourceCache::<u32, u8>::new();
+        let mut created = 0;
+
+        let mut create = |value| {
+            created += 1;
+            value
+        };
+
+        assert_eq!(
+            *cache.fetch_keyed_or_create(&b'a', |&r| r == 1, || create(1)),
+            1
+        );
+        assert_eq!(
+            *cache.fetch_keyed_or_create(&b'a', |&r| r == 1, || create(1)),
+            1
+        );
+        assert_eq!(
+            *cache.fetch_keyed_or_create(&b'a', |&r| r == 2, || create(2)),
+            2
+        );
+        assert_eq!(
+            *cache.fetch_keyed_or_create(&b'b', |&r| r == 1, || create(1)),
+            1
+        );
+        assert_eq!(
+            *cache.fetch_keyed_or_create(&b'a', |&r| r == 2, || create(2)),
+            2
+        );
+        assert_eq!(created, 3);
+
+        assert_eq!(cache.fetch_keyed(&b'b', |&r| r == 1), Some(&1));
+        assert_eq!(cache.fetch_keyed(&b'b', |&r| r == 2), None);
+        assert_eq!(cache.fetch_keyed(&b'c', |&r| r == 1), None);
+    }
+
+    #[test]
+    fn keyed_eviction() {
+        let mut cache = ResourceCache::<u32, u8>::new();
+
+        cache.fetch_keyed_or_create(&b'a', |&r| r == 1, || 1);
+        cache.fetch_keyed_or_create(&b'a', |&r| r == 2, || 2);
+        cache.fetch_keyed_or_create(&b'b', |&r| r == 1, || 1);
+        cache.next_epoch();
+
+        cache.fetch_keyed(&b'a', |&r| r == 2);
+        cache.next_epoch();
+
+        cache.evict_unused(1);
+        assert_eq!(cache.fetch_keyed_no_update(&b'a', |&r| r == 1), None);
+        assert_eq!(cache.fetch_keyed_no_update(&b'a', |&r| r == 2), Some(&2));
+        assert_eq!(cache.fetch_keyed_no_update(&b'b', |&r| r == 1), None);
+
+        // Evicted variant has to be created again.
+        let result = cache.try_fetch_keyed_or_create(&b'b', |&r| r == 1, || Err(()));
+        assert_eq!(result, Err(()));
+
+        cache.evict_unused(0);
+        assert_eq!(cache.fetch_keyed_no_update(&b'a', |&r| r == 2), None);
+    }
+
+    #[test]
+    fn unkeyed_reuse_and_eviction() {
+        let mut cache = ResourceCache::<u32>::with_capacity(2);
+
+        assert_eq!(*cache.get_or_create(&1, || 1), 1);
+        assert_eq!(cache.try_get_or_create(&1, || Err::<u32, _>(())), Ok(&1));
+        cache.next_epoch();
+
+        assert_eq!(*cache.get_or_create(&2, || 2), 2);
+        cache.evict_unused(0);
+        assert_eq!(cache.get_no_update(&1), None);
+        assert_eq!(cache.get_no_update(&2), Some(&2));
+    }
+}
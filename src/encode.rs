@@ -1,33 +1,40 @@
 use std::{
     fmt,
-    mem::{forget, size_of_val},
+    mem::{forget, size_of, size_of_val},
     ops::Range,
 };
 
-use bytemuck::{cast_slice, Pod};
+use bytemuck::{cast_slice, Pod, Zeroable};
 use scoped_arena::Scope;
 
 use crate::{
-    accel::AccelerationStructureBuildGeometryInfo,
+    accel::{
+        AabbPositions, AccelerationStructure, AccelerationStructureBuildGeometryInfo,
+        AccelerationStructureCopyMode, AccelerationStructureGeometry, AccelerationStructureLevel,
+    },
     access::Access,
     arith_ge, arith_le,
-    buffer::{Buffer, BufferMemoryBarrier},
+    buffer::{Buffer, BufferMemoryBarrier, BufferRange},
     descriptor::{DescriptorSet, UpdatedPipelineDescriptors},
+    format::{AspectFlags, Channels},
     framebuffer::{Framebuffer, FramebufferError},
-    image::{Image, ImageBlit, ImageMemoryBarrier, Layout, SubresourceLayers},
+    image::{Image, ImageBlit, ImageMemoryBarrier, Layout, SubresourceLayers, SubresourceRange},
+    memory::MemoryUsage,
     minimal_extent,
     pipeline::{
         ComputePipeline, DynamicGraphicsPipeline, GraphicsPipeline, PipelineInputLayout,
         PipelineLayout, RayTracingPipeline, ShaderBindingTable, Viewport,
     },
+    query::{QueryPool, QueryResultFlags, QueryType},
     queue::QueueCapabilityFlags,
     render_pass::{ClearValue, RenderPass, RenderPassInstance},
+    repr::{Padded, ShaderRepr, Std140, Std430},
     sampler::Filter,
     shader::ShaderStageFlags,
     stage::PipelineStages,
-    BufferInfo, BufferUsage, Device, Extent3, Format, IndexType, Offset3, OutOfMemory,
-    PipelinePushConstants, Rect, RenderingColorInfo, RenderingDepthStencilAttachmentInfo,
-    RenderingInfo,
+    BufferInfo, BufferUsage, Device, Extent3, Format, IndexType, MappableBuffer, Offset3,
+    OutOfMemory, PipelinePushConstants, Rect, RenderingColorInfo,
+    RenderingDepthStencilAttachmentInfo, RenderingInfo,
 };
 
 pub use crate::backend::CommandBuffer;
@@ -71,12 +78,92 @@ pub struct BufferImageCopy {
     pub image_extent: Extent3,
 }
 
+/// Parameters of a single draw read from indirect buffer.
+///
+/// Layout matches one expected by the device,
+/// so it can be written directly to buffers used in `draw_indirect`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrawIndirectCommand {
+    pub vertex_count: u32,
+    pub instance_count: u32,
+    pub first_vertex: u32,
+    pub first_instance: u32,
+}
+
+/// Parameters of a single indexed draw read from indirect buffer.
+///
+/// Layout matches one expected by the device,
+/// so it can be written directly to buffers used in `draw_indexed_indirect`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrawIndexedIndirectCommand {
+    pub index_count: u32,
+    pub instance_count: u32,
+    pub first_index: u32,
+    pub vertex_offset: i32,
+    pub first_instance: u32,
+}
+
+/// Parameters of a dispatch read from indirect buffer.
+///
+/// Layout matches one expected by the device,
+/// so it can be written directly to buffers used in `dispatch_indirect`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct DispatchIndirectCommand {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+macro_rules! impl_indirect_command {
+    ($($ty:ident: $std140_pad:literal),* $(,)?) => {
+        $(
+            unsafe impl Zeroable for $ty {}
+            unsafe impl Pod for $ty {}
+
+            impl ShaderRepr<Std140> for $ty {
+                const ALIGN_MASK: usize = 15;
+                const ARRAY_PADDING: usize = 0;
+                type Type = Padded<$ty, [u8; $std140_pad]>;
+                type ArrayPadding = [u8; 0];
+
+                fn copy_to_repr(&self, repr: &mut Padded<$ty, [u8; $std140_pad]>) {
+                    repr.value = *self;
+                }
+            }
+
+            impl ShaderRepr<Std430> for $ty {
+                const ALIGN_MASK: usize = 3;
+                const ARRAY_PADDING: usize = 0;
+                type Type = $ty;
+                type ArrayPadding = [u8; 0];
+
+                fn copy_to_repr(&self, repr: &mut $ty) {
+                    *repr = *self;
+                }
+            }
+        )*
+    };
+}
+
+impl_indirect_command! {
+    DrawIndirectCommand: 0,
+    DrawIndexedIndirectCommand: 12,
+    DispatchIndirectCommand: 4,
+}
+
 #[derive(Debug)]
 pub(crate) enum Command<'a> {
     BeginRenderPass {
         framebuffer: &'a Framebuffer,
         clears: &'a [ClearValue],
     },
+    NextSubpass,
     EndRenderPass,
 
     BindGraphicsPipeline {
@@ -131,6 +218,38 @@ pub(crate) enum Command<'a> {
         instances: Range<u32>,
     },
 
+    DrawIndirect {
+        buffer: &'a Buffer,
+        offset: u64,
+        draw_count: u32,
+        stride: u32,
+    },
+
+    DrawIndexedIndirect {
+        buffer: &'a Buffer,
+        offset: u64,
+        draw_count: u32,
+        stride: u32,
+    },
+
+    DrawIndirectCount {
+        buffer: &'a Buffer,
+        offset: u64,
+        count_buffer: &'a Buffer,
+        count_buffer_offset: u64,
+        max_draw_count: u32,
+        stride: u32,
+    },
+
+    DrawIndexedIndirectCount {
+        buffer: &'a Buffer,
+        offset: u64,
+        count_buffer: &'a Buffer,
+        count_buffer_offset: u64,
+        max_draw_count: u32,
+        stride: u32,
+    },
+
     UpdateBuffer {
         buffer: &'a Buffer,
         offset: u64,
@@ -152,6 +271,28 @@ pub(crate) enum Command<'a> {
         infos: &'a [AccelerationStructureBuildGeometryInfo<'a>],
     },
 
+    WriteAccelerationStructuresProperties {
+        acceleration_structures: &'a [&'a AccelerationStructure],
+        pool: &'a QueryPool,
+        first_query: u32,
+    },
+
+    CopyAccelerationStructure {
+        src: &'a AccelerationStructure,
+        dst: &'a AccelerationStructure,
+        mode: AccelerationStructureCopyMode,
+    },
+
+    SerializeAccelerationStructure {
+        src: &'a AccelerationStructure,
+        dst: &'a BufferRange,
+    },
+
+    DeserializeAccelerationStructure {
+        src: &'a BufferRange,
+        dst: &'a AccelerationStructure,
+    },
+
     TraceRays {
         shader_binding_table: &'a ShaderBindingTable,
         extent: Extent3,
@@ -178,6 +319,13 @@ pub(crate) enum Command<'a> {
         regions: &'a [BufferImageCopy],
     },
 
+    CopyImageBuffer {
+        src_image: &'a Image,
+        src_layout: Layout,
+        dst_buffer: &'a Buffer,
+        regions: &'a [BufferImageCopy],
+    },
+
     BlitImage {
         src_image: &'a Image,
         src_layout: Layout,
@@ -208,11 +356,46 @@ pub(crate) enum Command<'a> {
         z: u32,
     },
 
+    DispatchIndirect {
+        buffer: &'a Buffer,
+        offset: u64,
+    },
+
     BeginRendering {
         info: RenderingInfo<'a>,
     },
 
     EndRendering,
+
+    ResetQueries {
+        pool: &'a QueryPool,
+        queries: Range<u32>,
+    },
+
+    WriteTimestamp {
+        pool: &'a QueryPool,
+        query: u32,
+        stage: PipelineStages,
+    },
+
+    BeginQuery {
+        pool: &'a QueryPool,
+        query: u32,
+    },
+
+    EndQuery {
+        pool: &'a QueryPool,
+        query: u32,
+    },
+
+    CopyQueryResults {
+        pool: &'a QueryPool,
+        queries: Range<u32>,
+        buffer: &'a Buffer,
+        offset: u64,
+        stride: u64,
+        flags: QueryResultFlags,
+    },
 }
 
 /// Basis for encoding capabilities.
@@ -402,6 +585,40 @@ impl<'a> EncoderCommon<'a> {
     {
         layout.push_constants(constants, self);
     }
+
+    /// Writes device timestamp into the query when all previous commands
+    /// complete specified stage.
+    ///
+    /// `stage` must contain exactly one stage.
+    pub fn write_timestamp(&mut self, stage: PipelineStages, pool: &QueryPool, query: u32) {
+        assert_eq!(
+            stage.bits().count_ones(),
+            1,
+            "Exactly one stage must be specified"
+        );
+        assert_eq!(pool.info().ty, QueryType::Timestamp);
+        assert!(query < pool.info().count);
+
+        self.command_buffer
+            .write(self.scope, Command::WriteTimestamp { pool, query, stage });
+    }
+
+    /// Begins occlusion or pipeline statistics query.
+    pub fn begin_query(&mut self, pool: &QueryPool, query: u32) {
+        assert_ne!(pool.info().ty, QueryType::Timestamp);
+        assert!(query < pool.info().count);
+
+        self.command_buffer
+            .write(self.scope, Command::BeginQuery { pool, query });
+    }
+
+    /// Ends query started with `begin_query`.
+    pub fn end_query(&mut self, pool: &QueryPool, query: u32) {
+        assert!(query < pool.info().count);
+
+        self.command_buffer
+            .write(self.scope, Command::EndQuery { pool, query });
+    }
 }
 
 /// Command encoder that can encode commands outside render pass.
@@ -720,6 +937,230 @@ impl<'a> Encoder<'a> {
         Ok(())
     }
 
+    /// Uploads texel data to the image region.
+    /// Creates intermediate staging buffer.
+    ///
+    /// Data must contain tightly packed texels of the region.
+    /// Previous content of specified subresource layers is discarded.
+    /// After upload image subresource layers are in `ShaderReadOnlyOptimal` layout.
+    pub fn upload_image<T>(
+        &mut self,
+        image: &Image,
+        subresource: SubresourceLayers,
+        offset: Offset3,
+        extent: Extent3,
+        data: &[T],
+        device: &Device,
+    ) -> Result<(), OutOfMemory>
+    where
+        T: Pod,
+    {
+        let size = image_region_size(image, subresource, extent);
+        assert_eq!(
+            size_of_val(data) as u64,
+            size,
+            "Image uploading data size does not match region size"
+        );
+
+        if size == 0 {
+            return Ok(());
+        }
+
+        let staging = device.create_buffer_static(
+            BufferInfo {
+                align: 15,
+                size,
+                usage: BufferUsage::TRANSFER_SRC,
+            },
+            data,
+        )?;
+
+        let range = SubresourceRange::new(
+            subresource.aspect,
+            subresource.level..subresource.level + 1,
+            subresource.first_layer..subresource.first_layer + subresource.layer_count,
+        );
+
+        self.image_barriers(
+            PipelineStages::TOP_OF_PIPE,
+            PipelineStages::TRANSFER,
+            &[ImageMemoryBarrier {
+                image,
+                old_access: Access::empty(),
+                old_layout: None,
+                new_access: Access::TRANSFER_WRITE,
+                new_layout: Layout::TransferDstOptimal,
+                family_transfer: None,
+                range,
+            }],
+        );
+
+        self.copy_buffer_to_image(
+            &staging,
+            image,
+            Layout::TransferDstOptimal,
+            &[BufferImageCopy {
+                buffer_offset: 0,
+                buffer_row_length: 0,
+                buffer_image_height: 0,
+                image_subresource: subresource,
+                image_offset: offset,
+                image_extent: extent,
+            }],
+        );
+
+        self.image_barriers(
+            PipelineStages::TRANSFER,
+            PipelineStages::ALL_COMMANDS,
+            &[ImageMemoryBarrier {
+                image,
+                old_access: Access::TRANSFER_WRITE,
+                old_layout: Some(Layout::TransferDstOptimal),
+                new_access: Access::SHADER_SAMPLED_READ,
+                new_layout: Layout::ShaderReadOnlyOptimal,
+                family_transfer: None,
+                range,
+            }],
+        );
+
+        Ok(())
+    }
+
+    /// Copies image region into new buffer that can be mapped by host
+    /// once commands recorded so far are complete.
+    ///
+    /// Texels are tightly packed in the buffer.
+    /// Image must be in `layout` and is returned to it after copy.
+    pub fn download_image(
+        &mut self,
+        image: &Image,
+        layout: Layout,
+        subresource: SubresourceLayers,
+        offset: Offset3,
+        extent: Extent3,
+        device: &Device,
+    ) -> Result<MappableBuffer, OutOfMemory> {
+        let size = image_region_size(image, subresource, extent);
+
+        let buffer = device.create_mappable_buffer(
+            BufferInfo {
+                align: 15,
+                size,
+                usage: BufferUsage::TRANSFER_DST,
+            },
+            MemoryUsage::DOWNLOAD,
+        )?;
+
+        let range = SubresourceRange::new(
+            subresource.aspect,
+            subresource.level..subresource.level + 1,
+            subresource.first_layer..subresource.first_layer + subresource.layer_count,
+        );
+
+        let src_layout = match layout {
+            Layout::General | Layout::TransferSrcOptimal => layout,
+            _ => Layout::TransferSrcOptimal,
+        };
+
+        self.image_barriers(
+            PipelineStages::ALL_COMMANDS,
+            PipelineStages::TRANSFER,
+            &[ImageMemoryBarrier {
+                image,
+                old_access: WRITE_ACCESS,
+                old_layout: Some(layout),
+                new_access: Access::TRANSFER_READ,
+                new_layout: src_layout,
+                family_transfer: None,
+                range,
+            }],
+        );
+
+        self.copy_image_to_buffer(
+            image,
+            src_layout,
+            &buffer,
+            &[BufferImageCopy {
+                buffer_offset: 0,
+                buffer_row_length: 0,
+                buffer_image_height: 0,
+                image_subresource: subresource,
+                image_offset: offset,
+                image_extent: extent,
+            }],
+        );
+
+        if src_layout != layout {
+            self.image_barriers(
+                PipelineStages::TRANSFER,
+                PipelineStages::ALL_COMMANDS,
+                &[ImageMemoryBarrier {
+                    image,
+                    old_access: Access::empty(),
+                    old_layout: Some(src_layout),
+                    new_access: Access::empty(),
+                    new_layout: layout,
+                    family_transfer: None,
+                    range,
+                }],
+            );
+        }
+
+        self.memory_barrier(
+            PipelineStages::TRANSFER,
+            Access::TRANSFER_WRITE,
+            PipelineStages::HOST,
+            Access::HOST_READ,
+        );
+
+        Ok(buffer)
+    }
+
+    /// Copies buffer range into new buffer that can be mapped by host
+    /// once commands recorded so far are complete.
+    pub fn download_buffer(
+        &mut self,
+        buffer: &Buffer,
+        offset: u64,
+        size: u64,
+        device: &Device,
+    ) -> Result<MappableBuffer, OutOfMemory> {
+        let download = device.create_mappable_buffer(
+            BufferInfo {
+                align: 15,
+                size,
+                usage: BufferUsage::TRANSFER_DST,
+            },
+            MemoryUsage::DOWNLOAD,
+        )?;
+
+        self.memory_barrier(
+            PipelineStages::ALL_COMMANDS,
+            WRITE_ACCESS,
+            PipelineStages::TRANSFER,
+            Access::TRANSFER_READ,
+        );
+
+        self.copy_buffer(
+            buffer,
+            &download,
+            &[BufferCopy {
+                src_offset: offset,
+                dst_offset: 0,
+                size,
+            }],
+        );
+
+        self.memory_barrier(
+            PipelineStages::TRANSFER,
+            Access::TRANSFER_WRITE,
+            PipelineStages::HOST,
+            Access::HOST_READ,
+        );
+
+        Ok(download)
+    }
+
     /// Builds acceleration structures.
     pub fn build_acceleration_structure(
         &mut self,
@@ -752,6 +1193,24 @@ impl<'a> Encoder<'a> {
                     i,
                 );
             }
+
+            for geometry in info.geometries {
+                if let AccelerationStructureGeometry::AABBs { data, stride, .. } = geometry {
+                    assert_eq!(
+                        info.dst.info().level,
+                        AccelerationStructureLevel::Bottom,
+                        "AABBs must be built into bottom level acceleration structure"
+                    );
+                    assert!(
+                        *stride >= size_of::<AabbPositions>() as u64 && stride.is_multiple_of(8),
+                        "AABBs stride must be a multiple of 8 not less than size of `AabbPositions`"
+                    );
+                    assert!(
+                        data.offset.is_multiple_of(8),
+                        "AABBs data must be aligned to 8 bytes"
+                    );
+                }
+            }
         }
 
         self.inner.command_buffer.write(
@@ -760,6 +1219,122 @@ impl<'a> Encoder<'a> {
         )
     }
 
+    /// Writes properties of acceleration structures into consecutive queries
+    /// starting from `first_query`.
+    ///
+    /// Pool must be of `QueryType::AccelerationStructureCompactedSize`
+    /// or `QueryType::AccelerationStructureSerializationSize` type.
+    /// Builds of the acceleration structures must be complete,
+    /// which requires barrier with `ACCELERATION_STRUCTURE_BUILD` stage.
+    pub fn write_acceleration_structures_properties(
+        &mut self,
+        acceleration_structures: &[&AccelerationStructure],
+        pool: &QueryPool,
+        first_query: u32,
+    ) {
+        assert!(self.inner.capabilities.supports_compute());
+        assert!(
+            matches!(
+                pool.info().ty,
+                QueryType::AccelerationStructureCompactedSize
+                    | QueryType::AccelerationStructureSerializationSize
+            ),
+            "Query pool must be of `AccelerationStructureCompactedSize` or `AccelerationStructureSerializationSize` type, found `{:?}`",
+            pool.info().ty
+        );
+        assert!(
+            arith_le(
+                u64::from(first_query) + acceleration_structures.len() as u64,
+                pool.info().count
+            ),
+            "Not enough queries in the pool"
+        );
+
+        if acceleration_structures.is_empty() {
+            return;
+        }
+
+        self.inner.command_buffer.write(
+            self.inner.scope,
+            Command::WriteAccelerationStructuresProperties {
+                acceleration_structures,
+                pool,
+                first_query,
+            },
+        )
+    }
+
+    /// Copies acceleration structure.
+    pub fn copy_acceleration_structure(
+        &mut self,
+        src: &AccelerationStructure,
+        dst: &AccelerationStructure,
+        mode: AccelerationStructureCopyMode,
+    ) {
+        assert!(self.inner.capabilities.supports_compute());
+        assert_ne!(src, dst, "Source and destination must be different");
+        assert_eq!(
+            src.info().level,
+            dst.info().level,
+            "Source and destination must be of the same level"
+        );
+
+        self.inner.command_buffer.write(
+            self.inner.scope,
+            Command::CopyAccelerationStructure { src, dst, mode },
+        )
+    }
+
+    /// Serializes acceleration structure into buffer region.
+    ///
+    /// Required size can be queried with `QueryType::AccelerationStructureSerializationSize`.
+    /// Serialized data starts with `SerializedAccelerationStructureHeader`.
+    pub fn serialize_acceleration_structure(
+        &mut self,
+        src: &AccelerationStructure,
+        dst: &BufferRange,
+    ) {
+        assert!(self.inner.capabilities.supports_compute());
+        assert!(
+            dst.buffer
+                .info()
+                .usage
+                .contains(BufferUsage::DEVICE_ADDRESS),
+            "Buffer must be created with `DEVICE_ADDRESS` usage"
+        );
+        assert_eq!(dst.offset & 255, 0, "Offset must be multiple of 256");
+
+        self.inner.command_buffer.write(
+            self.inner.scope,
+            Command::SerializeAccelerationStructure { src, dst },
+        )
+    }
+
+    /// Deserializes acceleration structure from buffer region.
+    ///
+    /// Data must be produced by `serialize_acceleration_structure`
+    /// on compatible device. See `Device::is_acceleration_structure_compatible`.
+    pub fn deserialize_acceleration_structure(
+        &mut self,
+        src: &BufferRange,
+        dst: &AccelerationStructure,
+    ) {
+        assert!(self.inner.capabilities.supports_compute());
+        assert!(
+            src.buffer
+                .info()
+                .usage
+                .contains(BufferUsage::DEVICE_ADDRESS),
+            "Buffer must be created with `DEVICE_ADDRESS` usage"
+        );
+        assert_eq!(src.offset & 255, 0, "Offset must be multiple of 256");
+
+        self.inner.command_buffer.write(
+            self.inner.scope,
+            Command::DeserializeAccelerationStructure { src, dst },
+        )
+    }
+
     pub fn trace_rays(&mut self, shader_binding_table: &'a ShaderBindingTable, extent: Extent3) {
         assert!(self.inner.capabilities.supports_compute());
 
@@ -834,6 +1409,24 @@ impl<'a> Encoder<'a> {
         )
     }
 
+    pub fn copy_image_to_buffer(
+        &mut self,
+        src_image: &Image,
+        src_layout: Layout,
+        dst_buffer: &Buffer,
+        regions: &[BufferImageCopy],
+    ) {
+        self.inner.command_buffer.write(
+            self.inner.scope,
+            Command::CopyImageBuffer {
+                src_image,
+                src_layout,
+                dst_buffer,
+                regions,
+            },
+        )
+    }
+
     pub fn blit_image(
         &mut self,
         src_image: &Image,
@@ -858,6 +1451,60 @@ impl<'a> Encoder<'a> {
         )
     }
 
+    /// Dispatches compute work with parameters read from the buffer.
+    /// See [`DispatchIndirectCommand`].
+    pub fn dispatch_indirect(&mut self, buffer: &Buffer, offset: u64) {
+        assert!(self.inner.capabilities.supports_compute());
+        check_indirect_buffer(buffer, offset, size_of::<DispatchIndirectCommand>());
+
+        self.inner.command_buffer.write(
+            self.inner.scope,
+            Command::DispatchIndirect { buffer, offset },
+        );
+    }
+
+    /// Resets queries in the pool.
+    /// Queries must be reset before use.
+    pub fn reset_queries(&mut self, pool: &QueryPool, queries: Range<u32>) {
+        assert!(queries.start <= queries.end);
+        assert!(queries.end <= pool.info().count);
+
+        self.inner
+            .command_buffer
+            .write(self.inner.scope, Command::ResetQueries { pool, queries });
+    }
+
+    /// Copies results of the queries to the buffer.
+    /// Each value is written as `u64`.
+    ///
+    /// Timestamps are copied as raw device ticks.
+    pub fn copy_query_results(
+        &mut self,
+        pool: &QueryPool,
+        queries: Range<u32>,
+        buffer: &Buffer,
+        offset: u64,
+        stride: u64,
+        flags: QueryResultFlags,
+    ) {
+        assert!(queries.start <= queries.end);
+        assert!(queries.end <= pool.info().count);
+        assert_eq!(offset & 7, 0, "Offset must be multiple of 8");
+        assert_eq!(stride & 7, 0, "Stride must be multiple of 8");
+
+        self.inner.command_buffer.write(
+            self.inner.scope,
+            Command::CopyQueryResults {
+                pool,
+                queries,
+                buffer,
+                offset,
+                stride,
+                flags,
+            },
+        )
+    }
+
     pub fn dispatch(&mut self, x: u32, y: u32, z: u32) {
         assert!(self.inner.capabilities.supports_compute());
 
@@ -967,6 +1614,24 @@ impl<'a, 'b> RenderPassEncoder<'a, 'b> {
         self.framebuffer
     }
 
+    /// Returns index of the current subpass.
+    pub fn subpass(&self) -> u32 {
+        self.subpass
+    }
+
+    /// Transitions to the next subpass of the render pass.
+    pub fn next_subpass(&mut self) {
+        assert!(
+            usize::try_from(self.subpass + 1).unwrap() < self.render_pass.info().subpasses.len(),
+            "Render pass has no more subpasses"
+        );
+
+        self.subpass += 1;
+        self.inner
+            .command_buffer
+            .write(self.scope, Command::NextSubpass);
+    }
+
     pub fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
         self.inner.command_buffer.write(
             self.scope,
@@ -988,6 +1653,130 @@ impl<'a, 'b> RenderPassEncoder<'a, 'b> {
         );
     }
 
+    /// Performs draws with parameters read from the buffer.
+    /// See [`DrawIndirectCommand`].
+    pub fn draw_indirect(&mut self, buffer: &Buffer, offset: u64, draw_count: u32, stride: u32) {
+        check_indirect_draw(
+            buffer,
+            offset,
+            draw_count,
+            stride,
+            size_of::<DrawIndirectCommand>(),
+        );
+
+        self.inner.command_buffer.write(
+            self.scope,
+            Command::DrawIndirect {
+                buffer,
+                offset,
+                draw_count,
+                stride,
+            },
+        );
+    }
+
+    /// Performs indexed draws with parameters read from the buffer.
+    /// See [`DrawIndexedIndirectCommand`].
+    pub fn draw_indexed_indirect(
+        &mut self,
+        buffer: &Buffer,
+        offset: u64,
+        draw_count: u32,
+        stride: u32,
+    ) {
+        check_indirect_draw(
+            buffer,
+            offset,
+            draw_count,
+            stride,
+            size_of::<DrawIndexedIndirectCommand>(),
+        );
+
+        self.inner.command_buffer.write(
+            self.scope,
+            Command::DrawIndexedIndirect {
+                buffer,
+                offset,
+                draw_count,
+                stride,
+            },
+        );
+    }
+
+    /// Performs draws with parameters read from the buffer.
+    /// Number of draws is read from `count_buffer` and clamped to `max_draw_count`.
+    ///
+    /// Requires [`Feature::DrawIndirectCount`].
+    ///
+    /// [`Feature::DrawIndirectCount`]: crate::Feature::DrawIndirectCount
+    pub fn draw_indirect_count(
+        &mut self,
+        buffer: &Buffer,
+        offset: u64,
+        count_buffer: &Buffer,
+        count_buffer_offset: u64,
+        max_draw_count: u32,
+        stride: u32,
+    ) {
+        check_indirect_draw(
+            buffer,
+            offset,
+            max_draw_count,
+            stride,
+            size_of::<DrawIndirectCommand>(),
+        );
+        check_indirect_buffer(count_buffer, count_buffer_offset, size_of::<u32>());
+
+        self.inner.command_buffer.write(
+            self.scope,
+            Command::DrawIndirectCount {
+                buffer,
+                offset,
+                count_buffer,
+                count_buffer_offset,
+                max_draw_count,
+                stride,
+            },
+        );
+    }
+
+    /// Performs indexed draws with parameters read from the buffer.
+    /// Number of draws is read from `count_buffer` and clamped to `max_draw_count`.
+    ///
+    /// Requires [`Feature::DrawIndirectCount`].
+    ///
+    /// [`Feature::DrawIndirectCount`]: crate::Feature::DrawIndirectCount
+    pub fn draw_indexed_indirect_count(
+        &mut self,
+        buffer: &Buffer,
+        offset: u64,
+        count_buffer: &Buffer,
+        count_buffer_offset: u64,
+        max_draw_count: u32,
+        stride: u32,
+    ) {
+        check_indirect_draw(
+            buffer,
+            offset,
+            max_draw_count,
+            stride,
+            size_of::<DrawIndexedIndirectCommand>(),
+        );
+        check_indirect_buffer(count_buffer, count_buffer_offset, size_of::<u32>());
+
+        self.inner.command_buffer.write(
+            self.scope,
+            Command::DrawIndexedIndirectCount {
+                buffer,
+                offset,
+                count_buffer,
+                count_buffer_offset,
+                max_draw_count,
+                stride,
+            },
+        );
+    }
+
     pub fn bind_dynamic_graphics_pipeline(
         &mut self,
         pipeline: &mut DynamicGraphicsPipeline,
@@ -1080,6 +1869,130 @@ impl<'a, 'b> RenderingEncoder<'a, 'b> {
         );
     }
 
+    /// Performs draws with parameters read from the buffer.
+    /// See [`DrawIndirectCommand`].
+    pub fn draw_indirect(&mut self, buffer: &Buffer, offset: u64, draw_count: u32, stride: u32) {
+        check_indirect_draw(
+            buffer,
+            offset,
+            draw_count,
+            stride,
+            size_of::<DrawIndirectCommand>(),
+        );
+
+        self.inner.command_buffer.write(
+            self.scope,
+            Command::DrawIndirect {
+                buffer,
+                offset,
+                draw_count,
+                stride,
+            },
+        );
+    }
+
+    /// Performs indexed draws with parameters read from the buffer.
+    /// See [`DrawIndexedIndirectCommand`].
+    pub fn draw_indexed_indirect(
+        &mut self,
+        buffer: &Buffer,
+        offset: u64,
+        draw_count: u32,
+        stride: u32,
+    ) {
+        check_indirect_draw(
+            buffer,
+            offset,
+            draw_count,
+            stride,
+            size_of::<DrawIndexedIndirectCommand>(),
+        );
+
+        self.inner.command_buffer.write(
+            self.scope,
+            Command::DrawIndexedIndirect {
+                buffer,
+                offset,
+                draw_count,
+                stride,
+            },
+        );
+    }
+
+    /// Performs draws with parameters read from the buffer.
+    /// Number of draws is read from `count_buffer` and clamped to `max_draw_count`.
+    ///
+    /// Requires [`Feature::DrawIndirectCount`].
+    ///
+    /// [`Feature::DrawIndirectCount`]: crate::Feature::DrawIndirectCount
+    pub fn draw_indirect_count(
+        &mut self,
+        buffer: &Buffer,
+        offset: u64,
+        count_buffer: &Buffer,
+        count_buffer_offset: u64,
+        max_draw_count: u32,
+        stride: u32,
+    ) {
+        check_indirect_draw(
+            buffer,
+            offset,
+            max_draw_count,
+            stride,
+            size_of::<DrawIndirectCommand>(),
+        );
+        check_indirect_buffer(count_buffer, count_buffer_offset, size_of::<u32>());
+
+        self.inner.command_buffer.write(
+            self.scope,
+            Command::DrawIndirectCount {
+                buffer,
+                offset,
+                count_buffer,
+                count_buffer_offset,
+                max_draw_count,
+                stride,
+            },
+        );
+    }
+
+    /// Performs indexed draws with parameters read from the buffer.
+    /// Number of draws is read from `count_buffer` and clamped to `max_draw_count`.
+    ///
+    /// Requires [`Feature::DrawIndirectCount`].
+    ///
+    /// [`Feature::DrawIndirectCount`]: crate::Feature::DrawIndirectCount
+    pub fn draw_indexed_indirect_count(
+        &mut self,
+        buffer: &Buffer,
+        offset: u64,
+        count_buffer: &Buffer,
+        count_buffer_offset: u64,
+        max_draw_count: u32,
+        stride: u32,
+    ) {
+        check_indirect_draw(
+            buffer,
+            offset,
+            max_draw_count,
+            stride,
+            size_of::<DrawIndexedIndirectCommand>(),
+        );
+        check_indirect_buffer(count_buffer, count_buffer_offset, size_of::<u32>());
+
+        self.inner.command_buffer.write(
+            self.scope,
+            Command::DrawIndexedIndirectCount {
+                buffer,
+                offset,
+                count_buffer,
+                count_buffer_offset,
+                max_draw_count,
+                stride,
+            },
+        );
+    }
+
     pub fn bind_dynamic_graphics_pipeline(
         &mut self,
         pipeline: &mut DynamicGraphicsPipeline,
@@ -1130,3 +2043,80 @@ impl<'a, 'b> std::ops::DerefMut for RenderingEncoder<'a, 'b> {
         self.inner
     }
 }
+
+fn check_indirect_buffer(buffer: &Buffer, offset: u64, size: usize) {
+    debug_assert!(
+        buffer.info().usage.contains(BufferUsage::INDIRECT),
+        "Buffer must be created with `BufferUsage::INDIRECT`"
+    );
+    debug_assert_eq!(offset & 3, 0, "Offset must be multiple of 4");
+    debug_assert!(
+        offset + size as u64 <= buffer.info().size,
+        "Indirect command is out of buffer bounds"
+    );
+}
+
+fn check_indirect_draw(buffer: &Buffer, offset: u64, draw_count: u32, stride: u32, size: usize) {
+    if draw_count > 1 {
+        debug_assert_eq!(stride & 3, 0, "Stride must be multiple of 4");
+        debug_assert!(
+            arith_ge(stride, size),
+            "Stride must not be less than size of the indirect command"
+        );
+
+        let last = offset + u64::from(stride) * u64::from(draw_count - 1);
+        check_indirect_buffer(buffer, last, size);
+    }
+
+    if draw_count > 0 {
+        check_indirect_buffer(buffer, offset, size);
+    }
+}
+
+/// All write accesses that may precede reading for download.
+const WRITE_ACCESS: Access = Access::from_bits_truncate(
+    Access::SHADER_STORAGE_WRITE.bits()
+        | Access::COLOR_ATTACHMENT_WRITE.bits()
+        | Access::DEPTH_STENCIL_ATTACHMENT_WRITE.bits()
+        | Access::TRANSFER_WRITE.bits()
+        | Access::HOST_WRITE.bits(),
+);
+
+/// Returns size of tightly packed texel block of the format aspect in bytes.
+fn copy_block_size(format: Format, aspect: AspectFlags) -> u64 {
+    let description = format.description();
+
+    match description.channels {
+        Channels::D | Channels::S | Channels::DS => {
+            assert_eq!(
+                aspect.bits().count_ones(),
+                1,
+                "Depth and stencil aspects must be copied separately"
+            );
+
+            if aspect == AspectFlags::STENCIL {
+                1
+            } else if description.bits == 24 {
+                // 24 bit depth is copied as 32 bit values.
+                4
+            } else {
+                u64::from(description.bits / 8)
+            }
+        }
+        _ => u64::from(format.block_size()),
+    }
+}
+
+/// Returns size of tightly packed image region in bytes.
+fn image_region_size(image: &Image, subresource: SubresourceLayers, extent: Extent3) -> u64 {
+    let format = image.info().format;
+    let block = format.block_extent();
+
+    let row_pitch =
+        copy_block_size(format, subresource.aspect) * u64::from(extent.width.div_ceil(block.width));
+
+    row_pitch
+        * u64::from(extent.height.div_ceil(block.height))
+        * u64::from(extent.depth)
+        * u64::from(subresource.layer_count)
+}
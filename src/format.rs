@@ -1,3 +1,5 @@
+use crate::Extent2;
+
 /// Texel format.
 /// Images can have different texel formats.
 /// Some of which are color or depth and/or stencil.
@@ -106,6 +108,81 @@ pub enum Format {
     D16UnormS8Uint,
     D24UnormS8Uint,
     D32SfloatS8Uint,
+    R4G4B4A4Unorm,
+    B4G4R4A4Unorm,
+    R5G6B5Unorm,
+    B5G6R5Unorm,
+    R5G5B5A1Unorm,
+    B5G5R5A1Unorm,
+    A1R5G5B5Unorm,
+    A2R10G10B10Unorm,
+    A2R10G10B10Snorm,
+    A2R10G10B10Uscaled,
+    A2R10G10B10Sscaled,
+    A2R10G10B10Uint,
+    A2R10G10B10Sint,
+    A2B10G10R10Unorm,
+    A2B10G10R10Snorm,
+    A2B10G10R10Uscaled,
+    A2B10G10R10Sscaled,
+    A2B10G10R10Uint,
+    A2B10G10R10Sint,
+    B10G11R11Ufloat,
+    E5B9G9R9Ufloat,
+    BC1RGBUnorm,
+    BC1RGBSrgb,
+    BC1RGBAUnorm,
+    BC1RGBASrgb,
+    BC2Unorm,
+    BC2Srgb,
+    BC3Unorm,
+    BC3Srgb,
+    BC4Unorm,
+    BC4Snorm,
+    BC5Unorm,
+    BC5Snorm,
+    BC6HUfloat,
+    BC6HSfloat,
+    BC7Unorm,
+    BC7Srgb,
+    ETC2RGB8Unorm,
+    ETC2RGB8Srgb,
+    ETC2RGB8A1Unorm,
+    ETC2RGB8A1Srgb,
+    ETC2RGBA8Unorm,
+    ETC2RGBA8Srgb,
+    EACR11Unorm,
+    EACR11Snorm,
+    EACRG11Unorm,
+    EACRG11Snorm,
+    ASTC4x4Unorm,
+    ASTC4x4Srgb,
+    ASTC5x4Unorm,
+    ASTC5x4Srgb,
+    ASTC5x5Unorm,
+    ASTC5x5Srgb,
+    ASTC6x5Unorm,
+    ASTC6x5Srgb,
+    ASTC6x6Unorm,
+    ASTC6x6Srgb,
+    ASTC8x5Unorm,
+    ASTC8x5Srgb,
+    ASTC8x6Unorm,
+    ASTC8x6Srgb,
+    ASTC8x8Unorm,
+    ASTC8x8Srgb,
+    ASTC10x5Unorm,
+    ASTC10x5Srgb,
+    ASTC10x6Unorm,
+    ASTC10x6Srgb,
+    ASTC10x8Unorm,
+    ASTC10x8Srgb,
+    ASTC10x10Unorm,
+    ASTC10x10Srgb,
+    ASTC12x10Unorm,
+    ASTC12x10Srgb,
+    ASTC12x12Unorm,
+    ASTC12x12Srgb,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -119,6 +196,7 @@ pub enum Type {
     Uscaled,
     Sscaled,
     Sfloat,
+    Ufloat,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -133,6 +211,44 @@ pub enum Channels {
     D,
     S,
     DS,
+    R4G4B4A4,
+    B4G4R4A4,
+    R5G6B5,
+    B5G6R5,
+    R5G5B5A1,
+    B5G5R5A1,
+    A1R5G5B5,
+    A2R10G10B10,
+    A2B10G10R10,
+    B10G11R11,
+    E5B9G9R9,
+    BC1RGB,
+    BC1RGBA,
+    BC2,
+    BC3,
+    BC4,
+    BC5,
+    BC6H,
+    BC7,
+    ETC2RGB,
+    ETC2RGBA1,
+    ETC2RGBA,
+    EACR,
+    EACRG,
+    ASTC4x4,
+    ASTC5x4,
+    ASTC5x5,
+    ASTC6x5,
+    ASTC6x6,
+    ASTC8x5,
+    ASTC8x6,
+    ASTC8x8,
+    ASTC10x5,
+    ASTC10x6,
+    ASTC10x8,
+    ASTC10x10,
+    ASTC12x10,
+    ASTC12x12,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -152,6 +268,58 @@ bitflags::bitflags! {
     }
 }
 
+bitflags::bitflags! {
+    /// Operations supported by device for a format.
+    /// Image features are reported for images with optimal tiling.
+    #[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+    pub struct FormatFeatures: u32 {
+        /// Image can be sampled in shaders.
+        const SAMPLED_IMAGE = 0x0001;
+
+        /// Image can be used as storage image.
+        const STORAGE_IMAGE = 0x0002;
+
+        /// Storage image supports atomic operations.
+        const STORAGE_IMAGE_ATOMIC = 0x0004;
+
+        /// Image can be used as color attachment.
+        const COLOR_ATTACHMENT = 0x0008;
+
+        /// Color attachment supports blending.
+        const COLOR_ATTACHMENT_BLEND = 0x0010;
+
+        /// Image can be used as depth-stencil attachment.
+        const DEPTH_STENCIL_ATTACHMENT = 0x0020;
+
+        /// Sampled image supports linear filtering.
+        const SAMPLED_IMAGE_FILTER_LINEAR = 0x0040;
+
+        /// Image can be used as source of blit commands.
+        const BLIT_SRC = 0x0080;
+
+        /// Image can be used as destination of blit commands.
+        const BLIT_DST = 0x0100;
+
+        /// Image can be used as source of transfer commands.
+        const TRANSFER_SRC = 0x0200;
+
+        /// Image can be used as destination of transfer commands.
+        const TRANSFER_DST = 0x0400;
+
+        /// Format can be used for vertex attributes.
+        const VERTEX_BUFFER = 0x0800;
+
+        /// Format can be used for uniform texel buffers.
+        const UNIFORM_TEXEL_BUFFER = 0x1000;
+
+        /// Format can be used for storage texel buffers.
+        const STORAGE_TEXEL_BUFFER = 0x2000;
+
+        /// Format can be used for acceleration structure vertices.
+        const ACCELERATION_STRUCTURE_VERTEX_BUFFER = 0x4000;
+    }
+}
+
 impl Format {
     pub fn aspect_flags(&self) -> AspectFlags {
         let mut flags = AspectFlags::empty();
@@ -201,6 +369,86 @@ impl Format {
         )
     }
 
+    /// Returns true if format is block-compressed.
+    pub fn is_compressed(&self) -> bool {
+        self.block_extent() != Extent2::new(1, 1)
+    }
+
+    /// Returns extent of the texel block in texels.
+    /// Uncompressed formats have blocks of single texel.
+    pub fn block_extent(&self) -> Extent2 {
+        match self {
+            Self::BC1RGBUnorm
+            | Self::BC1RGBSrgb
+            | Self::BC1RGBAUnorm
+            | Self::BC1RGBASrgb
+            | Self::BC2Unorm
+            | Self::BC2Srgb
+            | Self::BC3Unorm
+            | Self::BC3Srgb
+            | Self::BC4Unorm
+            | Self::BC4Snorm
+            | Self::BC5Unorm
+            | Self::BC5Snorm
+            | Self::BC6HUfloat
+            | Self::BC6HSfloat
+            | Self::BC7Unorm
+            | Self::BC7Srgb
+            | Self::ETC2RGB8Unorm
+            | Self::ETC2RGB8Srgb
+            | Self::ETC2RGB8A1Unorm
+            | Self::ETC2RGB8A1Srgb
+            | Self::ETC2RGBA8Unorm
+            | Self::ETC2RGBA8Srgb
+            | Self::EACR11Unorm
+            | Self::EACR11Snorm
+            | Self::EACRG11Unorm
+            | Self::EACRG11Snorm
+            | Self::ASTC4x4Unorm
+            | Self::ASTC4x4Srgb => Extent2::new(4, 4),
+            Self::ASTC5x4Unorm | Self::ASTC5x4Srgb => Extent2::new(5, 4),
+            Self::ASTC5x5Unorm | Self::ASTC5x5Srgb => Extent2::new(5, 5),
+            Self::ASTC6x5Unorm | Self::ASTC6x5Srgb => Extent2::new(6, 5),
+            Self::ASTC6x6Unorm | Self::ASTC6x6Srgb => Extent2::new(6, 6),
+            Self::ASTC8x5Unorm | Self::ASTC8x5Srgb => Extent2::new(8, 5),
+            Self::ASTC8x6Unorm | Self::ASTC8x6Srgb => Extent2::new(8, 6),
+            Self::ASTC8x8Unorm | Self::ASTC8x8Srgb => Extent2::new(8, 8),
+            Self::ASTC10x5Unorm | Self::ASTC10x5Srgb => Extent2::new(10, 5),
+            Self::ASTC10x6Unorm | Self::ASTC10x6Srgb => Extent2::new(10, 6),
+            Self::ASTC10x8Unorm | Self::ASTC10x8Srgb => Extent2::new(10, 8),
+            Self::ASTC10x10Unorm | Self::ASTC10x10Srgb => Extent2::new(10, 10),
+            Self::ASTC12x10Unorm | Self::ASTC12x10Srgb => Extent2::new(12, 10),
+            Self::ASTC12x12Unorm | Self::ASTC12x12Srgb => Extent2::new(12, 12),
+            _ => Extent2::new(1, 1),
+        }
+    }
+
+    /// Returns size of the texel block in bytes.
+    ///
+    /// For depth-stencil formats this is the size of both aspects together,
+    /// while copy commands operate on aspects separately.
+    pub fn block_size(&self) -> u32 {
+        let description = self.description();
+
+        match description.channels {
+            Channels::R | Channels::D | Channels::S => description.bits / 8,
+            Channels::RG => description.bits / 4,
+            Channels::RGB | Channels::BGR => description.bits * 3 / 8,
+            Channels::RGBA | Channels::BGRA => description.bits / 2,
+            Channels::DS => match self {
+                Self::D24UnormS8Uint => 4,
+                _ => description.bits / 8 + 1,
+            },
+            // Packed and compressed formats have size of whole block specified.
+            _ => description.bits / 8,
+        }
+    }
+
+    /// Returns description of the format.
+    ///
+    /// For plain formats `bits` is number of bits per channel.
+    /// For packed and block-compressed formats `channels` specify layout of the whole
+    /// texel or block and `bits` is its total size.
     pub fn description(&self) -> FormatDescription<Channels, u32, Type> {
         match self {
             Self::R8Unorm => FormatDescription {
@@ -703,6 +951,381 @@ impl Format {
                 bits: 32,
                 ty: Type::Sfloat,
             },
+            Self::R4G4B4A4Unorm => FormatDescription {
+                channels: Channels::R4G4B4A4,
+                bits: 16,
+                ty: Type::Unorm,
+            },
+            Self::B4G4R4A4Unorm => FormatDescription {
+                channels: Channels::B4G4R4A4,
+                bits: 16,
+                ty: Type::Unorm,
+            },
+            Self::R5G6B5Unorm => FormatDescription {
+                channels: Channels::R5G6B5,
+                bits: 16,
+                ty: Type::Unorm,
+            },
+            Self::B5G6R5Unorm => FormatDescription {
+                channels: Channels::B5G6R5,
+                bits: 16,
+                ty: Type::Unorm,
+            },
+            Self::R5G5B5A1Unorm => FormatDescription {
+                channels: Channels::R5G5B5A1,
+                bits: 16,
+                ty: Type::Unorm,
+            },
+            Self::B5G5R5A1Unorm => FormatDescription {
+                channels: Channels::B5G5R5A1,
+                bits: 16,
+                ty: Type::Unorm,
+            },
+            Self::A1R5G5B5Unorm => FormatDescription {
+                channels: Channels::A1R5G5B5,
+                bits: 16,
+                ty: Type::Unorm,
+            },
+            Self::A2R10G10B10Unorm => FormatDescription {
+                channels: Channels::A2R10G10B10,
+                bits: 32,
+                ty: Type::Unorm,
+            },
+            Self::A2R10G10B10Snorm => FormatDescription {
+                channels: Channels::A2R10G10B10,
+                bits: 32,
+                ty: Type::Snorm,
+            },
+            Self::A2R10G10B10Uscaled => FormatDescription {
+                channels: Channels::A2R10G10B10,
+                bits: 32,
+                ty: Type::Uscaled,
+            },
+            Self::A2R10G10B10Sscaled => FormatDescription {
+                channels: Channels::A2R10G10B10,
+                bits: 32,
+                ty: Type::Sscaled,
+            },
+            Self::A2R10G10B10Uint => FormatDescription {
+                channels: Channels::A2R10G10B10,
+                bits: 32,
+                ty: Type::Uint,
+            },
+            Self::A2R10G10B10Sint => FormatDescription {
+                channels: Channels::A2R10G10B10,
+                bits: 32,
+                ty: Type::Sint,
+            },
+            Self::A2B10G10R10Unorm => FormatDescription {
+                channels: Channels::A2B10G10R10,
+                bits: 32,
+                ty: Type::Unorm,
+            },
+            Self::A2B10G10R10Snorm => FormatDescription {
+                channels: Channels::A2B10G10R10,
+                bits: 32,
+                ty: Type::Snorm,
+            },
+            Self::A2B10G10R10Uscaled => FormatDescription {
+                channels: Channels::A2B10G10R10,
+                bits: 32,
+                ty: Type::Uscaled,
+            },
+            Self::A2B10G10R10Sscaled => FormatDescription {
+                channels: Channels::A2B10G10R10,
+                bits: 32,
+                ty: Type::Sscaled,
+            },
+            Self::A2B10G10R10Uint => FormatDescription {
+                channels: Channels::A2B10G10R10,
+                bits: 32,
+                ty: Type::Uint,
+            },
+            Self::A2B10G10R10Sint => FormatDescription {
+                channels: Channels::A2B10G10R10,
+                bits: 32,
+                ty: Type::Sint,
+            },
+            Self::B10G11R11Ufloat => FormatDescription {
+                channels: Channels::B10G11R11,
+                bits: 32,
+                ty: Type::Ufloat,
+            },
+            Self::E5B9G9R9Ufloat => FormatDescription {
+                channels: Channels::E5B9G9R9,
+                bits: 32,
+                ty: Type::Ufloat,
+            },
+            Self::BC1RGBUnorm => FormatDescription {
+                channels: Channels::BC1RGB,
+                bits: 64,
+                ty: Type::Unorm,
+            },
+            Self::BC1RGBSrgb => FormatDescription {
+                channels: Channels::BC1RGB,
+                bits: 64,
+                ty: Type::Srgb,
+            },
+            Self::BC1RGBAUnorm => FormatDescription {
+                channels: Channels::BC1RGBA,
+                bits: 64,
+                ty: Type::Unorm,
+            },
+            Self::BC1RGBASrgb => FormatDescription {
+                channels: Channels::BC1RGBA,
+                bits: 64,
+                ty: Type::Srgb,
+            },
+            Self::BC2Unorm => FormatDescription {
+                channels: Channels::BC2,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::BC2Srgb => FormatDescription {
+                channels: Channels::BC2,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::BC3Unorm => FormatDescription {
+                channels: Channels::BC3,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::BC3Srgb => FormatDescription {
+                channels: Channels::BC3,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::BC4Unorm => FormatDescription {
+                channels: Channels::BC4,
+                bits: 64,
+                ty: Type::Unorm,
+            },
+            Self::BC4Snorm => FormatDescription {
+                channels: Channels::BC4,
+                bits: 64,
+                ty: Type::Snorm,
+            },
+            Self::BC5Unorm => FormatDescription {
+                channels: Channels::BC5,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::BC5Snorm => FormatDescription {
+                channels: Channels::BC5,
+                bits: 128,
+                ty: Type::Snorm,
+            },
+            Self::BC6HUfloat => FormatDescription {
+                channels: Channels::BC6H,
+                bits: 128,
+                ty: Type::Ufloat,
+            },
+            Self::BC6HSfloat => FormatDescription {
+                channels: Channels::BC6H,
+                bits: 128,
+                ty: Type::Sfloat,
+            },
+            Self::BC7Unorm => FormatDescription {
+                channels: Channels::BC7,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::BC7Srgb => FormatDescription {
+                channels: Channels::BC7,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ETC2RGB8Unorm => FormatDescription {
+                channels: Channels::ETC2RGB,
+                bits: 64,
+                ty: Type::Unorm,
+            },
+            Self::ETC2RGB8Srgb => FormatDescription {
+                channels: Channels::ETC2RGB,
+                bits: 64,
+                ty: Type::Srgb,
+            },
+            Self::ETC2RGB8A1Unorm => FormatDescription {
+                channels: Channels::ETC2RGBA1,
+                bits: 64,
+                ty: Type::Unorm,
+            },
+            Self::ETC2RGB8A1Srgb => FormatDescription {
+                channels: Channels::ETC2RGBA1,
+                bits: 64,
+                ty: Type::Srgb,
+            },
+            Self::ETC2RGBA8Unorm => FormatDescription {
+                channels: Channels::ETC2RGBA,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ETC2RGBA8Srgb => FormatDescription {
+                channels: Channels::ETC2RGBA,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::EACR11Unorm => FormatDescription {
+                channels: Channels::EACR,
+                bits: 64,
+                ty: Type::Unorm,
+            },
+            Self::EACR11Snorm => FormatDescription {
+                channels: Channels::EACR,
+                bits: 64,
+                ty: Type::Snorm,
+            },
+            Self::EACRG11Unorm => FormatDescription {
+                channels: Channels::EACRG,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::EACRG11Snorm => FormatDescription {
+                channels: Channels::EACRG,
+                bits: 128,
+                ty: Type::Snorm,
+            },
+            Self::ASTC4x4Unorm => FormatDescription {
+                channels: Channels::ASTC4x4,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC4x4Srgb => FormatDescription {
+                channels: Channels::ASTC4x4,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ASTC5x4Unorm => FormatDescription {
+                channels: Channels::ASTC5x4,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC5x4Srgb => FormatDescription {
+                channels: Channels::ASTC5x4,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ASTC5x5Unorm => FormatDescription {
+                channels: Channels::ASTC5x5,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC5x5Srgb => FormatDescription {
+                channels: Channels::ASTC5x5,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ASTC6x5Unorm => FormatDescription {
+                channels: Channels::ASTC6x5,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC6x5Srgb => FormatDescription {
+                channels: Channels::ASTC6x5,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ASTC6x6Unorm => FormatDescription {
+                channels: Channels::ASTC6x6,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC6x6Srgb => FormatDescription {
+                channels: Channels::ASTC6x6,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ASTC8x5Unorm => FormatDescription {
+                channels: Channels::ASTC8x5,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC8x5Srgb => FormatDescription {
+                channels: Channels::ASTC8x5,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ASTC8x6Unorm => FormatDescription {
+                channels: Channels::ASTC8x6,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC8x6Srgb => FormatDescription {
+                channels: Channels::ASTC8x6,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ASTC8x8Unorm => FormatDescription {
+                channels: Channels::ASTC8x8,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC8x8Srgb => FormatDescription {
+                channels: Channels::ASTC8x8,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ASTC10x5Unorm => FormatDescription {
+                channels: Channels::ASTC10x5,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC10x5Srgb => FormatDescription {
+                channels: Channels::ASTC10x5,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ASTC10x6Unorm => FormatDescription {
+                channels: Channels::ASTC10x6,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC10x6Srgb => FormatDescription {
+                channels: Channels::ASTC10x6,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ASTC10x8Unorm => FormatDescription {
+                channels: Channels::ASTC10x8,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC10x8Srgb => FormatDescription {
+                channels: Channels::ASTC10x8,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ASTC10x10Unorm => FormatDescription {
+                channels: Channels::ASTC10x10,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC10x10Srgb => FormatDescription {
+                channels: Channels::ASTC10x10,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ASTC12x10Unorm => FormatDescription {
+                channels: Channels::ASTC12x10,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC12x10Srgb => FormatDescription {
+                channels: Channels::ASTC12x10,
+                bits: 128,
+                ty: Type::Srgb,
+            },
+            Self::ASTC12x12Unorm => FormatDescription {
+                channels: Channels::ASTC12x12,
+                bits: 128,
+                ty: Type::Unorm,
+            },
+            Self::ASTC12x12Srgb => FormatDescription {
+                channels: Channels::ASTC12x12,
+                bits: 128,
+                ty: Type::Srgb,
+            },
         }
     }
 }
@@ -734,6 +1357,120 @@ pub enum S {}
 #[derive(Clone, Copy, Debug)]
 pub enum DS {}
 
+#[derive(Clone, Copy, Debug)]
+pub enum R4G4B4A4 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum B4G4R4A4 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum R5G6B5 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum B5G6R5 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum R5G5B5A1 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum B5G5R5A1 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum A1R5G5B5 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum A2R10G10B10 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum A2B10G10R10 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum B10G11R11 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum E5B9G9R9 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BC1RGB {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BC1RGBA {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BC2 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BC3 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BC4 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BC5 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BC6H {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BC7 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ETC2RGB {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ETC2RGBA1 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ETC2RGBA {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum EACR {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum EACRG {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC4x4 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC5x4 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC5x5 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC6x5 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC6x6 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC8x5 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC8x6 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC8x8 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC10x5 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC10x6 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC10x8 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC10x10 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC12x10 {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ASTC12x12 {}
+
 #[derive(Clone, Copy, Debug)]
 pub enum Uint {}
 
@@ -758,6 +1495,9 @@ pub enum Sscaled {}
 #[derive(Clone, Copy, Debug)]
 pub enum Sfloat {}
 
+#[derive(Clone, Copy, Debug)]
+pub enum Ufloat {}
+
 #[derive(Clone, Copy, Debug)]
 pub enum ConstBits<const BITS: u32> {}
 
@@ -1065,3 +1805,228 @@ impl StaticFormat for FormatDescription<DS, ConstBits<24>, Unorm> {
 impl StaticFormat for FormatDescription<DS, ConstBits<32>, Sfloat> {
     const FORMAT: Format = Format::D32SfloatS8Uint;
 }
+impl StaticFormat for FormatDescription<R4G4B4A4, ConstBits<16>, Unorm> {
+    const FORMAT: Format = Format::R4G4B4A4Unorm;
+}
+impl StaticFormat for FormatDescription<B4G4R4A4, ConstBits<16>, Unorm> {
+    const FORMAT: Format = Format::B4G4R4A4Unorm;
+}
+impl StaticFormat for FormatDescription<R5G6B5, ConstBits<16>, Unorm> {
+    const FORMAT: Format = Format::R5G6B5Unorm;
+}
+impl StaticFormat for FormatDescription<B5G6R5, ConstBits<16>, Unorm> {
+    const FORMAT: Format = Format::B5G6R5Unorm;
+}
+impl StaticFormat for FormatDescription<R5G5B5A1, ConstBits<16>, Unorm> {
+    const FORMAT: Format = Format::R5G5B5A1Unorm;
+}
+impl StaticFormat for FormatDescription<B5G5R5A1, ConstBits<16>, Unorm> {
+    const FORMAT: Format = Format::B5G5R5A1Unorm;
+}
+impl StaticFormat for FormatDescription<A1R5G5B5, ConstBits<16>, Unorm> {
+    const FORMAT: Format = Format::A1R5G5B5Unorm;
+}
+impl StaticFormat for FormatDescription<A2R10G10B10, ConstBits<32>, Unorm> {
+    const FORMAT: Format = Format::A2R10G10B10Unorm;
+}
+impl StaticFormat for FormatDescription<A2R10G10B10, ConstBits<32>, Snorm> {
+    const FORMAT: Format = Format::A2R10G10B10Snorm;
+}
+impl StaticFormat for FormatDescription<A2R10G10B10, ConstBits<32>, Uscaled> {
+    const FORMAT: Format = Format::A2R10G10B10Uscaled;
+}
+impl StaticFormat for FormatDescription<A2R10G10B10, ConstBits<32>, Sscaled> {
+    const FORMAT: Format = Format::A2R10G10B10Sscaled;
+}
+impl StaticFormat for FormatDescription<A2R10G10B10, ConstBits<32>, Uint> {
+    const FORMAT: Format = Format::A2R10G10B10Uint;
+}
+impl StaticFormat for FormatDescription<A2R10G10B10, ConstBits<32>, Sint> {
+    const FORMAT: Format = Format::A2R10G10B10Sint;
+}
+impl StaticFormat for FormatDescription<A2B10G10R10, ConstBits<32>, Unorm> {
+    const FORMAT: Format = Format::A2B10G10R10Unorm;
+}
+impl StaticFormat for FormatDescription<A2B10G10R10, ConstBits<32>, Snorm> {
+    const FORMAT: Format = Format::A2B10G10R10Snorm;
+}
+impl StaticFormat for FormatDescription<A2B10G10R10, ConstBits<32>, Uscaled> {
+    const FORMAT: Format = Format::A2B10G10R10Uscaled;
+}
+impl StaticFormat for FormatDescription<A2B10G10R10, ConstBits<32>, Sscaled> {
+    const FORMAT: Format = Format::A2B10G10R10Sscaled;
+}
+impl StaticFormat for FormatDescription<A2B10G10R10, ConstBits<32>, Uint> {
+    const FORMAT: Format = Format::A2B10G10R10Uint;
+}
+impl StaticFormat for FormatDescription<A2B10G10R10, ConstBits<32>, Sint> {
+    const FORMAT: Format = Format::A2B10G10R10Sint;
+}
+impl StaticFormat for FormatDescription<B10G11R11, ConstBits<32>, Ufloat> {
+    const FORMAT: Format = Format::B10G11R11Ufloat;
+}
+impl StaticFormat for FormatDescription<E5B9G9R9, ConstBits<32>, Ufloat> {
+    const FORMAT: Format = Format::E5B9G9R9Ufloat;
+}
+impl StaticFormat for FormatDescription<BC1RGB, ConstBits<64>, Unorm> {
+    const FORMAT: Format = Format::BC1RGBUnorm;
+}
+impl StaticFormat for FormatDescription<BC1RGB, ConstBits<64>, Srgb> {
+    const FORMAT: Format = Format::BC1RGBSrgb;
+}
+impl StaticFormat for FormatDescription<BC1RGBA, ConstBits<64>, Unorm> {
+    const FORMAT: Format = Format::BC1RGBAUnorm;
+}
+impl StaticFormat for FormatDescription<BC1RGBA, ConstBits<64>, Srgb> {
+    const FORMAT: Format = Format::BC1RGBASrgb;
+}
+impl StaticFormat for FormatDescription<BC2, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::BC2Unorm;
+}
+impl StaticFormat for FormatDescription<BC2, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::BC2Srgb;
+}
+impl StaticFormat for FormatDescription<BC3, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::BC3Unorm;
+}
+impl StaticFormat for FormatDescription<BC3, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::BC3Srgb;
+}
+impl StaticFormat for FormatDescription<BC4, ConstBits<64>, Unorm> {
+    const FORMAT: Format = Format::BC4Unorm;
+}
+impl StaticFormat for FormatDescription<BC4, ConstBits<64>, Snorm> {
+    const FORMAT: Format = Format::BC4Snorm;
+}
+impl StaticFormat for FormatDescription<BC5, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::BC5Unorm;
+}
+impl StaticFormat for FormatDescription<BC5, ConstBits<128>, Snorm> {
+    const FORMAT: Format = Format::BC5Snorm;
+}
+impl StaticFormat for FormatDescription<BC6H, ConstBits<128>, Ufloat> {
+    const FORMAT: Format = Format::BC6HUfloat;
+}
+impl StaticFormat for FormatDescription<BC6H, ConstBits<128>, Sfloat> {
+    const FORMAT: Format = Format::BC6HSfloat;
+}
+impl StaticFormat for FormatDescription<BC7, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::BC7Unorm;
+}
+impl StaticFormat for FormatDescription<BC7, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::BC7Srgb;
+}
+impl StaticFormat for FormatDescription<ETC2RGB, ConstBits<64>, Unorm> {
+    const FORMAT: Format = Format::ETC2RGB8Unorm;
+}
+impl StaticFormat for FormatDescription<ETC2RGB, ConstBits<64>, Srgb> {
+    const FORMAT: Format = Format::ETC2RGB8Srgb;
+}
+impl StaticFormat for FormatDescription<ETC2RGBA1, ConstBits<64>, Unorm> {
+    const FORMAT: Format = Format::ETC2RGB8A1Unorm;
+}
+impl StaticFormat for FormatDescription<ETC2RGBA1, ConstBits<64>, Srgb> {
+    const FORMAT: Format = Format::ETC2RGB8A1Srgb;
+}
+impl StaticFormat for FormatDescription<ETC2RGBA, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ETC2RGBA8Unorm;
+}
+impl StaticFormat for FormatDescription<ETC2RGBA, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ETC2RGBA8Srgb;
+}
+impl StaticFormat for FormatDescription<EACR, ConstBits<64>, Unorm> {
+    const FORMAT: Format = Format::EACR11Unorm;
+}
+impl StaticFormat for FormatDescription<EACR, ConstBits<64>, Snorm> {
+    const FORMAT: Format = Format::EACR11Snorm;
+}
+impl StaticFormat for FormatDescription<EACRG, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::EACRG11Unorm;
+}
+impl StaticFormat for FormatDescription<EACRG, ConstBits<128>, Snorm> {
+    const FORMAT: Format = Format::EACRG11Snorm;
+}
+impl StaticFormat for FormatDescription<ASTC4x4, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC4x4Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC4x4, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC4x4Srgb;
+}
+impl StaticFormat for FormatDescription<ASTC5x4, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC5x4Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC5x4, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC5x4Srgb;
+}
+impl StaticFormat for FormatDescription<ASTC5x5, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC5x5Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC5x5, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC5x5Srgb;
+}
+impl StaticFormat for FormatDescription<ASTC6x5, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC6x5Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC6x5, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC6x5Srgb;
+}
+impl StaticFormat for FormatDescription<ASTC6x6, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC6x6Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC6x6, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC6x6Srgb;
+}
+impl StaticFormat for FormatDescription<ASTC8x5, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC8x5Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC8x5, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC8x5Srgb;
+}
+impl StaticFormat for FormatDescription<ASTC8x6, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC8x6Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC8x6, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC8x6Srgb;
+}
+impl StaticFormat for FormatDescription<ASTC8x8, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC8x8Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC8x8, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC8x8Srgb;
+}
+impl StaticFormat for FormatDescription<ASTC10x5, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC10x5Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC10x5, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC10x5Srgb;
+}
+impl StaticFormat for FormatDescription<ASTC10x6, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC10x6Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC10x6, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC10x6Srgb;
+}
+impl StaticFormat for FormatDescription<ASTC10x8, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC10x8Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC10x8, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC10x8Srgb;
+}
+impl StaticFormat for FormatDescription<ASTC10x10, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC10x10Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC10x10, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC10x10Srgb;
+}
+impl StaticFormat for FormatDescription<ASTC12x10, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC12x10Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC12x10, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC12x10Srgb;
+}
+impl StaticFormat for FormatDescription<ASTC12x12, ConstBits<128>, Unorm> {
+    const FORMAT: Format = Format::ASTC12x12Unorm;
+}
+impl StaticFormat for FormatDescription<ASTC12x12, ConstBits<128>, Srgb> {
+    const FORMAT: Format = Format::ASTC12x12Srgb;
+}
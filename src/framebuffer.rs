@@ -215,6 +215,39 @@ pub enum FramebufferError {
     )]
     DepthAttachmentReferenceOutOfBound { subpass: usize, attachment: u32 },
 
+    #[error(
+        "Subpass {subpass} attachment index {attachment} for input attachment {index} is out of bounds"
+    )]
+    InputAttachmentReferenceOutOfBound {
+        subpass: usize,
+        index: usize,
+        attachment: u32,
+    },
+
+    #[error(
+        "Subpass {subpass} attachment index {attachment} for resolve attachment {index} is out of bounds"
+    )]
+    ResolveAttachmentReferenceOutOfBound {
+        subpass: usize,
+        index: usize,
+        attachment: u32,
+    },
+
+    #[error("Subpass {subpass} has {resolves} resolve attachments for {colors} color attachments")]
+    ResolveAttachmentCountMismatch {
+        subpass: usize,
+        colors: usize,
+        resolves: usize,
+    },
+
+    #[error(
+        "Subpass {subpass} attachment index {attachment} for depth resolve attachment is out of bounds"
+    )]
+    DepthResolveAttachmentReferenceOutOfBound { subpass: usize, attachment: u32 },
+
+    #[error("Subpass {subpass} uses depth-stencil resolve which requires Vulkan 1.2")]
+    DepthResolveNotSupported { subpass: usize },
+
     #[error("Parameters combination `{info:?}` is unsupported")]
     Unsupported { info: ImageInfo },
 }
@@ -242,6 +275,43 @@ impl From<CreateRenderPassError> for FramebufferError {
                 subpass,
                 attachment,
             },
+            CreateRenderPassError::InputAttachmentReferenceOutOfBound {
+                subpass,
+                index,
+                attachment,
+            } => FramebufferError::InputAttachmentReferenceOutOfBound {
+                subpass,
+                index,
+                attachment,
+            },
+            CreateRenderPassError::ResolveAttachmentReferenceOutOfBound {
+                subpass,
+                index,
+                attachment,
+            } => FramebufferError::ResolveAttachmentReferenceOutOfBound {
+                subpass,
+                index,
+                attachment,
+            },
+            CreateRenderPassError::ResolveAttachmentCountMismatch {
+                subpass,
+                colors,
+                resolves,
+            } => FramebufferError::ResolveAttachmentCountMismatch {
+                subpass,
+                colors,
+                resolves,
+            },
+            CreateRenderPassError::DepthResolveAttachmentReferenceOutOfBound {
+                subpass,
+                attachment,
+            } => FramebufferError::DepthResolveAttachmentReferenceOutOfBound {
+                subpass,
+                attachment,
+            },
+            CreateRenderPassError::DepthResolveNotSupported { subpass } => {
+                FramebufferError::DepthResolveNotSupported { subpass }
+            }
         }
     }
 }
@@ -1,97 +1,150 @@
 use {
-    crate::{repr::*, shader::ShaderStage},
-    std::{any::TypeId, borrow::Cow, collections::HashSet},
+    crate::{repr::*, shader::ShaderStage, wgsl::WgslTypeContext},
+    std::{
+        any::TypeId,
+        borrow::Cow,
+        collections::{BTreeSet, HashSet},
+    },
 };
 
-/// Provides shader input
+/// Line in GLSL source that is replaced with generated declarations
+/// by [`ShaderInclude::include_glsl`].
+pub const GLSL_INCLUDE: &str = "#include \"sierra_generated.h\"";
+
+/// Line in WGSL source that is replaced with generated declarations
+/// by [`ShaderInclude::include_wgsl`].
+pub const WGSL_INCLUDE: &str = "#include \"sierra_generated.wgsl\"";
+
+/// Provides shader declarations generated from Rust types.
+///
+/// Implemented by `#[derive(Descriptors)]`, `#[derive(PipelineInput)]`
+/// and `#[derive(ShaderRepr)]`.
 pub trait ShaderInclude {
+    /// Returns GLSL declarations visible in specified stage.
     fn glsl(stage: ShaderStage) -> String;
 
+    /// Returns WGSL declarations visible in specified stage.
+    fn wgsl(stage: ShaderStage) -> String;
+
+    /// Replaces `#include "sierra_generated.h"` lines in GLSL code
+    /// with declarations for specified stage.
     fn include_glsl(stage: ShaderStage, code: &str) -> String {
-        code.lines()
-            .map(|line| {
-                let include_here = line
-                    .strip_prefix("#include \"sierra_generated.h\"")
-                    .is_some();
-
-                if include_here {
-                    Cow::Owned(Self::glsl(stage))
-                } else {
-                    Cow::Borrowed(line)
-                }
-            })
-            .collect()
+        include(code, GLSL_INCLUDE, || Self::glsl(stage))
+    }
+
+    /// Replaces `#include "sierra_generated.wgsl"` lines in WGSL code
+    /// with declarations for specified stage.
+    fn include_wgsl(stage: ShaderStage, code: &str) -> String {
+        include(code, WGSL_INCLUDE, || Self::wgsl(stage))
+    }
+}
+
+/// Provides shader declarations for descriptor set bound at any index.
+///
+/// Implemented by `#[derive(Descriptors)]`.
+pub trait DescriptorsInclude {
+    /// Returns GLSL declarations of bindings in `set` visible in specified stage.
+    /// Definitions of types used by the bindings are added to `ctx`.
+    fn glsl_set(set: u32, stage: ShaderStage, ctx: &mut GlslTypeContext) -> String;
+
+    /// Returns WGSL declarations of bindings in `group` visible in specified stage.
+    /// Definitions of types used by the bindings are added to `ctx`.
+    fn wgsl_group(group: u32, stage: ShaderStage, ctx: &mut WgslTypeContext) -> String;
+}
+
+fn include(code: &str, marker: &str, generate: impl Fn() -> String) -> String {
+    let mut result = code
+        .lines()
+        .map(|line| {
+            if line.trim() == marker {
+                Cow::Owned(generate())
+            } else {
+                Cow::Borrowed(line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if code.ends_with('\n') {
+        result.push('\n');
     }
+    result
 }
 
+/// Collects GLSL definitions of types, each one only once
+/// and after definitions of types it depends on.
+#[derive(Debug, Default)]
 pub struct GlslTypeContext {
     generated: HashSet<TypeId>,
+    extensions: BTreeSet<&'static str>,
     code: String,
 }
 
 impl GlslTypeContext {
     pub fn new() -> Self {
-        GlslTypeContext {
-            generated: HashSet::new(),
-            code: String::new(),
-        }
+        GlslTypeContext::default()
     }
 
-    pub fn add<T, U>(&mut self)
+    /// Adds definition of the type and its dependencies.
+    pub fn add<T>(&mut self)
     where
-        T: GlslType,
-        U: Into<T>,
+        T: GlslType + ?Sized,
     {
         if self.generated.insert(TypeId::of::<T>()) {
             T::deps(self);
-            self.code += "\n";
             self.code += &T::def();
         }
     }
 
+    /// Requires GLSL extension used by generated declarations.
+    pub fn require_extension(&mut self, name: &'static str) {
+        self.extensions.insert(name);
+    }
+
+    /// Returns collected definitions
+    /// preceded by required extensions.
     pub fn code(self) -> String {
-        self.code
+        let mut code = String::new();
+        for name in self.extensions {
+            code += &format!("#extension {} : require\n", name);
+        }
+        code + &self.code
     }
 }
 
 /// Generates declarations for struct in glsl shaders.
 pub trait GlslType: 'static {
+    /// Name of the type in GLSL.
     fn name() -> &'static str;
 
+    /// Suffix added after declared name. Non-empty for arrays.
     fn suffix() -> String {
         String::new()
     }
 
+    /// Adds definitions of types this type depends on.
     fn deps(_ctx: &mut GlslTypeContext) {}
 
+    /// Definition of the type. Empty for builtin types.
     fn def() -> String {
         String::new()
     }
+
+    /// Declaration of variable or member with this type.
+    fn declare(name: &str) -> String {
+        format!("{} {}{}", Self::name(), name, Self::suffix())
+    }
 }
 
 macro_rules! builtin_glsl_type {
-    ($ty:ty as $name:ident $(($($suffix:tt)+))?) => {
-        impl GlslType for $ty {
-            fn name() -> &'static str {
-                std::stringify!($name)
-            }
-
-            $(
-                fn suffix() -> String {
-                    std::stringify!($($suffix)+).to_owned()
+    ($($ty:ty as $name:ident),* $(,)?) => {
+        $(
+            impl GlslType for $ty {
+                fn name() -> &'static str {
+                    std::stringify!($name)
                 }
-            )?
-        }
-    };
-    ($ty:ty as $name:expr) => {
-        impl GlslType for $ty {
-            fn name() -> &'static str {
-                $name
             }
-        }
-    };
-    ($($ty:ty as $name:tt $(($($suffix:tt)+))?),* $(,)?) => {
-        $(builtin_glsl_type!($ty as $name $(($($suffix)+))?);)*
+        )*
     };
 }
 
@@ -100,10 +153,7 @@ builtin_glsl_type!(
     f64 as double,
     i32 as int,
     u32 as uint,
-    i16 as short,
-    u16 as ushort,
-    i8 as char,
-    bool as bool,
+    boolean as bool,
 );
 
 impl<T> GlslType for [T]
@@ -115,15 +165,11 @@ where
     }
 
     fn suffix() -> String {
-        "[]".to_owned()
+        format!("[]{}", T::suffix())
     }
 
     fn deps(ctx: &mut GlslTypeContext) {
-        T::deps(ctx)
-    }
-
-    fn def() -> String {
-        String::new()
+        ctx.add::<T>()
     }
 }
 
@@ -136,79 +182,151 @@ where
     }
 
     fn suffix() -> String {
-        format!("[{}]", N)
+        format!("[{}]{}", N, T::suffix())
     }
 
     fn deps(ctx: &mut GlslTypeContext) {
-        T::deps(ctx)
+        ctx.add::<T>()
     }
+}
 
-    fn def() -> String {
-        String::new()
+builtin_glsl_type!(
+    vec2<f32> as vec2,
+    vec3<f32> as vec3,
+    vec4<f32> as vec4,
+    vec2<f64> as dvec2,
+    vec3<f64> as dvec3,
+    vec4<f64> as dvec4,
+    vec2<i32> as ivec2,
+    vec3<i32> as ivec3,
+    vec4<i32> as ivec4,
+    vec2<u32> as uvec2,
+    vec3<u32> as uvec3,
+    vec4<u32> as uvec4,
+    vec2<boolean> as bvec2,
+    vec3<boolean> as bvec3,
+    vec4<boolean> as bvec4,
+);
+
+builtin_glsl_type!(
+    mat2x2<f32> as mat2x2,
+    mat3x2<f32> as mat3x2,
+    mat4x2<f32> as mat4x2,
+    mat2x3<f32> as mat2x3,
+    mat3x3<f32> as mat3x3,
+    mat4x3<f32> as mat4x3,
+    mat2x4<f32> as mat2x4,
+    mat3x4<f32> as mat3x4,
+    mat4x4<f32> as mat4x4,
+    mat2x2<f64> as dmat2x2,
+    mat3x2<f64> as dmat3x2,
+    mat4x2<f64> as dmat4x2,
+    mat2x3<f64> as dmat2x3,
+    mat3x3<f64> as dmat3x3,
+    mat4x3<f64> as dmat4x3,
+    mat2x4<f64> as dmat2x4,
+    mat3x4<f64> as dmat3x4,
+    mat4x4<f64> as dmat4x4,
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{repr::vec3, shader::ShaderStage, ShaderInclude};
+
+    #[derive(crate::ShaderRepr)]
+    #[sierra(scalar)]
+    struct Packed {
+        position: vec3,
+        weight: f32,
     }
-}
 
-builtin_glsl_type!(vec2<f32> as vec2);
-builtin_glsl_type!(vec3<f32> as vec3);
-builtin_glsl_type!(vec4<f32> as vec4);
-builtin_glsl_type!(mat2x2<f32> as mat2x2);
-builtin_glsl_type!(mat3x2<f32> as mat3x2);
-builtin_glsl_type!(mat4x2<f32> as mat4x2);
-builtin_glsl_type!(mat2x3<f32> as mat2x3);
-builtin_glsl_type!(mat3x3<f32> as mat3x3);
-builtin_glsl_type!(mat4x3<f32> as mat4x3);
-builtin_glsl_type!(mat2x4<f32> as mat2x4);
-builtin_glsl_type!(mat3x4<f32> as mat3x4);
-builtin_glsl_type!(mat4x4<f32> as mat4x4);
-
-builtin_glsl_type!(vec2<f64> as dvec2);
-builtin_glsl_type!(vec3<f64> as dvec3);
-builtin_glsl_type!(vec4<f64> as dvec4);
-builtin_glsl_type!(mat2x2<f64> as dmat2x2);
-builtin_glsl_type!(mat3x2<f64> as dmat3x2);
-builtin_glsl_type!(mat4x2<f64> as dmat4x2);
-builtin_glsl_type!(mat2x3<f64> as dmat2x3);
-builtin_glsl_type!(mat3x3<f64> as dmat3x3);
-builtin_glsl_type!(mat4x3<f64> as dmat4x3);
-builtin_glsl_type!(mat2x4<f64> as dmat2x4);
-builtin_glsl_type!(mat3x4<f64> as dmat3x4);
-builtin_glsl_type!(mat4x4<f64> as dmat4x4);
-
-builtin_glsl_type!(vec2<i32> as ivec2);
-builtin_glsl_type!(vec3<i32> as ivec3);
-builtin_glsl_type!(vec4<i32> as ivec4);
-builtin_glsl_type!(mat2x2<i32> as imat2x2);
-builtin_glsl_type!(mat3x2<i32> as imat3x2);
-builtin_glsl_type!(mat4x2<i32> as imat4x2);
-builtin_glsl_type!(mat2x3<i32> as imat2x3);
-builtin_glsl_type!(mat3x3<i32> as imat3x3);
-builtin_glsl_type!(mat4x3<i32> as imat4x3);
-builtin_glsl_type!(mat2x4<i32> as imat2x4);
-builtin_glsl_type!(mat3x4<i32> as imat3x4);
-builtin_glsl_type!(mat4x4<i32> as imat4x4);
-
-builtin_glsl_type!(vec2<u32> as uvec2);
-builtin_glsl_type!(vec3<u32> as uvec3);
-builtin_glsl_type!(vec4<u32> as uvec4);
-builtin_glsl_type!(mat2x2<u32> as umat2x2);
-builtin_glsl_type!(mat3x2<u32> as umat3x2);
-builtin_glsl_type!(mat4x2<u32> as umat4x2);
-builtin_glsl_type!(mat2x3<u32> as umat2x3);
-builtin_glsl_type!(mat3x3<u32> as umat3x3);
-builtin_glsl_type!(mat4x3<u32> as umat4x3);
-builtin_glsl_type!(mat2x4<u32> as umat2x4);
-builtin_glsl_type!(mat3x4<u32> as umat3x4);
-builtin_glsl_type!(mat4x4<u32> as umat4x4);
-
-builtin_glsl_type!(vec2<bool> as bvec2);
-builtin_glsl_type!(vec3<bool> as bvec3);
-builtin_glsl_type!(vec4<bool> as bvec4);
-builtin_glsl_type!(mat2x2<bool> as bmat2x2);
-builtin_glsl_type!(mat3x2<bool> as bmat3x2);
-builtin_glsl_type!(mat4x2<bool> as bmat4x2);
-builtin_glsl_type!(mat2x3<bool> as bmat2x3);
-builtin_glsl_type!(mat3x3<bool> as bmat3x3);
-builtin_glsl_type!(mat4x3<bool> as bmat4x3);
-builtin_glsl_type!(mat2x4<bool> as bmat2x4);
-builtin_glsl_type!(mat3x4<bool> as bmat3x4);
-builtin_glsl_type!(mat4x4<bool> as bmat4x4);
+    #[allow(dead_code)]
+    #[derive(crate::PipelineInput)]
+    struct Input {
+        #[sierra(push(scalar), compute)]
+        packed: Packed,
+    }
+
+    #[derive(crate::ShaderRepr)]
+    #[sierra(std140)]
+    struct Light {
+        color: vec3,
+        intensity: f32,
+    }
+
+    #[allow(dead_code)]
+    #[derive(crate::Descriptors)]
+    struct Material {
+        #[sierra(sampler, fragment)]
+        linear: crate::Sampler,
+
+        #[sierra(image(sampled), fragment)]
+        albedo: crate::ImageView,
+
+        #[sierra(image(storage, format = rgba8unorm, readonly), compute)]
+        source: crate::Image,
+
+        #[sierra(image(storage, format = r32sfloat, writeonly), compute)]
+        target: crate::Image,
+
+        #[sierra(uniform, stages(fragment))]
+        light: Light,
+    }
+
+    #[allow(dead_code)]
+    #[derive(crate::Descriptors)]
+    struct Scene {
+        #[sierra(uniform, stages(fragment))]
+        exposure: f32,
+    }
+
+    #[allow(dead_code)]
+    #[derive(crate::PipelineInput)]
+    struct TwoSets {
+        #[sierra(set)]
+        material: Material,
+
+        #[sierra(set)]
+        scene: Scene,
+    }
+
+    #[test]
+    fn descriptors_declarations() {
+        assert_eq!(
+            Material::glsl(ShaderStage::Fragment),
+            "struct Light {\n    vec3 color;\n    float intensity;\n};\n\
+             layout(set = 0, binding = 0) uniform sampler linear;\n\
+             layout(set = 0, binding = 1) uniform texture2D albedo;\n\
+             layout(std140, set = 0, binding = 4) uniform MaterialUniforms {\n    Light light;\n};\n",
+        );
+    }
+
+    #[test]
+    fn storage_image_qualifiers() {
+        assert_eq!(
+            Material::glsl(ShaderStage::Compute),
+            "layout(rgba8, set = 0, binding = 2) readonly uniform image2D source;\n\
+             layout(r32f, set = 0, binding = 3) writeonly uniform image2D target;\n",
+        );
+    }
+
+    #[test]
+    fn two_sets_declarations() {
+        assert_eq!(
+            TwoSets::glsl(ShaderStage::Fragment),
+            "struct Light {\n    vec3 color;\n    float intensity;\n};\n\
+             layout(set = 0, binding = 0) uniform sampler linear;\n\
+             layout(set = 0, binding = 1) uniform texture2D albedo;\n\
+             layout(std140, set = 0, binding = 4) uniform MaterialUniforms {\n    Light light;\n};\n\
+             layout(std140, set = 1, binding = 0) uniform SceneUniforms {\n    float exposure;\n};\n",
+        );
+    }
+
+    #[test]
+    fn scalar_block_requires_extension() {
+        assert_eq!(
+            Input::glsl(ShaderStage::Compute),
+            "#extension GL_EXT_scalar_block_layout : require\nstruct Packed {\n    vec3 position;\n    float weight;\n};\nlayout(push_constant, scalar) uniform InputPushConstants {\n    Packed packed;\n};\n",
+        );
+    }
+}
@@ -123,6 +123,17 @@ impl Default for Layout {
     }
 }
 
+impl Layout {
+    /// Returns read-only layout for input attachment with specified format.
+    pub fn input_attachment(format: Format) -> Self {
+        if format.is_color() {
+            Layout::ShaderReadOnlyOptimal
+        } else {
+            Layout::DepthStencilReadOnlyOptimal
+        }
+    }
+}
+
 /// Extent of the image.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
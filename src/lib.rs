@@ -90,6 +90,9 @@ macro_rules! error {
     ($($e:expr),*) => {{ $(let _ = &$e;)* }};
 }
 
+// Allows using derive macros inside this crate.
+extern crate self as sierra;
+
 pub mod backend;
 
 mod accel;
@@ -102,11 +105,16 @@ mod encode;
 mod fence;
 mod format;
 mod framebuffer;
+mod glsl;
 mod image;
 mod memory;
 mod physical;
 mod pipeline;
+mod preprocess;
+mod query;
 mod queue;
+#[cfg(feature = "reflect")]
+mod reflect;
 mod render_pass;
 mod repr;
 mod sampler;
@@ -115,6 +123,7 @@ mod shader;
 mod stage;
 mod surface;
 mod view;
+mod wgsl;
 
 pub use self::{
     accel::*,
@@ -128,10 +137,16 @@ pub use self::{
     fence::*,
     format::*,
     framebuffer::*,
+    glsl::*,
     image::*,
     memory::*,
     physical::*,
     pipeline::*,
+    preprocess::{
+        FileIncludeResolver, IncludeError, IncludeResolver, IncludedSource, MemoryIncludeResolver,
+        SourceChunk, SourceFile, SourceMap,
+    },
+    query::*,
     queue::*,
     render_pass::*,
     repr::*,
@@ -141,8 +156,12 @@ pub use self::{
     stage::*,
     surface::*,
     view::*,
+    wgsl::*,
 };
 
+#[cfg(feature = "reflect")]
+pub use self::reflect::*;
+
 pub use sierra_proc::{
     binding_flags, format, graphics_pipeline_desc, shader_stages, swizzle, Descriptors, Pass,
     PipelineInput, ShaderRepr,
@@ -1,5 +1,9 @@
 pub use crate::backend::PhysicalDevice;
-use crate::{assert_error, queue::FamilyInfo, OutOfMemory};
+use crate::{
+    assert_error,
+    queue::{FamilyInfo, QueuesQuery},
+    CreateDeviceError, Device, OutOfMemory,
+};
 
 /// Error occured during device enumeration.
 #[derive(Clone, Copy, Debug, thiserror::Error, PartialEq, Eq)]
@@ -21,11 +25,218 @@ pub struct DeviceInfo {
     /// Kind of the device.
     pub kind: Option<DeviceKind>,
 
+    /// Vendor identifier of the device.
+    pub vendor_id: u32,
+
+    /// Identifier of the device within vendor.
+    pub device_id: u32,
+
+    /// UUID of pipeline cache data format.
+    /// Pipeline cache data is valid only for device with same vendor, device and this UUID.
+    pub pipeline_cache_uuid: [u8; 16],
+
     /// Features supported by device.
     pub features: Vec<Feature>,
 
     /// Information about queue families that device has.
     pub families: Vec<FamilyInfo>,
+
+    /// Limits of the device.
+    pub limits: DeviceLimits,
+}
+
+/// Implementation-dependent limits of the device.
+///
+/// Fields mirror members of `VkPhysicalDeviceLimits` with the same names,
+/// except for `subgroup_size` and `ray_tracing`
+/// which come from extension properties.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceLimits {
+    /// Maximum width of 1D image.
+    pub max_image_dimension_1d: u32,
+
+    /// Maximum width and height of 2D image.
+    pub max_image_dimension_2d: u32,
+
+    /// Maximum width, height and depth of 3D image.
+    pub max_image_dimension_3d: u32,
+
+    /// Maximum width and height of cube-compatible image.
+    pub max_image_dimension_cube: u32,
+
+    /// Maximum number of layers in an image.
+    pub max_image_array_layers: u32,
+
+    /// Maximum number of addressable texels in texel buffer view.
+    pub max_texel_buffer_elements: u32,
+
+    /// Maximum range of uniform buffer descriptor in bytes.
+    pub max_uniform_buffer_range: u32,
+
+    /// Maximum range of storage buffer descriptor in bytes.
+    pub max_storage_buffer_range: u32,
+
+    /// Maximum size of push constants in bytes.
+    pub max_push_constants_size: u32,
+
+    /// Maximum number of simultaneously existing device memory allocations.
+    pub max_memory_allocation_count: u32,
+
+    /// Maximum number of simultaneously existing samplers.
+    pub max_sampler_allocation_count: u32,
+
+    /// Maximum number of descriptor sets bound to a pipeline at once.
+    pub max_bound_descriptor_sets: u32,
+
+    /// Maximum number of samplers accessible by single shader stage.
+    pub max_per_stage_descriptor_samplers: u32,
+
+    /// Maximum number of uniform buffers accessible by single shader stage.
+    pub max_per_stage_descriptor_uniform_buffers: u32,
+
+    /// Maximum number of storage buffers accessible by single shader stage.
+    pub max_per_stage_descriptor_storage_buffers: u32,
+
+    /// Maximum number of sampled images accessible by single shader stage.
+    pub max_per_stage_descriptor_sampled_images: u32,
+
+    /// Maximum number of storage images accessible by single shader stage.
+    pub max_per_stage_descriptor_storage_images: u32,
+
+    /// Maximum number of input attachments accessible by single shader stage.
+    pub max_per_stage_descriptor_input_attachments: u32,
+
+    /// Maximum number of resources of all kinds accessible by single shader stage.
+    pub max_per_stage_resources: u32,
+
+    /// Maximum number of samplers in all descriptor sets of a pipeline layout.
+    pub max_descriptor_set_samplers: u32,
+
+    /// Maximum number of uniform buffers in all descriptor sets of a pipeline layout.
+    pub max_descriptor_set_uniform_buffers: u32,
+
+    /// Maximum number of dynamic uniform buffers in all descriptor sets of a pipeline layout.
+    pub max_descriptor_set_uniform_buffers_dynamic: u32,
+
+    /// Maximum number of storage buffers in all descriptor sets of a pipeline layout.
+    pub max_descriptor_set_storage_buffers: u32,
+
+    /// Maximum number of dynamic storage buffers in all descriptor sets of a pipeline layout.
+    pub max_descriptor_set_storage_buffers_dynamic: u32,
+
+    /// Maximum number of sampled images in all descriptor sets of a pipeline layout.
+    pub max_descriptor_set_sampled_images: u32,
+
+    /// Maximum number of storage images in all descriptor sets of a pipeline layout.
+    pub max_descriptor_set_storage_images: u32,
+
+    /// Maximum number of input attachments in all descriptor sets of a pipeline layout.
+    pub max_descriptor_set_input_attachments: u32,
+
+    /// Maximum number of vertex attributes in graphics pipeline.
+    pub max_vertex_input_attributes: u32,
+
+    /// Maximum number of vertex buffers in graphics pipeline.
+    pub max_vertex_input_bindings: u32,
+
+    /// Maximum offset of vertex attribute in bytes.
+    pub max_vertex_input_attribute_offset: u32,
+
+    /// Maximum vertex buffer stride in bytes.
+    pub max_vertex_input_binding_stride: u32,
+
+    /// Maximum size of workgroup shared memory in bytes.
+    pub max_compute_shared_memory_size: u32,
+
+    /// Maximum number of workgroups dispatched in each dimension.
+    pub max_compute_work_group_count: [u32; 3],
+
+    /// Maximum number of invocations in single workgroup.
+    pub max_compute_work_group_invocations: u32,
+
+    /// Maximum size of workgroup in each dimension.
+    pub max_compute_work_group_size: [u32; 3],
+
+    /// Maximum degree of sampler anisotropy.
+    pub max_sampler_anisotropy: f32,
+
+    /// Maximum number of active viewports.
+    pub max_viewports: u32,
+
+    /// Maximum width and height of viewport.
+    pub max_viewport_dimensions: [u32; 2],
+
+    /// Maximum width of framebuffer.
+    pub max_framebuffer_width: u32,
+
+    /// Maximum height of framebuffer.
+    pub max_framebuffer_height: u32,
+
+    /// Maximum number of layers in framebuffer.
+    pub max_framebuffer_layers: u32,
+
+    /// Maximum number of color attachments in a subpass.
+    pub max_color_attachments: u32,
+
+    /// Required alignment of texel buffer view offsets in bytes.
+    pub min_texel_buffer_offset_alignment: u64,
+
+    /// Required alignment of uniform buffer offsets in descriptors.
+    pub min_uniform_buffer_offset_alignment: u64,
+
+    /// Required alignment of storage buffer offsets in descriptors.
+    pub min_storage_buffer_offset_alignment: u64,
+
+    /// Optimal alignment of buffer offsets in buffer-image copies.
+    pub optimal_buffer_copy_offset_alignment: u64,
+
+    /// Optimal alignment of row pitch in buffer-image copies.
+    pub optimal_buffer_copy_row_pitch_alignment: u64,
+
+    /// Granularity of mapped memory ranges flushed or invalidated
+    /// for non-coherent memory.
+    pub non_coherent_atom_size: u64,
+
+    /// Whether timestamps are supported by all graphics and compute queues.
+    pub timestamp_compute_and_graphics: bool,
+
+    /// Number of nanoseconds per timestamp tick.
+    pub timestamp_period: f32,
+
+    /// Default number of invocations in a subgroup.
+    /// `None` if device does not report it.
+    pub subgroup_size: Option<u32>,
+
+    /// Limits of ray-tracing pipelines.
+    /// `None` unless [`Feature::RayTracingPipeline`] is supported.
+    pub ray_tracing: Option<RayTracingLimits>,
+}
+
+/// Limits of ray-tracing pipelines.
+///
+/// Fields mirror members of `VkPhysicalDeviceRayTracingPipelinePropertiesKHR`
+/// with the same names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct RayTracingLimits {
+    /// Size of shader group handle in bytes.
+    pub shader_group_handle_size: u32,
+
+    /// Required alignment of shader group handles in shader binding table.
+    pub shader_group_handle_alignment: u32,
+
+    /// Required alignment of shader binding table base address.
+    pub shader_group_base_alignment: u32,
+
+    /// Maximum stride between shader groups in shader binding table.
+    pub max_shader_group_stride: u32,
+
+    /// Maximum depth of recursive ray tracing calls.
+    pub max_ray_recursion_depth: u32,
+
+    /// Maximum size of hit attributes in bytes.
+    pub max_ray_hit_attribute_size: u32,
 }
 
 /// Kind of the device.
@@ -120,14 +331,587 @@ pub enum Feature {
     /// Allows fetching display timings.
     DisplayTiming,
 
+    /// Allows passing HDR metadata to presentation engine.
+    HdrMetadata,
+
     /// Allows rendering without render-pass.
     DynamicRendering,
 
     /// Allows moving depth and stencil aspects of a image into different layouts.
     SeparateDepthStencilLayouts,
+
+    /// Allows creating query pools for pipeline statistics.
+    PipelineStatisticsQuery,
+
+    /// Allows indirect draws with draw count greater than one.
+    MultiDrawIndirect,
+
+    /// Allows indirect draws with draw count read from a buffer.
+    DrawIndirectCount,
+
+    /// Allows creating timeline semaphores.
+    TimelineSemaphore,
+
+    /// Allows creating cube array image views.
+    ImageCubeArray,
+}
+
+/// Minimal values of device limits required by application.
+/// Zero means no requirement.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct MinDeviceLimits {
+    pub max_image_dimension_2d: u32,
+    pub max_image_array_layers: u32,
+    pub max_uniform_buffer_range: u32,
+    pub max_storage_buffer_range: u32,
+    pub max_push_constants_size: u32,
+    pub max_bound_descriptor_sets: u32,
+    pub max_per_stage_resources: u32,
+    pub max_color_attachments: u32,
+    pub max_framebuffer_width: u32,
+    pub max_framebuffer_height: u32,
+    pub max_compute_shared_memory_size: u32,
+    pub max_compute_work_group_invocations: u32,
+}
+
+impl MinDeviceLimits {
+    /// Returns first limit of the device that is lower than required.
+    pub fn check(&self, limits: &DeviceLimits) -> Result<(), DeviceRejection> {
+        let pairs = [
+            (
+                "max_image_dimension_2d",
+                self.max_image_dimension_2d,
+                limits.max_image_dimension_2d,
+            ),
+            (
+                "max_image_array_layers",
+                self.max_image_array_layers,
+                limits.max_image_array_layers,
+            ),
+            (
+                "max_uniform_buffer_range",
+                self.max_uniform_buffer_range,
+                limits.max_uniform_buffer_range,
+            ),
+            (
+                "max_storage_buffer_range",
+                self.max_storage_buffer_range,
+                limits.max_storage_buffer_range,
+            ),
+            (
+                "max_push_constants_size",
+                self.max_push_constants_size,
+                limits.max_push_constants_size,
+            ),
+            (
+                "max_bound_descriptor_sets",
+                self.max_bound_descriptor_sets,
+                limits.max_bound_descriptor_sets,
+            ),
+            (
+                "max_per_stage_resources",
+                self.max_per_stage_resources,
+                limits.max_per_stage_resources,
+            ),
+            (
+                "max_color_attachments",
+                self.max_color_attachments,
+                limits.max_color_attachments,
+            ),
+            (
+                "max_framebuffer_width",
+                self.max_framebuffer_width,
+                limits.max_framebuffer_width,
+            ),
+            (
+                "max_framebuffer_height",
+                self.max_framebuffer_height,
+                limits.max_framebuffer_height,
+            ),
+            (
+                "max_compute_shared_memory_size",
+                self.max_compute_shared_memory_size,
+                limits.max_compute_shared_memory_size,
+            ),
+            (
+                "max_compute_work_group_invocations",
+                self.max_compute_work_group_invocations,
+                limits.max_compute_work_group_invocations,
+            ),
+        ];
+
+        for (limit, required, actual) in pairs {
+            if actual < required {
+                return Err(DeviceRejection::LimitTooLow {
+                    limit,
+                    required,
+                    actual,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Requirements used to pick a device with [`Graphics::select_device`].
+///
+/// [`Graphics::select_device`]: crate::Graphics::select_device
+#[derive(Clone, Debug)]
+pub struct DeviceRequirements<Q> {
+    /// Features device must support.
+    pub required: Vec<Feature>,
+
+    /// Features enabled if device supports them.
+    pub optional: Vec<Feature>,
+
+    /// Minimal limits device must have.
+    pub min_limits: MinDeviceLimits,
+
+    /// Queues to create with device.
+    pub queues: Q,
+}
+
+impl<Q> DeviceRequirements<Q>
+where
+    Q: QueuesQuery + Clone,
+{
+    pub fn new(queues: Q) -> Self {
+        DeviceRequirements {
+            required: Vec::new(),
+            optional: Vec::new(),
+            min_limits: MinDeviceLimits::default(),
+            queues,
+        }
+    }
+
+    /// Adds required feature.
+    pub fn require(mut self, feature: Feature) -> Self {
+        self.required.push(feature);
+        self
+    }
+
+    /// Adds optional feature.
+    pub fn prefer(mut self, feature: Feature) -> Self {
+        self.optional.push(feature);
+        self
+    }
+
+    /// Sets minimal limits.
+    pub fn with_min_limits(mut self, min_limits: MinDeviceLimits) -> Self {
+        self.min_limits = min_limits;
+        self
+    }
+
+    /// Checks device against requirements.
+    ///
+    /// Score of suitable device prefers discrete over integrated over software devices.
+    /// Among devices of the same kind more supported optional features wins.
+    pub fn evaluate(&self, info: &DeviceInfo) -> Result<DeviceCandidate, DeviceRejection> {
+        let missing = self
+            .required
+            .iter()
+            .filter(|feature| !info.features.contains(feature))
+            .copied()
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            return Err(DeviceRejection::MissingFeatures { features: missing });
+        }
+
+        self.min_limits.check(&info.limits)?;
+
+        if let Err(err) = self.queues.clone().query(&info.families) {
+            return Err(DeviceRejection::QueuesNotFound {
+                reason: err.to_string(),
+            });
+        }
+
+        let enabled_optional = self
+            .optional
+            .iter()
+            .filter(|feature| info.features.contains(feature))
+            .copied()
+            .collect::<Vec<_>>();
+
+        let kind_rank = match info.kind {
+            None => 0,
+            Some(DeviceKind::Software) => 1,
+            Some(DeviceKind::Integrated) => 2,
+            Some(DeviceKind::Discrete) => 3,
+        };
+
+        let optional_count = u32::try_from(enabled_optional.len()).unwrap_or(u32::MAX);
+
+        Ok(DeviceCandidate {
+            score: (kind_rank << 16) | optional_count.min(0xFFFF),
+            enabled_optional,
+        })
+    }
+
+    /// Evaluates all devices and picks one with highest score.
+    /// First device wins among devices with equal score.
+    pub fn rank(&self, infos: &[DeviceInfo]) -> DeviceRanking {
+        let mut best: Option<(usize, DeviceCandidate)> = None;
+        let mut rejected = Vec::new();
+
+        for (index, info) in infos.iter().enumerate() {
+            match self.evaluate(info) {
+                Ok(candidate) => match &best {
+                    Some((_, best_candidate)) if best_candidate.score >= candidate.score => {}
+                    _ => best = Some((index, candidate)),
+                },
+                Err(reason) => rejected.push(RejectedDevice {
+                    index,
+                    name: info.name.clone(),
+                    reason,
+                }),
+            }
+        }
+
+        DeviceRanking { best, rejected }
+    }
+
+    /// Returns required features followed by supported optional ones,
+    /// each listed once.
+    pub(crate) fn enabled_features(&self, candidate: &DeviceCandidate) -> Vec<Feature> {
+        let mut features = Vec::new();
+        for &feature in self.required.iter().chain(&candidate.enabled_optional) {
+            if !features.contains(&feature) {
+                features.push(feature);
+            }
+        }
+        features
+    }
+}
+
+/// Device that satisfies [`DeviceRequirements`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceCandidate {
+    /// Score of the device. Higher is better.
+    pub score: u32,
+
+    /// Optional features supported by the device.
+    pub enabled_optional: Vec<Feature>,
+}
+
+/// Reason why device does not satisfy [`DeviceRequirements`].
+#[derive(Clone, Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DeviceRejection {
+    #[error("Required features {features:?} are not supported")]
+    MissingFeatures { features: Vec<Feature> },
+
+    #[error("Limit `{limit}` is {actual} while at least {required} is required")]
+    LimitTooLow {
+        limit: &'static str,
+        required: u32,
+        actual: u32,
+    },
+
+    #[error("Required queues are not available. {reason}")]
+    QueuesNotFound { reason: String },
+}
+
+/// Device rejected by [`DeviceRequirements`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RejectedDevice {
+    /// Index of the device in evaluated list.
+    pub index: usize,
+
+    /// Name of the device.
+    pub name: String,
+
+    /// Reason for rejection.
+    pub reason: DeviceRejection,
+}
+
+/// Result of [`DeviceRequirements::rank`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceRanking {
+    /// Index of the best device and its evaluation.
+    pub best: Option<(usize, DeviceCandidate)>,
+
+    /// Devices that do not satisfy requirements.
+    pub rejected: Vec<RejectedDevice>,
+}
+
+/// Device picked by [`Graphics::select_device`].
+///
+/// [`Graphics::select_device`]: crate::Graphics::select_device
+#[derive(Debug)]
+pub struct DeviceSelection<Q> {
+    pub physical: PhysicalDevice,
+
+    /// Information about picked device.
+    pub info: DeviceInfo,
+
+    /// Required and supported optional features.
+    /// Those features are enabled by [`DeviceSelection::create_device`].
+    pub features: Vec<Feature>,
+
+    /// Optional features supported by the device.
+    pub enabled_optional: Vec<Feature>,
+
+    /// Devices that do not satisfy requirements.
+    pub rejected: Vec<RejectedDevice>,
+
+    /// Queues to create with device.
+    pub queues: Q,
+}
+
+impl<Q> DeviceSelection<Q>
+where
+    Q: QueuesQuery,
+{
+    /// Creates device with selected features and requested queues.
+    pub fn create_device(self) -> Result<(Device, Q::Queues), CreateDeviceError<Q::Error>> {
+        self.physical.create_device(&self.features, self.queues)
+    }
+}
+
+/// Error returned by [`Graphics::select_device`].
+///
+/// [`Graphics::select_device`]: crate::Graphics::select_device
+#[derive(Debug, thiserror::Error)]
+pub enum SelectDeviceError {
+    #[error(transparent)]
+    EnumerateDeviceError {
+        #[from]
+        source: EnumerateDeviceError,
+    },
+
+    #[error("No device satisfies requirements")]
+    NoSuitableDevice { rejected: Vec<RejectedDevice> },
 }
 
 #[allow(dead_code)]
 fn check() {
     assert_error::<EnumerateDeviceError>();
+    assert_error::<DeviceRejection>();
+    assert_error::<SelectDeviceError>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queue::{QueueCapabilityFlags, SingleQueueQuery};
+
+    fn limits() -> DeviceLimits {
+        DeviceLimits {
+            max_image_dimension_1d: 16384,
+            max_image_dimension_2d: 16384,
+            max_image_dimension_3d: 2048,
+            max_image_dimension_cube: 16384,
+            max_image_array_layers: 2048,
+            max_texel_buffer_elements: 1 << 27,
+            max_uniform_buffer_range: 65536,
+            max_storage_buffer_range: 1 << 30,
+            max_push_constants_size: 128,
+            max_memory_allocation_count: 4096,
+            max_sampler_allocation_count: 4000,
+            max_bound_descriptor_sets: 8,
+            max_per_stage_descriptor_samplers: 16,
+            max_per_stage_descriptor_uniform_buffers: 15,
+            max_per_stage_descriptor_storage_buffers: 16,
+            max_per_stage_descriptor_sampled_images: 128,
+            max_per_stage_descriptor_storage_images: 8,
+            max_per_stage_descriptor_input_attachments: 8,
+            max_per_stage_resources: 200,
+            max_descriptor_set_samplers: 80,
+            max_descriptor_set_uniform_buffers: 90,
+            max_descriptor_set_uniform_buffers_dynamic: 8,
+            max_descriptor_set_storage_buffers: 96,
+            max_descriptor_set_storage_buffers_dynamic: 8,
+            max_descriptor_set_sampled_images: 640,
+            max_descriptor_set_storage_images: 40,
+            max_descriptor_set_input_attachments: 8,
+            max_vertex_input_attributes: 16,
+            max_vertex_input_bindings: 16,
+            max_vertex_input_attribute_offset: 2047,
+            max_vertex_input_binding_stride: 2048,
+            max_compute_shared_memory_size: 32768,
+            max_compute_work_group_count: [65535; 3],
+            max_compute_work_group_invocations: 1024,
+            max_compute_work_group_size: [1024, 1024, 64],
+            max_sampler_anisotropy: 16.0,
+            max_viewports: 16,
+            max_viewport_dimensions: [16384; 2],
+            max_framebuffer_width: 16384,
+            max_framebuffer_height: 16384,
+            max_framebuffer_layers: 2048,
+            max_color_attachments: 8,
+            min_texel_buffer_offset_alignment: 16,
+            min_uniform_buffer_offset_alignment: 256,
+            min_storage_buffer_offset_alignment: 32,
+            optimal_buffer_copy_offset_alignment: 1,
+            optimal_buffer_copy_row_pitch_alignment: 1,
+            non_coherent_atom_size: 64,
+            timestamp_compute_and_graphics: true,
+            timestamp_period: 1.0,
+            subgroup_size: Some(32),
+            ray_tracing: None,
+        }
+    }
+
+    fn device(name: &str, kind: DeviceKind, features: &[Feature]) -> DeviceInfo {
+        DeviceInfo {
+            name: name.to_owned(),
+            kind: Some(kind),
+            vendor_id: 0,
+            device_id: 0,
+            pipeline_cache_uuid: [0; 16],
+            features: features.to_vec(),
+            families: vec![FamilyInfo {
+                capabilities: QueueCapabilityFlags::all(),
+                count: 1,
+            }],
+            limits: limits(),
+        }
+    }
+
+    fn requirements() -> DeviceRequirements<SingleQueueQuery> {
+        DeviceRequirements::new(SingleQueueQuery::GRAPHICS)
+    }
+
+    #[test]
+    fn reject_missing_feature() {
+        let info = device("a", DeviceKind::Discrete, &[Feature::DynamicRendering]);
+        let requirements = requirements()
+            .require(Feature::DynamicRendering)
+            .require(Feature::TimelineSemaphore)
+            .require(Feature::ImageCubeArray);
+
+        assert_eq!(
+            requirements.evaluate(&info),
+            Err(DeviceRejection::MissingFeatures {
+                features: vec![Feature::TimelineSemaphore, Feature::ImageCubeArray],
+            })
+        );
+    }
+
+    #[test]
+    fn reject_low_limit() {
+        let info = device("a", DeviceKind::Discrete, &[]);
+        let requirements = requirements().with_min_limits(MinDeviceLimits {
+            max_push_constants_size: 256,
+            ..MinDeviceLimits::default()
+        });
+
+        assert_eq!(
+            requirements.evaluate(&info),
+            Err(DeviceRejection::LimitTooLow {
+                limit: "max_push_constants_size",
+                required: 256,
+                actual: 128,
+            })
+        );
+    }
+
+    #[test]
+    fn reject_missing_queue() {
+        let mut info = device("a", DeviceKind::Discrete, &[]);
+        info.families[0].capabilities =
+            QueueCapabilityFlags::COMPUTE | QueueCapabilityFlags::TRANSFER;
+
+        assert!(matches!(
+            requirements().evaluate(&info),
+            Err(DeviceRejection::QueuesNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn discrete_ranked_above_integrated() {
+        let infos = [
+            device(
+                "integrated",
+                DeviceKind::Integrated,
+                &[Feature::DynamicRendering],
+            ),
+            device("discrete", DeviceKind::Discrete, &[]),
+            device(
+                "software",
+                DeviceKind::Software,
+                &[Feature::DynamicRendering],
+            ),
+        ];
+
+        // Optional features do not outweigh device kind.
+        let ranking = requirements()
+            .prefer(Feature::DynamicRendering)
+            .rank(&infos);
+        assert_eq!(ranking.best.map(|(index, _)| index), Some(1));
+        assert_eq!(ranking.rejected, []);
+    }
+
+    #[test]
+    fn optional_features_break_ties() {
+        let infos = [
+            device("a", DeviceKind::Discrete, &[Feature::DynamicRendering]),
+            device(
+                "b",
+                DeviceKind::Discrete,
+                &[Feature::DynamicRendering, Feature::TimelineSemaphore],
+            ),
+            device("c", DeviceKind::Discrete, &[Feature::TimelineSemaphore]),
+            device("d", DeviceKind::Discrete, &[]),
+        ];
+
+        let requirements = requirements()
+            .prefer(Feature::DynamicRendering)
+            .prefer(Feature::TimelineSemaphore);
+
+        let (index, candidate) = requirements.rank(&infos).best.unwrap();
+        assert_eq!(index, 1);
+        assert_eq!(
+            candidate.enabled_optional,
+            [Feature::DynamicRendering, Feature::TimelineSemaphore]
+        );
+
+        // First device wins on equal score.
+        let (index, _) = requirements.rank(&infos[2..]).best.unwrap();
+        assert_eq!(index, 0);
+    }
+
+    #[test]
+    fn rank_reports_rejected() {
+        let infos = [
+            device("a", DeviceKind::Discrete, &[]),
+            device("b", DeviceKind::Integrated, &[Feature::TimelineSemaphore]),
+        ];
+
+        let ranking = requirements()
+            .require(Feature::TimelineSemaphore)
+            .rank(&infos);
+        assert_eq!(ranking.best.map(|(index, _)| index), Some(1));
+        assert_eq!(
+            ranking.rejected,
+            [RejectedDevice {
+                index: 0,
+                name: "a".to_owned(),
+                reason: DeviceRejection::MissingFeatures {
+                    features: vec![Feature::TimelineSemaphore],
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn enabled_features_are_unique() {
+        let info = device(
+            "a",
+            DeviceKind::Discrete,
+            &[Feature::DynamicRendering, Feature::TimelineSemaphore],
+        );
+        let requirements = requirements()
+            .require(Feature::DynamicRendering)
+            .prefer(Feature::DynamicRendering)
+            .prefer(Feature::TimelineSemaphore);
+
+        let candidate = requirements.evaluate(&info).unwrap();
+        assert_eq!(
+            requirements.enabled_features(&candidate),
+            [Feature::DynamicRendering, Feature::TimelineSemaphore]
+        );
+    }
 }
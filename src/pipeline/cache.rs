@@ -0,0 +1,107 @@
+pub use crate::backend::PipelineCache;
+use crate::physical::DeviceInfo;
+
+/// Header of pipeline cache data.
+///
+/// Pipeline cache data can be used only by device
+/// with same vendor, device and pipeline cache UUID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct PipelineCacheHeader {
+    /// Vendor identifier of the device that produced the data.
+    pub vendor_id: u32,
+
+    /// Identifier of the device that produced the data.
+    pub device_id: u32,
+
+    /// Pipeline cache UUID of the device that produced the data.
+    pub uuid: [u8; 16],
+}
+
+impl PipelineCacheHeader {
+    /// Size of the header in pipeline cache data.
+    pub const SIZE: usize = 32;
+
+    /// Parses header from the beginning of pipeline cache data.
+    /// Returns `None` if data is too short or header is malformed.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < Self::SIZE {
+            return None;
+        }
+
+        // Header fields are written least significant byte first.
+        let u32_at = |offset: usize| {
+            let mut bytes = [0; 4];
+            bytes.copy_from_slice(&data[offset..offset + 4]);
+            u32::from_le_bytes(bytes)
+        };
+
+        let header_size = u32_at(0);
+        let header_version = u32_at(4);
+
+        if (header_size as usize) < Self::SIZE || header_version != 1 {
+            return None;
+        }
+
+        let mut uuid = [0; 16];
+        uuid.copy_from_slice(&data[16..32]);
+
+        Some(PipelineCacheHeader {
+            vendor_id: u32_at(8),
+            device_id: u32_at(12),
+            uuid,
+        })
+    }
+
+    /// Returns `true` if pipeline cache data with this header
+    /// can be used by the device.
+    pub fn is_compatible(&self, info: &DeviceInfo) -> bool {
+        self.vendor_id == info.vendor_id
+            && self.device_id == info.device_id
+            && self.uuid == info.pipeline_cache_uuid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PipelineCacheHeader;
+
+    fn header_data(header_size: u32, header_version: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&header_size.to_le_bytes());
+        data.extend_from_slice(&header_version.to_le_bytes());
+        data.extend_from_slice(&0x10de_u32.to_le_bytes());
+        data.extend_from_slice(&0x2204_u32.to_le_bytes());
+        data.extend((0..16).map(|i| i as u8));
+        data.extend_from_slice(&[0xff; 8]);
+        data
+    }
+
+    #[test]
+    fn short_buffer() {
+        let data = header_data(32, 1);
+        assert_eq!(PipelineCacheHeader::parse(&data[..31]), None);
+        assert_eq!(PipelineCacheHeader::parse(&[]), None);
+    }
+
+    #[test]
+    fn wrong_header_version() {
+        assert_eq!(PipelineCacheHeader::parse(&header_data(32, 2)), None);
+
+        // Header size must cover all header fields.
+        assert_eq!(PipelineCacheHeader::parse(&header_data(16, 1)), None);
+    }
+
+    #[test]
+    fn valid_header() {
+        let header = PipelineCacheHeader::parse(&header_data(32, 1)).unwrap();
+        assert_eq!(
+            header,
+            PipelineCacheHeader {
+                vendor_id: 0x10de,
+                device_id: 0x2204,
+                uuid: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+            }
+        );
+    }
+}
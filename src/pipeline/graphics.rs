@@ -1,7 +1,13 @@
-use std::hash::{Hash, Hasher};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use parking_lot::Mutex;
 
 use crate::{
     backend::Device,
+    cache::ResourceCache,
     dimensions::{Extent2, Extent3, Offset2},
     format::Format,
     render_pass::RenderPass,
@@ -927,9 +933,14 @@ bitflags::bitflags! {
     }
 }
 
+/// Graphics pipeline that is created on demand for each rendering configuration it is used with.
+///
+/// Keeps variants for different render passes, subpasses and attachment formats,
+/// so alternating between them does not recreate pipelines.
+/// Variants not used for a while can be evicted with [`DynamicGraphicsPipeline::evict_unused`].
 #[derive(Debug)]
 pub struct DynamicGraphicsPipeline {
-    graphics_pipeline: Option<GraphicsPipeline>,
+    graphics_pipelines: ResourceCache<GraphicsPipeline, GraphicsPipelineKey>,
     pub desc: GraphicsPipelineDesc,
 }
 
@@ -937,7 +948,7 @@ impl DynamicGraphicsPipeline {
     pub fn new(desc: GraphicsPipelineDesc) -> Self {
         DynamicGraphicsPipeline {
             desc,
-            graphics_pipeline: None,
+            graphics_pipelines: ResourceCache::new(),
         }
     }
 
@@ -947,36 +958,19 @@ impl DynamicGraphicsPipeline {
         depth_stencil: Option<Format>,
         device: &Device,
     ) -> Result<&GraphicsPipeline, OutOfMemory> {
-        if let Some(graphics_pipeline) = &mut self.graphics_pipeline {
-            let info = graphics_pipeline.info();
-
-            let compatible = match info.rendering {
-                GraphicsPipelineRenderingInfo::DynamicRendering {
-                    colors: ref current_colors,
-                    depth_stencil: current_depth_stencil,
-                } => current_colors[..] != colors[..] || current_depth_stencil != depth_stencil,
-                _ => false,
-            };
-
-            if !compatible || info.desc != self.desc {
-                self.graphics_pipeline = None;
-            }
-        }
-
-        let graphics_pipeline = match &mut self.graphics_pipeline {
-            Some(graphics_pipeline) => graphics_pipeline,
-            graphics_pipeline => graphics_pipeline.get_or_insert(device.create_graphics_pipeline(
-                GraphicsPipelineInfo {
-                    desc: self.desc.clone(),
-                    rendering: GraphicsPipelineRenderingInfo::DynamicRendering {
-                        colors: colors.to_vec(),
-                        depth_stencil,
-                    },
-                },
-            )?),
-        };
-
-        Ok(graphics_pipeline)
+        let desc = &self.desc;
+        let key = pipeline_key(
+            desc,
+            GraphicsPipelineRenderingInfo::DynamicRendering {
+                colors: colors.to_vec(),
+                depth_stencil,
+            },
+        );
+        self.graphics_pipelines.try_fetch_keyed_or_create(
+            &key,
+            |pipeline| pipeline.info().desc == *desc,
+            || create_for_key(desc, &key, device),
+        )
     }
 
     pub fn get_for_render_pass(
@@ -985,35 +979,141 @@ impl DynamicGraphicsPipeline {
         subpass: u32,
         device: &Device,
     ) -> Result<&GraphicsPipeline, OutOfMemory> {
-        if let Some(graphics_pipeline) = &mut self.graphics_pipeline {
-            let info = graphics_pipeline.info();
-
-            let compatible = match info.rendering {
-                GraphicsPipelineRenderingInfo::RenderPass {
-                    render_pass: ref current_render_pass,
-                    subpass: current_subpass,
-                } => *current_render_pass != *render_pass || current_subpass != subpass,
-                _ => false,
-            };
-
-            if !compatible || info.desc != self.desc {
-                self.graphics_pipeline = None;
-            }
-        }
+        let desc = &self.desc;
+        let key = pipeline_key(
+            desc,
+            GraphicsPipelineRenderingInfo::RenderPass {
+                render_pass: render_pass.clone(),
+                subpass,
+            },
+        );
+        self.graphics_pipelines.try_fetch_keyed_or_create(
+            &key,
+            |pipeline| pipeline.info().desc == *desc,
+            || create_for_key(desc, &key, device),
+        )
+    }
 
-        let graphics_pipeline = match &mut self.graphics_pipeline {
-            Some(graphics_pipeline) => graphics_pipeline,
-            graphics_pipeline => graphics_pipeline.get_or_insert(device.create_graphics_pipeline(
-                GraphicsPipelineInfo {
-                    desc: self.desc.clone(),
-                    rendering: GraphicsPipelineRenderingInfo::RenderPass {
-                        render_pass: render_pass.clone(),
-                        subpass,
-                    },
-                },
-            )?),
-        };
+    /// Moves to the next epoch.
+    /// Variants used after this call are marked with new epoch.
+    pub fn next_epoch(&mut self) {
+        self.graphics_pipelines.next_epoch();
+    }
 
-        Ok(graphics_pipeline)
+    /// Evicts variants that have not been used during last `epochs` epochs.
+    /// This includes variants created for previous values of `desc`.
+    pub fn evict_unused(&mut self, epochs: u64) {
+        self.graphics_pipelines.evict_unused(epochs);
     }
 }
+
+/// Thread-safe cache of graphics pipelines for any number of [`GraphicsPipelineDesc`]s.
+///
+/// Pipelines not used for a while can be evicted with [`GraphicsPipelineCache::evict_unused`].
+#[derive(Debug, Default)]
+pub struct GraphicsPipelineCache {
+    pipelines: Mutex<ResourceCache<GraphicsPipeline, GraphicsPipelineKey>>,
+}
+
+impl GraphicsPipelineCache {
+    pub fn new() -> Self {
+        GraphicsPipelineCache::default()
+    }
+
+    pub fn get_for_dynamic_rendering(
+        &self,
+        desc: &GraphicsPipelineDesc,
+        colors: &[Format],
+        depth_stencil: Option<Format>,
+        device: &Device,
+    ) -> Result<GraphicsPipeline, OutOfMemory> {
+        self.get_or_create(
+            desc,
+            GraphicsPipelineRenderingInfo::DynamicRendering {
+                colors: colors.to_vec(),
+                depth_stencil,
+            },
+            device,
+        )
+    }
+
+    pub fn get_for_render_pass(
+        &self,
+        desc: &GraphicsPipelineDesc,
+        render_pass: &RenderPass,
+        subpass: u32,
+        device: &Device,
+    ) -> Result<GraphicsPipeline, OutOfMemory> {
+        self.get_or_create(
+            desc,
+            GraphicsPipelineRenderingInfo::RenderPass {
+                render_pass: render_pass.clone(),
+                subpass,
+            },
+            device,
+        )
+    }
+
+    /// Moves to the next epoch.
+    /// Pipelines used after this call are marked with new epoch.
+    pub fn next_epoch(&self) {
+        self.pipelines.lock().next_epoch();
+    }
+
+    /// Evicts pipelines that have not been used during last `epochs` epochs.
+    pub fn evict_unused(&self, epochs: u64) {
+        self.pipelines.lock().evict_unused(epochs);
+    }
+
+    fn get_or_create(
+        &self,
+        desc: &GraphicsPipelineDesc,
+        rendering: GraphicsPipelineRenderingInfo,
+        device: &Device,
+    ) -> Result<GraphicsPipeline, OutOfMemory> {
+        let key = pipeline_key(desc, rendering);
+        let eq = |pipeline: &GraphicsPipeline| pipeline.info().desc == *desc;
+
+        if let Some(pipeline) = self.pipelines.lock().fetch_keyed(&key, eq) {
+            return Ok(pipeline.clone());
+        }
+
+        // Lock is not held while pipeline is compiled,
+        // so other threads may fetch and create pipelines meanwhile.
+        let pipeline = create_for_key(desc, &key, device)?;
+
+        // Same pipeline could be created concurrently.
+        // Then the one already in cache is returned.
+        let pipeline = self
+            .pipelines
+            .lock()
+            .fetch_keyed_or_create(&key, eq, || pipeline)
+            .clone();
+        Ok(pipeline)
+    }
+}
+
+/// Key of graphics pipeline variant in caches.
+/// Consists of rendering info and hash of the desc.
+/// Descs with colliding hashes share one key and are compared on lookup.
+type GraphicsPipelineKey = (GraphicsPipelineRenderingInfo, u64);
+
+fn pipeline_key(
+    desc: &GraphicsPipelineDesc,
+    rendering: GraphicsPipelineRenderingInfo,
+) -> GraphicsPipelineKey {
+    let mut hasher = DefaultHasher::new();
+    desc.hash(&mut hasher);
+    (rendering, hasher.finish())
+}
+
+fn create_for_key(
+    desc: &GraphicsPipelineDesc,
+    key: &GraphicsPipelineKey,
+    device: &Device,
+) -> Result<GraphicsPipeline, OutOfMemory> {
+    device.create_graphics_pipeline(GraphicsPipelineInfo {
+        desc: desc.clone(),
+        rendering: key.0.clone(),
+    })
+}
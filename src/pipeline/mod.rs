@@ -1,9 +1,10 @@
+mod cache;
 mod compute;
 mod graphics;
 mod ray_tracing;
 
 pub use {
-    self::{compute::*, graphics::*, ray_tracing::*},
+    self::{cache::*, compute::*, graphics::*, ray_tracing::*},
     crate::{
         backend::PipelineLayout,
         descriptor::{UpdatedDescriptors, UpdatedPipelineDescriptors},
@@ -38,6 +39,15 @@ pub trait PipelineInputLayout {
 
     fn raw(&self) -> &PipelineLayout;
 
+    /// Checks that this layout matches interface declared by shaders.
+    #[cfg(feature = "reflect")]
+    fn validate_against(
+        &self,
+        shaders: &[crate::Shader],
+    ) -> Result<(), crate::ValidateLayoutError> {
+        self.raw().info().validate_against(shaders)
+    }
+
     fn bind_graphics<D, const N: u32>(
         &self,
         updated_descriptors: &D,
@@ -1,5 +1,6 @@
 pub use crate::backend::RayTracingPipeline;
 use crate::{buffer::StridedBufferRange, shader::Shader, PipelineLayout};
+use bytemuck::Pod;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct RayTracingPipelineInfo {
@@ -34,14 +35,88 @@ pub enum RayTracingShaderGroupInfo {
         /// Index of closest-hit shader in `RayTracingPipelineInfo::shaders`.
         closest_hit: Option<u32>,
     },
+    /// Hit group for AABB geometry.
+    /// Intersection shader computes hits with primitives inside AABBs.
+    Procedural {
+        /// Index of intersection shader in `RayTracingPipelineInfo::shaders`.
+        intersection: u32,
+        /// Index of any-hit shader in `RayTracingPipelineInfo::shaders`.
+        any_hit: Option<u32>,
+        /// Index of closest-hit shader in `RayTracingPipelineInfo::shaders`.
+        closest_hit: Option<u32>,
+    },
+    /// Group with single callable shader.
+    /// Placed into callable region of shader binding table.
+    Callable {
+        /// Index of callable shader in `RayTracingPipelineInfo::shaders`.
+        callable: u32,
+    },
+}
+
+impl RayTracingShaderGroupInfo {
+    /// Returns `true` if this is hit group.
+    /// Only hit groups can be placed into hit region of shader binding table.
+    pub fn is_hit(&self) -> bool {
+        matches!(
+            self,
+            RayTracingShaderGroupInfo::Triangles { .. }
+                | RayTracingShaderGroupInfo::Procedural { .. }
+        )
+    }
+}
+
+/// Record in shader binding table.
+/// Consists of shader group handle followed by inline data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ShaderRecord<'a> {
+    /// Index of shader group in `RayTracingPipelineInfo::groups`.
+    pub group: u32,
+
+    /// Data placed right after group handle.
+    /// Shaders access it via `shaderRecordEXT` buffer block with `std430` layout.
+    pub data: &'a [u8],
+}
+
+impl ShaderRecord<'static> {
+    /// Returns record without inline data.
+    pub const fn new(group: u32) -> Self {
+        ShaderRecord { group, data: &[] }
+    }
+}
+
+impl<'a> ShaderRecord<'a> {
+    /// Returns record with inline data.
+    pub const fn with_data(group: u32, data: &'a [u8]) -> Self {
+        ShaderRecord { group, data }
+    }
+
+    /// Returns record with bytes of the value as inline data.
+    /// Use with `ShaderRepr::Type` to match layout expected by shaders.
+    pub fn with_pod<T>(group: u32, data: &'a T) -> Self
+    where
+        T: Pod,
+    {
+        ShaderRecord {
+            group,
+            data: bytemuck::bytes_of(data),
+        }
+    }
+}
+
+impl From<u32> for ShaderRecord<'static> {
+    fn from(group: u32) -> Self {
+        ShaderRecord::new(group)
+    }
 }
 
+/// Layout of shader binding table.
+/// Stride of each region is large enough to fit largest record in it.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ShaderBindingTableInfo<'a> {
-    pub raygen: Option<u32>,
-    pub miss: &'a [u32],
-    pub hit: &'a [u32],
-    pub callable: &'a [u32],
+    pub raygen: Option<ShaderRecord<'a>>,
+    pub miss: &'a [ShaderRecord<'a>],
+    pub hit: &'a [ShaderRecord<'a>],
+    pub callable: &'a [ShaderRecord<'a>],
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
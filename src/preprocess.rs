@@ -0,0 +1,610 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+/// Source of the file returned by [`IncludeResolver`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IncludedSource {
+    /// Unique name of the file.
+    /// Used in diagnostics and to resolve nested includes.
+    pub name: String,
+
+    /// Source code of the file.
+    pub code: String,
+}
+
+/// Resolves `#include` directives in shader source.
+pub trait IncludeResolver {
+    /// Returns source of file at `path` included from file named `from`.
+    fn resolve(&self, path: &str, from: &str) -> Result<IncludedSource, IncludeError>;
+}
+
+/// Resolves includes from filesystem.
+///
+/// Paths are looked up relative to the including file first
+/// and then in each of include directories in order.
+#[derive(Clone, Debug, Default)]
+pub struct FileIncludeResolver {
+    dirs: Vec<PathBuf>,
+}
+
+impl FileIncludeResolver {
+    pub fn new() -> Self {
+        FileIncludeResolver::default()
+    }
+
+    /// Adds include directory.
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dirs.push(dir.into());
+        self
+    }
+}
+
+impl IncludeResolver for FileIncludeResolver {
+    fn resolve(&self, path: &str, from: &str) -> Result<IncludedSource, IncludeError> {
+        let relative = Path::new(from).parent().map(|dir| dir.join(path));
+
+        let found = relative
+            .into_iter()
+            .chain(self.dirs.iter().map(|dir| dir.join(path)))
+            .find(|candidate| candidate.is_file());
+
+        match found {
+            Some(found) => match std::fs::read_to_string(&found) {
+                Ok(code) => Ok(IncludedSource {
+                    name: found.to_string_lossy().into_owned(),
+                    code,
+                }),
+                Err(source) => Err(IncludeError::IoError {
+                    path: found,
+                    source,
+                }),
+            },
+            None => Err(IncludeError::NotFound {
+                path: path.to_owned(),
+                from: from.to_owned(),
+            }),
+        }
+    }
+}
+
+/// Resolves includes from files stored in memory.
+/// Paths are matched exactly.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryIncludeResolver {
+    files: HashMap<String, String>,
+}
+
+impl MemoryIncludeResolver {
+    pub fn new() -> Self {
+        MemoryIncludeResolver::default()
+    }
+
+    /// Adds file with specified path.
+    pub fn add(&mut self, path: impl Into<String>, code: impl Into<String>) -> &mut Self {
+        self.files.insert(path.into(), code.into());
+        self
+    }
+
+    /// Adds file with specified path.
+    pub fn with(mut self, path: impl Into<String>, code: impl Into<String>) -> Self {
+        self.add(path, code);
+        self
+    }
+}
+
+impl IncludeResolver for MemoryIncludeResolver {
+    fn resolve(&self, path: &str, from: &str) -> Result<IncludedSource, IncludeError> {
+        match self.files.get(path) {
+            Some(code) => Ok(IncludedSource {
+                name: path.to_owned(),
+                code: code.clone(),
+            }),
+            None => Err(IncludeError::NotFound {
+                path: path.to_owned(),
+                from: from.to_owned(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IncludeError {
+    #[error("Source code it not utf-8")]
+    SourceNotUtf8 {
+        #[from]
+        source: std::str::Utf8Error,
+    },
+
+    #[error("Malformed include directive `{line}` in '{file}'")]
+    MalformedDirective { line: String, file: String },
+
+    #[error("File '{path}' included from '{from}' is not found")]
+    NotFound { path: String, from: String },
+
+    #[error("Failed to read '{}'", .path.display())]
+    IoError {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("File '{name}' includes itself")]
+    RecursiveInclude { name: String },
+}
+
+/// Maps expanded shader source back to files it was assembled from.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceMap {
+    /// All files that contributed to the source.
+    /// First one is the root file.
+    pub files: Vec<SourceFile>,
+
+    /// Ranges of expanded source, each copied from a single file.
+    pub chunks: Vec<SourceChunk>,
+}
+
+/// File that contributed to the shader source.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceFile {
+    pub name: String,
+    pub code: String,
+}
+
+/// Range of expanded source copied from one file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceChunk {
+    /// Start of the range in expanded source.
+    pub start: usize,
+
+    /// End of the range in expanded source.
+    pub end: usize,
+
+    /// Index of the file in [`SourceMap::files`].
+    pub file: usize,
+
+    /// Offset of the range start in the file.
+    pub file_start: usize,
+}
+
+impl SourceMap {
+    /// Maps range in expanded source to file index and range in that file.
+    /// Ranges spanning several chunks are truncated to the first one.
+    pub fn locate(&self, range: Range<usize>) -> Option<(usize, Range<usize>)> {
+        let chunk = self
+            .chunks
+            .iter()
+            .find(|chunk| chunk.start <= range.start && range.start < chunk.end)
+            .or_else(|| self.chunks.iter().find(|chunk| chunk.end == range.start))?;
+
+        let start = chunk.file_start + (range.start - chunk.start);
+        let end = chunk.file_start + (range.end.clamp(range.start, chunk.end) - chunk.start);
+        Some((chunk.file, start..end))
+    }
+}
+
+/// Replaces `#include "path"` and `#include <path>` lines with included sources.
+///
+/// Conditional directives are tracked to skip includes in inactive blocks.
+/// `#ifdef`, `#ifndef`, `#else` and `#endif` are fully supported,
+/// `#if` and `#elif` only with `defined(NAME)`, integer literal or macro name,
+/// optionally negated with `!`.
+/// Blocks with other conditions are treated as active.
+/// Skipped include lines are replaced with empty lines.
+pub(crate) fn expand_includes(
+    name: String,
+    code: String,
+    defines: &BTreeMap<String, String>,
+    resolver: &(impl IncludeResolver + ?Sized),
+) -> Result<(String, SourceMap), IncludeError> {
+    let mut expanded = String::with_capacity(code.len());
+    let mut map = SourceMap::default();
+    let mut stack = Vec::new();
+    let mut defines = defines
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+
+    expand_file(
+        IncludedSource { name, code },
+        resolver,
+        &mut stack,
+        &mut defines,
+        &mut expanded,
+        &mut map,
+    )?;
+
+    Ok((expanded, map))
+}
+
+/// State of `#if` block.
+struct Conditional {
+    /// Whether enclosing block is active.
+    outer: bool,
+
+    /// Whether current branch is active.
+    active: bool,
+
+    /// Whether some branch was already taken.
+    /// `None` if some condition could not be evaluated.
+    taken: Option<bool>,
+}
+
+fn expand_file(
+    source: IncludedSource,
+    resolver: &(impl IncludeResolver + ?Sized),
+    stack: &mut Vec<String>,
+    defines: &mut HashMap<String, String>,
+    expanded: &mut String,
+    map: &mut SourceMap,
+) -> Result<(), IncludeError> {
+    if stack.contains(&source.name) {
+        return Err(IncludeError::RecursiveInclude { name: source.name });
+    }
+
+    let IncludedSource { name, code } = source;
+
+    // Code is stored after nested includes are expanded.
+    let file = map.files.len();
+    map.files.push(SourceFile {
+        name: name.clone(),
+        code: String::new(),
+    });
+    stack.push(name.clone());
+
+    let mut conditionals: Vec<Conditional> = Vec::new();
+
+    let mut offset = 0;
+    while offset < code.len() {
+        let line_end = code[offset..]
+            .find('\n')
+            .map_or(code.len(), |end| offset + end + 1);
+        let line = &code[offset..line_end];
+
+        let active = conditionals.last().is_none_or(|c| c.active);
+
+        let (directive, rest) = directive(line).unwrap_or(("", ""));
+        match directive {
+            "if" | "ifdef" | "ifndef" => {
+                let condition = match directive {
+                    "ifdef" => Some(defines.contains_key(identifier(rest))),
+                    "ifndef" => Some(!defines.contains_key(identifier(rest))),
+                    _ => evaluate(rest, defines),
+                };
+                conditionals.push(Conditional {
+                    outer: active,
+                    active: active && condition.unwrap_or(true),
+                    taken: condition,
+                });
+            }
+            "elif" => {
+                if let Some(c) = conditionals.last_mut() {
+                    match c.taken {
+                        Some(true) => c.active = false,
+                        None => c.active = c.outer,
+                        Some(false) => {
+                            let condition = evaluate(rest, defines);
+                            c.active = c.outer && condition.unwrap_or(true);
+                            c.taken = condition;
+                        }
+                    }
+                }
+            }
+            "else" => {
+                if let Some(c) = conditionals.last_mut() {
+                    c.active = c.outer && c.taken != Some(true);
+                }
+            }
+            "endif" => {
+                conditionals.pop();
+            }
+            "define" if active => {
+                let name = identifier(rest);
+                let value = rest.trim_start()[name.len()..].trim();
+                defines.insert(name.to_owned(), value.to_owned());
+            }
+            "undef" if active => {
+                defines.remove(identifier(rest));
+            }
+            _ => {}
+        }
+
+        match include_path(line) {
+            None => {
+                let start = expanded.len();
+                expanded.push_str(line);
+                push_chunk(map, start, expanded.len(), file, offset);
+            }
+            Some(_) if !active => {
+                if line.ends_with('\n') {
+                    expanded.push('\n');
+                }
+            }
+            Some(None) => {
+                return Err(IncludeError::MalformedDirective {
+                    line: line.trim().to_owned(),
+                    file: name,
+                })
+            }
+            Some(Some(path)) => {
+                let included = resolver.resolve(path, &name)?;
+                expand_file(included, resolver, stack, defines, expanded, map)?;
+                if !expanded.is_empty() && !expanded.ends_with('\n') {
+                    expanded.push('\n');
+                }
+            }
+        }
+
+        offset = line_end;
+    }
+
+    map.files[file].code = code;
+    stack.pop();
+    Ok(())
+}
+
+/// Evaluates `#if` condition.
+/// Returns `None` if condition is not supported.
+fn evaluate(condition: &str, defines: &HashMap<String, String>) -> Option<bool> {
+    let condition = condition.split("//").next().unwrap_or_default().trim();
+
+    if let Some(condition) = condition.strip_prefix('!') {
+        return evaluate(condition, defines).map(|value| !value);
+    }
+
+    if let Some(rest) = condition.strip_prefix("defined") {
+        let rest = rest.trim();
+        let name = match rest.strip_prefix('(') {
+            Some(rest) => rest.strip_suffix(')')?.trim(),
+            None => rest,
+        };
+        if name.is_empty() || identifier(name) != name {
+            return None;
+        }
+        return Some(defines.contains_key(name));
+    }
+
+    if let Ok(value) = condition.parse::<i64>() {
+        return Some(value != 0);
+    }
+
+    if !condition.is_empty() && identifier(condition) == condition {
+        // Undefined macros evaluate to zero.
+        return match defines.get(condition) {
+            None => Some(false),
+            Some(value) => value.parse::<i64>().ok().map(|value| value != 0),
+        };
+    }
+
+    None
+}
+
+/// Returns leading identifier of the string.
+fn identifier(string: &str) -> &str {
+    let string = string.trim_start();
+    let end = string
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(string.len());
+    &string[..end]
+}
+
+/// Splits preprocessor directive line into directive name and the rest.
+/// Returns `None` if line is not a directive.
+fn directive(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim_start().strip_prefix('#')?;
+    let name = identifier(rest);
+    let rest = &rest.trim_start()[name.len()..];
+    Some((name, rest))
+}
+
+/// Extends last chunk if it is contiguous with the new one.
+fn push_chunk(map: &mut SourceMap, start: usize, end: usize, file: usize, file_start: usize) {
+    if let Some(last) = map.chunks.last_mut() {
+        if last.file == file
+            && last.end == start
+            && last.file_start + (start - last.start) == file_start
+        {
+            last.end = end;
+            return;
+        }
+    }
+
+    map.chunks.push(SourceChunk {
+        start,
+        end,
+        file,
+        file_start,
+    });
+}
+
+/// Returns `None` if line is not an include directive
+/// and `Some(None)` if directive is malformed.
+fn include_path(line: &str) -> Option<Option<&str>> {
+    let (directive, rest) = directive(line)?;
+    if directive != "include" {
+        return None;
+    }
+    let rest = rest.trim();
+
+    let path = if let Some(rest) = rest.strip_prefix('"') {
+        rest.strip_suffix('"')
+    } else if let Some(rest) = rest.strip_prefix('<') {
+        rest.strip_suffix('>')
+    } else {
+        None
+    };
+
+    Some(path.filter(|path| !path.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(
+        code: &str,
+        resolver: &MemoryIncludeResolver,
+    ) -> Result<(String, SourceMap), IncludeError> {
+        expand_includes(
+            "root".to_owned(),
+            code.to_owned(),
+            &BTreeMap::new(),
+            resolver,
+        )
+    }
+
+    #[test]
+    fn nested_includes() {
+        let resolver = MemoryIncludeResolver::new()
+            .with("b", "b1\n#include \"c\"\nb2\n")
+            .with("c", "c1");
+
+        let (code, map) = expand("x\n#include \"b\"\ny\n", &resolver).unwrap();
+        assert_eq!(code, "x\nb1\nc1\nb2\ny\n");
+
+        let names = map
+            .files
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["root", "b", "c"]);
+        assert_eq!(map.files[1].code, "b1\n#include \"c\"\nb2\n");
+
+        // Offsets map back into the file each chunk was copied from.
+        assert_eq!(map.locate(0..1), Some((0, 0..1)));
+        assert_eq!(map.locate(2..4), Some((1, 0..2)));
+        assert_eq!(map.locate(5..7), Some((2, 0..2)));
+        assert_eq!(map.locate(8..10), Some((1, 16..18)));
+        assert_eq!(map.locate(11..12), Some((0, 15..16)));
+
+        // Range spanning several chunks is truncated to the first one.
+        assert_eq!(map.locate(0..6), Some((0, 0..2)));
+
+        // Empty range at the end of the source.
+        assert_eq!(map.locate(13..13), Some((0, 17..17)));
+        assert_eq!(map.locate(14..15), None);
+    }
+
+    #[test]
+    fn recursive_include() {
+        let resolver = MemoryIncludeResolver::new()
+            .with("a", "#include \"b\"\n")
+            .with("b", "#include <a>\n");
+
+        let err = expand("#include \"a\"\n", &resolver).unwrap_err();
+        assert!(matches!(err, IncludeError::RecursiveInclude { name } if name == "a"));
+    }
+
+    #[test]
+    fn malformed_directives() {
+        let resolver = MemoryIncludeResolver::new();
+
+        for line in [
+            "#include b",
+            "#include \"\"",
+            "#include \"b",
+            "  #  include <b\"",
+        ] {
+            let err = expand(&format!("x\n{}\n", line), &resolver).unwrap_err();
+            assert!(
+                matches!(&err, IncludeError::MalformedDirective { line: l, file } if l == line.trim() && file == "root"),
+                "{:?}",
+                err
+            );
+        }
+
+        // Not an include directive.
+        let (code, _) = expand("#includes \"b\"\n", &resolver).unwrap();
+        assert_eq!(code, "#includes \"b\"\n");
+    }
+
+    #[test]
+    fn missing_include() {
+        let err = expand("#include \"b\"\n", &MemoryIncludeResolver::new()).unwrap_err();
+        assert!(
+            matches!(err, IncludeError::NotFound { path, from } if path == "b" && from == "root")
+        );
+    }
+
+    #[test]
+    fn inactive_blocks() {
+        let resolver = MemoryIncludeResolver::new()
+            .with("c", "c1\n")
+            .with("d", "d1\n");
+
+        let code = "\
+#define USE_B 0
+#if USE_B
+#include \"b\"
+#elif !defined(USE_C)
+#include \"c\"
+#else
+#include \"b\"
+#endif
+#ifdef MISSING
+#include \"missing\"
+#endif
+#ifndef MISSING
+#include \"d\"
+#endif
+#if USE_B > 1
+#include \"c\"
+#endif
+";
+        let (expanded, map) = expand(code, &resolver).unwrap();
+        assert_eq!(
+            expanded,
+            "\
+#define USE_B 0
+#if USE_B
+
+#elif !defined(USE_C)
+c1
+#else
+
+#endif
+#ifdef MISSING
+
+#endif
+#ifndef MISSING
+d1
+#endif
+#if USE_B > 1
+c1
+#endif
+"
+        );
+
+        // Unsupported conditions are treated as active.
+        assert_eq!(map.files.len(), 4);
+    }
+
+    #[test]
+    fn defines_select_includes() {
+        let resolver = MemoryIncludeResolver::new().with("b", "b1\n");
+        let code = "#if !USE_B\n#else\n#include \"b\"\n#endif\n";
+
+        let (expanded, _) = expand(code, &resolver).unwrap();
+        assert_eq!(expanded, "#if !USE_B\n#else\n\n#endif\n");
+
+        let defines = BTreeMap::from([("USE_B".to_owned(), "1".to_owned())]);
+        let (expanded, _) =
+            expand_includes("root".to_owned(), code.to_owned(), &defines, &resolver).unwrap();
+        assert_eq!(expanded, "#if !USE_B\n#else\nb1\n#endif\n");
+    }
+
+    #[test]
+    fn include_guard() {
+        let resolver = MemoryIncludeResolver::new()
+            .with("g", "#ifndef G\n#define G\n#include \"h\"\n#endif\n")
+            .with("h", "h1\n");
+
+        let (expanded, _) = expand("#include \"g\"\n#include \"g\"\n", &resolver).unwrap();
+        assert_eq!(expanded.matches("h1").count(), 1);
+    }
+}
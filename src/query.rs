@@ -0,0 +1,145 @@
+pub use crate::backend::QueryPool;
+use crate::{DeviceLost, OutOfMemory};
+
+/// Kind of queries in a [`QueryPool`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub enum QueryType {
+    /// Counts samples that pass depth and stencil tests
+    /// between `begin_query` and `end_query`.
+    Occlusion,
+
+    /// Counts pipeline statistics specified by flags
+    /// between `begin_query` and `end_query`.
+    ///
+    /// Requires [`Feature::PipelineStatisticsQuery`].
+    ///
+    /// [`Feature::PipelineStatisticsQuery`]: crate::Feature::PipelineStatisticsQuery
+    PipelineStatistics(PipelineStatisticFlags),
+
+    /// Records device timestamps with `write_timestamp`.
+    Timestamp,
+
+    /// Records compacted sizes of acceleration structures
+    /// with `write_acceleration_structures_properties`.
+    ///
+    /// Requires [`Feature::AccelerationStructure`].
+    ///
+    /// [`Feature::AccelerationStructure`]: crate::Feature::AccelerationStructure
+    AccelerationStructureCompactedSize,
+
+    /// Records sizes of serialized acceleration structures
+    /// with `write_acceleration_structures_properties`.
+    ///
+    /// Requires [`Feature::AccelerationStructure`].
+    ///
+    /// [`Feature::AccelerationStructure`]: crate::Feature::AccelerationStructure
+    AccelerationStructureSerializationSize,
+}
+
+impl QueryType {
+    /// Returns number of values written for each query of this type.
+    pub fn values_per_query(&self) -> u32 {
+        match self {
+            QueryType::Occlusion
+            | QueryType::Timestamp
+            | QueryType::AccelerationStructureCompactedSize
+            | QueryType::AccelerationStructureSerializationSize => 1,
+            QueryType::PipelineStatistics(flags) => flags.bits().count_ones(),
+        }
+    }
+}
+
+bitflags::bitflags! {
+    /// Flags to specify set of pipeline statistics counted by queries.
+    ///
+    /// Values for each query are written in order of increasing bit.
+    #[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+    pub struct PipelineStatisticFlags: u32 {
+        /// Number of vertices processed by input assembly stage.
+        const INPUT_ASSEMBLY_VERTICES = 0x001;
+
+        /// Number of primitives processed by input assembly stage.
+        const INPUT_ASSEMBLY_PRIMITIVES = 0x002;
+
+        /// Number of vertex shader invocations.
+        const VERTEX_SHADER_INVOCATIONS = 0x004;
+
+        /// Number of geometry shader invocations.
+        const GEOMETRY_SHADER_INVOCATIONS = 0x008;
+
+        /// Number of primitives generated by geometry shader.
+        const GEOMETRY_SHADER_PRIMITIVES = 0x010;
+
+        /// Number of primitives processed by clipping stage.
+        const CLIPPING_INVOCATIONS = 0x020;
+
+        /// Number of primitives output by clipping stage.
+        const CLIPPING_PRIMITIVES = 0x040;
+
+        /// Number of fragment shader invocations.
+        const FRAGMENT_SHADER_INVOCATIONS = 0x080;
+
+        /// Number of patches processed by tessellation control shader.
+        const TESSELLATION_CONTROL_SHADER_PATCHES = 0x100;
+
+        /// Number of tessellation evaluation shader invocations.
+        const TESSELLATION_EVALUATION_SHADER_INVOCATIONS = 0x200;
+
+        /// Number of compute shader invocations.
+        const COMPUTE_SHADER_INVOCATIONS = 0x400;
+    }
+}
+
+bitflags::bitflags! {
+    /// Flags to control how query results are copied to a buffer.
+    /// Results are always written as 64-bit values.
+    #[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+    pub struct QueryResultFlags: u32 {
+        /// Wait for results of each query to become available.
+        const WAIT = 0x2;
+
+        /// Write availability value after results of each query.
+        /// Non-zero value means that results are available.
+        const WITH_AVAILABILITY = 0x4;
+
+        /// Allow writing partial results for unavailable queries.
+        const PARTIAL = 0x8;
+    }
+}
+
+/// Defines [`QueryPool`] state.
+/// Can be used to [`Device::create_query_pool`].
+///
+/// [`Device::create_query_pool`]: crate::Device::create_query_pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct QueryPoolInfo {
+    /// Type of queries in the pool.
+    pub ty: QueryType,
+
+    /// Number of queries in the pool.
+    pub count: u32,
+}
+
+/// Possible error that may occur when fetching query results.
+#[derive(Clone, Copy, Debug, thiserror::Error, PartialEq, Eq)]
+pub enum QueryError {
+    #[error(transparent)]
+    OutOfMemory {
+        #[from]
+        source: OutOfMemory,
+    },
+
+    #[error(transparent)]
+    DeviceLost {
+        #[from]
+        source: DeviceLost,
+    },
+}
+
+#[allow(dead_code)]
+fn check() {
+    crate::assert_object::<QueryPool>();
+    crate::assert_error::<QueryError>();
+}
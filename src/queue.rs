@@ -171,6 +171,311 @@ impl QueuesQuery for SingleQueueQuery {
     }
 }
 
+/// Role of a queue requested by [`QueuePlan`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub enum QueueRole {
+    /// Queue for graphics and general work.
+    Graphics,
+
+    /// Queue for compute work running asynchronously with graphics.
+    AsyncCompute,
+
+    /// Queue for transfers running asynchronously with other work.
+    Transfer,
+}
+
+impl QueueRole {
+    /// Returns true if queue family with specified capabilities can serve this role.
+    /// Graphics and compute queues implicitly support transfer operations.
+    #[inline]
+    pub fn supported_by(&self, capabilities: QueueCapabilityFlags) -> bool {
+        match self {
+            QueueRole::Graphics => capabilities.supports_graphics(),
+            QueueRole::AsyncCompute => capabilities.supports_compute(),
+            QueueRole::Transfer => capabilities.intersects(QueueCapabilityFlags::all()),
+        }
+    }
+
+    /// Lower rank means family is better suited for this role.
+    fn rank(&self, capabilities: QueueCapabilityFlags) -> u32 {
+        let graphics = capabilities.supports_graphics() as u32;
+        let compute = capabilities.supports_compute() as u32;
+
+        match self {
+            // Prefer general purpose family.
+            QueueRole::Graphics => 1 - compute,
+            // Prefer family without graphics.
+            QueueRole::AsyncCompute => graphics,
+            // Prefer dedicated transfer family, then compute-only family.
+            QueueRole::Transfer => graphics * 2 + compute,
+        }
+    }
+
+    /// Capabilities reported when no suitable queue is found.
+    fn capabilities(&self) -> QueueCapabilityFlags {
+        match self {
+            QueueRole::Graphics => QueueCapabilityFlags::GRAPHICS,
+            QueueRole::AsyncCompute => QueueCapabilityFlags::COMPUTE,
+            QueueRole::Transfer => QueueCapabilityFlags::TRANSFER,
+        }
+    }
+}
+
+/// Queue of a family picked for a role.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct QueueSlot {
+    /// Index of the family.
+    pub family: usize,
+
+    /// Index of the queue within family.
+    pub index: usize,
+}
+
+/// Query for queues with different roles.
+///
+/// Each role gets a separate queue, preferably from a family dedicated to that role.
+/// When families are shared, roles are assigned to different queues of the same family.
+/// When family has no more queues, role aliases queue of another role.
+/// Graphics queue is never aliased.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct QueuePlan {
+    /// Request queue for graphics and general work.
+    pub graphics: bool,
+
+    /// Request queue for compute work running asynchronously with graphics.
+    pub async_compute: bool,
+
+    /// Request queue for transfers running asynchronously with other work.
+    pub transfer: bool,
+}
+
+impl QueuePlan {
+    /// Plan with graphics, async compute and transfer queues.
+    pub const ALL: Self = QueuePlan {
+        graphics: true,
+        async_compute: true,
+        transfer: true,
+    };
+
+    /// Assigns queues to requested roles.
+    pub fn assign(&self, families: &[FamilyInfo]) -> Result<QueueAssignment, QueueNotFound> {
+        let mut used = vec![0; families.len()];
+        let mut assignment = QueueAssignment::default();
+
+        for (role, requested) in [
+            (QueueRole::Graphics, self.graphics),
+            (QueueRole::AsyncCompute, self.async_compute),
+            (QueueRole::Transfer, self.transfer),
+        ] {
+            if !requested {
+                continue;
+            }
+
+            let free = families
+                .iter()
+                .enumerate()
+                .filter(|(index, family)| {
+                    role.supported_by(family.capabilities) && used[*index] < family.count
+                })
+                .min_by_key(|(_, family)| role.rank(family.capabilities));
+
+            let slot = match free {
+                Some((family, _)) => {
+                    let slot = QueueSlot {
+                        family,
+                        index: used[family],
+                    };
+                    used[family] += 1;
+                    slot
+                }
+                None if role == QueueRole::Graphics => {
+                    return Err(QueueNotFound(role.capabilities()))
+                }
+                None => {
+                    // Alias queue of previously assigned role.
+                    let aliased = [QueueRole::AsyncCompute, QueueRole::Graphics]
+                        .iter()
+                        .filter_map(|&other| assignment.slot(other))
+                        .find(|slot| role.supported_by(families[slot.family].capabilities));
+
+                    match aliased {
+                        Some(slot) => slot,
+                        None => return Err(QueueNotFound(role.capabilities())),
+                    }
+                }
+            };
+
+            *assignment.slot_mut(role) = Some(slot);
+        }
+
+        Ok(assignment)
+    }
+}
+
+/// Queues assigned to roles by [`QueuePlan`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct QueueAssignment {
+    /// Queue for graphics and general work.
+    /// `None` if not requested.
+    pub graphics: Option<QueueSlot>,
+
+    /// Queue for async compute.
+    /// `None` if not requested.
+    /// May be the same slot as `graphics`.
+    pub async_compute: Option<QueueSlot>,
+
+    /// Queue for transfers.
+    /// `None` if not requested.
+    /// May be the same slot as `graphics` or `async_compute`.
+    pub transfer: Option<QueueSlot>,
+}
+
+impl QueueAssignment {
+    /// Returns queue assigned to the role.
+    #[inline]
+    pub fn slot(&self, role: QueueRole) -> Option<QueueSlot> {
+        match role {
+            QueueRole::Graphics => self.graphics,
+            QueueRole::AsyncCompute => self.async_compute,
+            QueueRole::Transfer => self.transfer,
+        }
+    }
+
+    fn slot_mut(&mut self, role: QueueRole) -> &mut Option<QueueSlot> {
+        match role {
+            QueueRole::Graphics => &mut self.graphics,
+            QueueRole::AsyncCompute => &mut self.async_compute,
+            QueueRole::Transfer => &mut self.transfer,
+        }
+    }
+
+    /// Returns role which queue is reused for specified role.
+    pub fn aliased(&self, role: QueueRole) -> Option<QueueRole> {
+        let slot = self.slot(role)?;
+
+        [QueueRole::Graphics, QueueRole::AsyncCompute]
+            .iter()
+            .take_while(|&&other| other != role)
+            .copied()
+            .find(|&other| self.slot(other) == Some(slot))
+    }
+
+    /// Returns true if no other role uses queue family of specified role.
+    pub fn is_dedicated(&self, role: QueueRole) -> bool {
+        match self.slot(role) {
+            None => false,
+            Some(slot) => [
+                QueueRole::Graphics,
+                QueueRole::AsyncCompute,
+                QueueRole::Transfer,
+            ]
+            .iter()
+            .filter(|&&other| other != role)
+            .filter_map(|&other| self.slot(other))
+            .all(|other| other.family != slot.family),
+        }
+    }
+
+    /// Returns number of queues to create for each family.
+    fn query(&self) -> Vec<(usize, usize)> {
+        let mut query: Vec<(usize, usize)> = Vec::new();
+
+        for slot in [self.graphics, self.async_compute, self.transfer]
+            .iter()
+            .flatten()
+        {
+            match query.iter_mut().find(|(family, _)| *family == slot.family) {
+                Some((_, count)) => *count = (*count).max(slot.index + 1),
+                None => query.push((slot.family, slot.index + 1)),
+            }
+        }
+
+        query
+    }
+}
+
+/// Queues created by [`QueuePlan`].
+#[derive(Debug)]
+pub struct PlannedQueues {
+    /// Queue for graphics and general work.
+    pub graphics: Option<Queue>,
+
+    /// Queue for async compute.
+    /// `None` if it aliases graphics queue.
+    pub async_compute: Option<Queue>,
+
+    /// Queue for transfers.
+    /// `None` if it aliases another queue.
+    pub transfer: Option<Queue>,
+
+    /// Assignment of queues to roles.
+    pub assignment: QueueAssignment,
+}
+
+impl PlannedQueues {
+    /// Returns queue serving the role, resolving aliasing.
+    pub fn get(&self, role: QueueRole) -> Option<&Queue> {
+        match self.assignment.aliased(role).unwrap_or(role) {
+            QueueRole::Graphics => self.graphics.as_ref(),
+            QueueRole::AsyncCompute => self.async_compute.as_ref(),
+            QueueRole::Transfer => self.transfer.as_ref(),
+        }
+    }
+
+    /// Returns queue serving the role, resolving aliasing.
+    pub fn get_mut(&mut self, role: QueueRole) -> Option<&mut Queue> {
+        match self.assignment.aliased(role).unwrap_or(role) {
+            QueueRole::Graphics => self.graphics.as_mut(),
+            QueueRole::AsyncCompute => self.async_compute.as_mut(),
+            QueueRole::Transfer => self.transfer.as_mut(),
+        }
+    }
+}
+
+impl QueuesQuery for QueuePlan {
+    type Collector = QueueAssignment;
+    type Error = QueueNotFound;
+    type Query = Vec<(usize, usize)>;
+    type Queues = PlannedQueues;
+
+    #[inline]
+    fn query(
+        self,
+        families: &[FamilyInfo],
+    ) -> Result<(Vec<(usize, usize)>, QueueAssignment), QueueNotFound> {
+        let assignment = self.assign(families)?;
+        Ok((assignment.query(), assignment))
+    }
+
+    fn collect(assignment: QueueAssignment, families: Vec<Family>) -> PlannedQueues {
+        let mut queues: Vec<Queue> = families.into_iter().flat_map(|f| f.queues).collect();
+
+        let mut take = |role| {
+            if assignment.aliased(role).is_some() {
+                return None;
+            }
+
+            let slot = assignment.slot(role)?;
+            let position = queues.iter().position(|queue| {
+                let id = queue.id();
+                id.family as usize == slot.family && id.index as usize == slot.index
+            })?;
+            Some(queues.swap_remove(position))
+        };
+
+        PlannedQueues {
+            graphics: take(QueueRole::Graphics),
+            async_compute: take(QueueRole::AsyncCompute),
+            transfer: take(QueueRole::Transfer),
+            assignment,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
 pub struct QueueId {
@@ -234,3 +539,141 @@ pub enum Ownership {
     Owned { family: u32 },
     Transition { from: u32, to: u32 },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn family(capabilities: QueueCapabilityFlags, count: usize) -> FamilyInfo {
+        FamilyInfo {
+            capabilities,
+            count,
+        }
+    }
+
+    fn slot(family: usize, index: usize) -> Option<QueueSlot> {
+        Some(QueueSlot { family, index })
+    }
+
+    const GENERAL: QueueCapabilityFlags = QueueCapabilityFlags::all();
+
+    #[test]
+    fn separate_families() {
+        let families = [
+            family(QueueCapabilityFlags::TRANSFER, 2),
+            family(GENERAL, 16),
+            family(
+                QueueCapabilityFlags::COMPUTE | QueueCapabilityFlags::TRANSFER,
+                8,
+            ),
+        ];
+
+        let assignment = QueuePlan::ALL.assign(&families).unwrap();
+        assert_eq!(
+            assignment,
+            QueueAssignment {
+                graphics: slot(1, 0),
+                async_compute: slot(2, 0),
+                transfer: slot(0, 0),
+            }
+        );
+        assert!(assignment.is_dedicated(QueueRole::Graphics));
+        assert!(assignment.is_dedicated(QueueRole::AsyncCompute));
+        assert!(assignment.is_dedicated(QueueRole::Transfer));
+        assert_eq!(assignment.query(), [(1, 1), (2, 1), (0, 1)]);
+    }
+
+    #[test]
+    fn single_queue_is_shared() {
+        let families = [family(GENERAL, 1)];
+
+        let assignment = QueuePlan::ALL.assign(&families).unwrap();
+        assert_eq!(
+            assignment,
+            QueueAssignment {
+                graphics: slot(0, 0),
+                async_compute: slot(0, 0),
+                transfer: slot(0, 0),
+            }
+        );
+        assert_eq!(
+            assignment.aliased(QueueRole::AsyncCompute),
+            Some(QueueRole::Graphics)
+        );
+        assert_eq!(
+            assignment.aliased(QueueRole::Transfer),
+            Some(QueueRole::Graphics)
+        );
+        assert_eq!(assignment.aliased(QueueRole::Graphics), None);
+        assert_eq!(assignment.query(), [(0, 1)]);
+    }
+
+    #[test]
+    fn shared_family_with_several_queues() {
+        let assignment = QueuePlan::ALL.assign(&[family(GENERAL, 3)]).unwrap();
+        assert_eq!(
+            assignment,
+            QueueAssignment {
+                graphics: slot(0, 0),
+                async_compute: slot(0, 1),
+                transfer: slot(0, 2),
+            }
+        );
+        assert_eq!(assignment.aliased(QueueRole::Transfer), None);
+        assert!(!assignment.is_dedicated(QueueRole::Graphics));
+        assert_eq!(assignment.query(), [(0, 3)]);
+
+        // Transfer reuses async compute queue when family runs out of queues.
+        let assignment = QueuePlan::ALL.assign(&[family(GENERAL, 2)]).unwrap();
+        assert_eq!(assignment.transfer, slot(0, 1));
+        assert_eq!(
+            assignment.aliased(QueueRole::Transfer),
+            Some(QueueRole::AsyncCompute)
+        );
+        assert_eq!(assignment.query(), [(0, 2)]);
+    }
+
+    #[test]
+    fn compute_only_family_for_async_compute() {
+        let families = [family(GENERAL, 1), family(QueueCapabilityFlags::COMPUTE, 1)];
+
+        let assignment = QueuePlan::ALL.assign(&families).unwrap();
+        assert_eq!(assignment.graphics, slot(0, 0));
+        assert_eq!(assignment.async_compute, slot(1, 0));
+
+        // Compute-only family supports transfers implicitly,
+        // but its only queue is taken, so transfer reuses it.
+        assert_eq!(assignment.transfer, slot(1, 0));
+        assert_eq!(
+            assignment.aliased(QueueRole::Transfer),
+            Some(QueueRole::AsyncCompute)
+        );
+    }
+
+    #[test]
+    fn graphics_not_found() {
+        let families = [
+            family(
+                QueueCapabilityFlags::COMPUTE | QueueCapabilityFlags::TRANSFER,
+                4,
+            ),
+            family(QueueCapabilityFlags::TRANSFER, 1),
+        ];
+
+        let result = QueuePlan::ALL.assign(&families);
+        assert!(
+            matches!(result, Err(QueueNotFound(flags)) if flags == QueueCapabilityFlags::GRAPHICS)
+        );
+
+        // Without graphics request compute family is enough.
+        let assignment = QueuePlan {
+            graphics: false,
+            ..QueuePlan::ALL
+        }
+        .assign(&families)
+        .unwrap();
+        assert_eq!(assignment.graphics, None);
+        assert_eq!(assignment.async_compute, slot(0, 0));
+        assert_eq!(assignment.transfer, slot(1, 0));
+    }
+}
@@ -0,0 +1,1212 @@
+use std::fmt::{self, Display};
+
+use crate::{
+    descriptor::{DescriptorSetLayoutInfo, DescriptorType},
+    format::{Format, Type},
+    pipeline::{
+        GraphicsPipelineInfo, GraphicsPipelineRenderingInfo, PipelineLayoutInfo, PushConstant,
+        VertexInputAttribute,
+    },
+    shader::{Shader, ShaderLanguage, ShaderModuleInfo, ShaderStage},
+};
+
+/// Interface of shader module entry points.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ShaderReflection {
+    /// All entry points declared in the module.
+    pub entry_points: Vec<EntryPointReflection>,
+}
+
+impl ShaderReflection {
+    /// Returns entry point with specified name and stage.
+    pub fn entry_point(&self, name: &str, stage: ShaderStage) -> Option<&EntryPointReflection> {
+        self.entry_points
+            .iter()
+            .find(|entry| entry.name == name && entry.stage == stage)
+    }
+}
+
+/// Interface of one entry point.
+/// Contains only resources statically used by the entry point.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EntryPointReflection {
+    /// Name of the entry point.
+    pub name: String,
+
+    /// Stage of the entry point.
+    pub stage: ShaderStage,
+
+    /// Descriptor bindings used by the entry point.
+    pub bindings: Vec<ReflectedBinding>,
+
+    /// Range of push constants used by the entry point.
+    pub push_constants: Option<ReflectedPushConstants>,
+
+    /// Location-bound inputs.
+    /// For vertex shaders these are vertex attributes.
+    pub inputs: Vec<ReflectedLocation>,
+
+    /// Location-bound outputs.
+    /// For fragment shaders these are color attachments.
+    pub outputs: Vec<ReflectedLocation>,
+}
+
+/// Descriptor binding declared in shader.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ReflectedBinding {
+    /// Index of descriptor set.
+    pub set: u32,
+
+    /// Binding index in the set.
+    pub binding: u32,
+
+    /// Type of descriptor.
+    ///
+    /// Shader code does not distinguish dynamic buffers and combined image samplers,
+    /// so only non-dynamic buffer types, `SampledImage` and `Sampler` are reported.
+    pub ty: DescriptorType,
+
+    /// Number of descriptors in the binding.
+    /// Zero for runtime-sized arrays.
+    pub count: u32,
+}
+
+/// Range of push constants declared in shader.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ReflectedPushConstants {
+    pub offset: u32,
+    pub size: u32,
+}
+
+/// Location-bound shader input or output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ReflectedLocation {
+    /// Location index.
+    pub location: u32,
+
+    /// Format matching the value type.
+    /// `None` for types without single matching format.
+    pub format: Option<Format>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReflectError {
+    #[error("Shader language {language:?} is unsupported")]
+    UnsupportedShaderLanguage { language: ShaderLanguage },
+
+    #[cfg(feature = "glsl")]
+    #[error("GLSL shader stage {stage} is unsupported")]
+    UnsupportedGlslStage { stage: ShaderStage },
+
+    #[error("Source code it not utf-8")]
+    SourceNotUtf8 {
+        #[from]
+        source: std::str::Utf8Error,
+    },
+
+    #[cfg(feature = "glsl")]
+    #[error("Failed to parse GLSL shader")]
+    NagaGlslParseError {
+        errors: Vec<naga::front::glsl::Error>,
+    },
+
+    #[cfg(feature = "wgsl")]
+    #[error("Failed to parse WGSL shader")]
+    NagaWgslParseError {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    #[error("Failed to parse SPIR-V shader")]
+    NagaSpvParseError {
+        #[from]
+        source: naga::front::spv::Error,
+    },
+
+    #[error("Failed to validate shader module")]
+    NagaValidationError {
+        #[from]
+        source: naga::WithSpan<naga::valid::ValidationError>,
+    },
+}
+
+/// Difference between pipeline layout and interface declared by shader.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LayoutMismatch {
+    /// Shader module has no entry point for the shader.
+    EntryPointNotFound { entry: String, stage: ShaderStage },
+
+    /// Layout has no binding used by shader.
+    MissingBinding {
+        stage: ShaderStage,
+        set: u32,
+        binding: u32,
+    },
+
+    /// Binding in layout is not visible in the shader stage.
+    BindingNotVisible {
+        stage: ShaderStage,
+        set: u32,
+        binding: u32,
+    },
+
+    /// Type of binding in layout is incompatible with type declared in shader.
+    DescriptorTypeMismatch {
+        stage: ShaderStage,
+        set: u32,
+        binding: u32,
+        layout: DescriptorType,
+        shader: DescriptorType,
+    },
+
+    /// Binding in layout has fewer descriptors than declared in shader.
+    DescriptorCountMismatch {
+        stage: ShaderStage,
+        set: u32,
+        binding: u32,
+        layout: u32,
+        shader: u32,
+    },
+
+    /// Push constant ranges in layout do not cover range used by shader.
+    PushConstantsNotCovered {
+        stage: ShaderStage,
+        offset: u32,
+        size: u32,
+    },
+
+    /// Pipeline has no vertex attribute for vertex shader input.
+    MissingVertexAttribute { location: u32 },
+
+    /// Numeric type of vertex attribute format differs from vertex shader input type.
+    VertexAttributeTypeMismatch {
+        location: u32,
+        attribute: Format,
+        shader: Format,
+    },
+
+    /// Pipeline has no color attachment for fragment shader output.
+    MissingColorAttachment { location: u32 },
+
+    /// Numeric type of color attachment format differs from fragment shader output type.
+    ColorAttachmentTypeMismatch {
+        location: u32,
+        attachment: Format,
+        shader: Format,
+    },
+}
+
+impl Display for LayoutMismatch {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EntryPointNotFound { entry, stage } => {
+                write!(fmt, "Entry point '{}' for {} stage not found", entry, stage)
+            }
+            Self::MissingBinding {
+                stage,
+                set,
+                binding,
+            } => write!(
+                fmt,
+                "Binding {} in set {} used by {} shader is missing in layout",
+                binding, set, stage
+            ),
+            Self::BindingNotVisible {
+                stage,
+                set,
+                binding,
+            } => write!(
+                fmt,
+                "Binding {} in set {} is not visible in {} stage",
+                binding, set, stage
+            ),
+            Self::DescriptorTypeMismatch {
+                stage,
+                set,
+                binding,
+                layout,
+                shader,
+            } => write!(
+                fmt,
+                "Binding {} in set {} is {:?} in layout but {:?} in {} shader",
+                binding, set, layout, shader, stage
+            ),
+            Self::DescriptorCountMismatch {
+                stage,
+                set,
+                binding,
+                layout,
+                shader,
+            } => write!(
+                fmt,
+                "Binding {} in set {} has {} descriptors in layout but {} in {} shader",
+                binding, set, layout, shader, stage
+            ),
+            Self::PushConstantsNotCovered {
+                stage,
+                offset,
+                size,
+            } => write!(
+                fmt,
+                "Push constants {}..{} used by {} shader are not covered by layout",
+                offset,
+                offset + size,
+                stage
+            ),
+            Self::MissingVertexAttribute { location } => write!(
+                fmt,
+                "Vertex shader input at location {} has no vertex attribute",
+                location
+            ),
+            Self::VertexAttributeTypeMismatch {
+                location,
+                attribute,
+                shader,
+            } => write!(
+                fmt,
+                "Vertex attribute at location {} is {:?} but vertex shader expects {:?}",
+                location, attribute, shader
+            ),
+            Self::MissingColorAttachment { location } => write!(
+                fmt,
+                "Fragment shader output at location {} has no color attachment",
+                location
+            ),
+            Self::ColorAttachmentTypeMismatch {
+                location,
+                attachment,
+                shader,
+            } => write!(
+                fmt,
+                "Color attachment at location {} is {:?} but fragment shader writes {:?}",
+                location, attachment, shader
+            ),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ValidateLayoutError {
+    #[error("Failed to reflect shader")]
+    ReflectError {
+        #[from]
+        source: ReflectError,
+    },
+
+    #[error("Pipeline layout does not match shaders: {}", display_mismatches(.mismatches))]
+    Mismatch { mismatches: Vec<LayoutMismatch> },
+}
+
+fn display_mismatches(mismatches: &[LayoutMismatch]) -> String {
+    mismatches
+        .iter()
+        .map(|mismatch| mismatch.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl ShaderModuleInfo {
+    /// Reflects interface of all entry points in the shader module.
+    ///
+    /// This function runs entirely on CPU.
+    pub fn reflect(&self) -> Result<ShaderReflection, ReflectError> {
+        let module = match self.language {
+            ShaderLanguage::SPIRV => naga::front::spv::parse_u8_slice(
+                &self.code,
+                &naga::front::spv::Options {
+                    adjust_coordinate_space: false,
+                    strict_capabilities: false,
+                    block_ctx_dump_prefix: None,
+                },
+            )?,
+
+            #[cfg(feature = "glsl")]
+            ShaderLanguage::GLSL { stage } => {
+                let naga_stage = crate::shader::naga_glsl_stage(stage)
+                    .ok_or(ReflectError::UnsupportedGlslStage { stage })?;
+
+                let code = std::str::from_utf8(&self.code)?;
+                naga::front::glsl::Parser::default()
+                    .parse(
+                        &naga::front::glsl::Options {
+                            stage: naga_stage,
+                            defines: self.naga_defines(),
+                        },
+                        code,
+                    )
+                    .map_err(|errors| ReflectError::NagaGlslParseError { errors })?
+            }
+
+            #[cfg(feature = "wgsl")]
+            ShaderLanguage::WGSL => {
+                let code = std::str::from_utf8(&self.code)?;
+                naga::front::wgsl::parse_str(code).map_err(|err| {
+                    ReflectError::NagaWgslParseError {
+                        source: Box::from(err.emit_to_string(".")),
+                    }
+                })?
+            }
+
+            #[allow(unreachable_patterns)]
+            language => return Err(ReflectError::UnsupportedShaderLanguage { language }),
+        };
+
+        let info = naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        )
+        .validate(&module)?;
+
+        let entry_points = module
+            .entry_points
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| reflect_entry_point(&module, info.get_entry_point(index), entry))
+            .collect();
+
+        Ok(ShaderReflection { entry_points })
+    }
+}
+
+impl PipelineLayoutInfo {
+    /// Checks that descriptor bindings and push constants used by shaders
+    /// are present in this layout with compatible types
+    /// and visible in corresponding stages.
+    ///
+    /// Dynamic buffers in layout are compatible with non-dynamic buffers in shader
+    /// and combined image samplers are compatible with both images and samplers.
+    pub fn validate_against(&self, shaders: &[Shader]) -> Result<(), ValidateLayoutError> {
+        let mut mismatches = Vec::new();
+
+        for shader in shaders {
+            if let Some(entry) = reflect_shader(shader, &mut mismatches)? {
+                self.check_entry_point(&entry, &mut mismatches);
+            }
+        }
+
+        mismatches_to_result(mismatches)
+    }
+
+    fn check_entry_point(
+        &self,
+        entry: &EntryPointReflection,
+        mismatches: &mut Vec<LayoutMismatch>,
+    ) {
+        let sets = self.sets.iter().map(|set| set.info()).collect::<Vec<_>>();
+        check_layout(&sets, &self.push_constants, entry, mismatches);
+    }
+}
+
+impl GraphicsPipelineInfo {
+    /// Checks pipeline layout against vertex and fragment shaders
+    /// as [`PipelineLayoutInfo::validate_against`] does.
+    ///
+    /// Additionally checks that every vertex shader input has vertex attribute
+    /// and every fragment shader output has color attachment
+    /// with format of the same numeric type.
+    pub fn validate_interface(&self) -> Result<(), ValidateLayoutError> {
+        let layout = self.desc.layout.info();
+        let mut mismatches = Vec::new();
+
+        let vertex = Shader::from(self.desc.vertex_shader.clone());
+        if let Some(entry) = reflect_shader(&vertex, &mut mismatches)? {
+            layout.check_entry_point(&entry, &mut mismatches);
+            check_vertex_inputs(&self.desc.vertex_attributes, &entry, &mut mismatches);
+        }
+
+        let fragment = self
+            .desc
+            .rasterizer
+            .as_ref()
+            .and_then(|rasterizer| rasterizer.fragment_shader.clone());
+
+        if let Some(fragment) = fragment {
+            let fragment = Shader::from(fragment);
+            if let Some(entry) = reflect_shader(&fragment, &mut mismatches)? {
+                layout.check_entry_point(&entry, &mut mismatches);
+                check_fragment_outputs(&self.color_formats(), &entry, &mut mismatches);
+            }
+        }
+
+        mismatches_to_result(mismatches)
+    }
+
+    fn color_formats(&self) -> Vec<Format> {
+        match &self.rendering {
+            GraphicsPipelineRenderingInfo::RenderPass {
+                render_pass,
+                subpass,
+            } => {
+                let info = render_pass.info();
+                info.subpasses
+                    .get(*subpass as usize)
+                    .map(|subpass| {
+                        subpass
+                            .colors
+                            .iter()
+                            .filter_map(|&(index, _)| info.attachments.get(index as usize))
+                            .map(|attachment| attachment.format)
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+            GraphicsPipelineRenderingInfo::DynamicRendering { colors, .. } => colors.clone(),
+        }
+    }
+}
+
+/// Reflects entry point of the shader.
+/// Returns `None` and records mismatch if module has no such entry point.
+fn reflect_shader(
+    shader: &Shader,
+    mismatches: &mut Vec<LayoutMismatch>,
+) -> Result<Option<EntryPointReflection>, ReflectError> {
+    let mut reflection = shader.module.info().reflect()?;
+
+    let index = reflection
+        .entry_points
+        .iter()
+        .position(|entry| entry.name == shader.entry && entry.stage == shader.stage);
+
+    match index {
+        Some(index) => Ok(Some(reflection.entry_points.swap_remove(index))),
+        None => {
+            mismatches.push(LayoutMismatch::EntryPointNotFound {
+                entry: shader.entry.clone().into_owned(),
+                stage: shader.stage,
+            });
+            Ok(None)
+        }
+    }
+}
+
+fn mismatches_to_result(mismatches: Vec<LayoutMismatch>) -> Result<(), ValidateLayoutError> {
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidateLayoutError::Mismatch { mismatches })
+    }
+}
+
+fn check_layout(
+    sets: &[&DescriptorSetLayoutInfo],
+    push_constants: &[PushConstant],
+    entry: &EntryPointReflection,
+    mismatches: &mut Vec<LayoutMismatch>,
+) {
+    for reflected in &entry.bindings {
+        let binding = sets.get(reflected.set as usize).and_then(|set| {
+            set.bindings
+                .iter()
+                .find(|binding| binding.binding == reflected.binding)
+        });
+
+        let binding = match binding {
+            Some(binding) => binding,
+            None => {
+                mismatches.push(LayoutMismatch::MissingBinding {
+                    stage: entry.stage,
+                    set: reflected.set,
+                    binding: reflected.binding,
+                });
+                continue;
+            }
+        };
+
+        if !binding.stages.contains(entry.stage.flag()) {
+            mismatches.push(LayoutMismatch::BindingNotVisible {
+                stage: entry.stage,
+                set: reflected.set,
+                binding: reflected.binding,
+            });
+        }
+
+        if !descriptor_type_compatible(binding.ty, reflected.ty) {
+            mismatches.push(LayoutMismatch::DescriptorTypeMismatch {
+                stage: entry.stage,
+                set: reflected.set,
+                binding: reflected.binding,
+                layout: binding.ty,
+                shader: reflected.ty,
+            });
+        }
+
+        if binding.count < reflected.count {
+            mismatches.push(LayoutMismatch::DescriptorCountMismatch {
+                stage: entry.stage,
+                set: reflected.set,
+                binding: reflected.binding,
+                layout: binding.count,
+                shader: reflected.count,
+            });
+        }
+    }
+
+    if let Some(push) = entry.push_constants {
+        if !push_constants_cover(push_constants, entry.stage, push) {
+            mismatches.push(LayoutMismatch::PushConstantsNotCovered {
+                stage: entry.stage,
+                offset: push.offset,
+                size: push.size,
+            });
+        }
+    }
+}
+
+fn check_vertex_inputs(
+    attributes: &[VertexInputAttribute],
+    entry: &EntryPointReflection,
+    mismatches: &mut Vec<LayoutMismatch>,
+) {
+    for input in &entry.inputs {
+        let attribute = attributes
+            .iter()
+            .find(|attribute| attribute.location == input.location);
+
+        match (attribute, input.format) {
+            (None, _) => mismatches.push(LayoutMismatch::MissingVertexAttribute {
+                location: input.location,
+            }),
+            (Some(attribute), Some(shader)) if !numeric_type_matches(attribute.format, shader) => {
+                mismatches.push(LayoutMismatch::VertexAttributeTypeMismatch {
+                    location: input.location,
+                    attribute: attribute.format,
+                    shader,
+                })
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_fragment_outputs(
+    colors: &[Format],
+    entry: &EntryPointReflection,
+    mismatches: &mut Vec<LayoutMismatch>,
+) {
+    for output in &entry.outputs {
+        match (colors.get(output.location as usize), output.format) {
+            (None, _) => mismatches.push(LayoutMismatch::MissingColorAttachment {
+                location: output.location,
+            }),
+            (Some(&attachment), Some(shader)) if !numeric_type_matches(attachment, shader) => {
+                mismatches.push(LayoutMismatch::ColorAttachmentTypeMismatch {
+                    location: output.location,
+                    attachment,
+                    shader,
+                })
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Checks that values of both formats are read in shader
+/// as the same numeric type: float, signed or unsigned integer.
+/// Component count may differ.
+fn numeric_type_matches(a: Format, b: Format) -> bool {
+    fn numeric_type(format: Format) -> Type {
+        match format.description().ty {
+            Type::Uint => Type::Uint,
+            Type::Sint => Type::Sint,
+            _ => Type::Sfloat,
+        }
+    }
+
+    numeric_type(a) == numeric_type(b)
+}
+
+fn descriptor_type_compatible(layout: DescriptorType, shader: DescriptorType) -> bool {
+    match (layout, shader) {
+        (DescriptorType::UniformBufferDynamic, DescriptorType::UniformBuffer) => true,
+        (DescriptorType::StorageBufferDynamic, DescriptorType::StorageBuffer) => true,
+        (
+            DescriptorType::CombinedImageSampler,
+            DescriptorType::SampledImage | DescriptorType::Sampler,
+        ) => true,
+        (layout, shader) => layout == shader,
+    }
+}
+
+fn push_constants_cover(
+    ranges: &[PushConstant],
+    stage: ShaderStage,
+    push: ReflectedPushConstants,
+) -> bool {
+    let mut ranges = ranges
+        .iter()
+        .filter(|range| range.stages.contains(stage.flag()))
+        .collect::<Vec<_>>();
+    ranges.sort_by_key(|range| range.offset);
+
+    let end = push.offset + push.size;
+    let mut covered = push.offset;
+    for range in ranges {
+        if range.offset > covered {
+            break;
+        }
+        covered = covered.max(range.offset + range.size);
+        if covered >= end {
+            break;
+        }
+    }
+    covered >= end
+}
+
+fn reflect_entry_point(
+    module: &naga::Module,
+    info: &naga::valid::FunctionInfo,
+    entry: &naga::EntryPoint,
+) -> EntryPointReflection {
+    let mut bindings = Vec::new();
+    let mut push_constants = None;
+
+    for (handle, var) in module.global_variables.iter() {
+        if info[handle].is_empty() {
+            continue;
+        }
+
+        match var.space {
+            naga::AddressSpace::PushConstant => {
+                push_constants = Some(reflect_push_constants(module, var.ty));
+            }
+            space => {
+                if let Some(binding) = &var.binding {
+                    if let Some((ty, count)) = reflect_descriptor(module, space, var.ty) {
+                        bindings.push(ReflectedBinding {
+                            set: binding.group,
+                            binding: binding.binding,
+                            ty,
+                            count,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    bindings.sort_by_key(|binding| (binding.set, binding.binding));
+
+    let mut inputs = Vec::new();
+    for arg in &entry.function.arguments {
+        collect_locations(module, arg.ty, arg.binding.as_ref(), &mut inputs);
+    }
+    inputs.sort_by_key(|input| input.location);
+
+    let mut outputs = Vec::new();
+    if let Some(result) = &entry.function.result {
+        collect_locations(module, result.ty, result.binding.as_ref(), &mut outputs);
+    }
+    outputs.sort_by_key(|output| output.location);
+
+    EntryPointReflection {
+        name: entry.name.clone(),
+        stage: match entry.stage {
+            naga::ShaderStage::Vertex => ShaderStage::Vertex,
+            naga::ShaderStage::Fragment => ShaderStage::Fragment,
+            naga::ShaderStage::Compute => ShaderStage::Compute,
+        },
+        bindings,
+        push_constants,
+        inputs,
+        outputs,
+    }
+}
+
+fn reflect_descriptor(
+    module: &naga::Module,
+    space: naga::AddressSpace,
+    ty: naga::Handle<naga::Type>,
+) -> Option<(DescriptorType, u32)> {
+    let (ty, count) = match module.types[ty].inner {
+        naga::TypeInner::BindingArray { base, size } => {
+            let count = match size {
+                naga::ArraySize::Constant(constant) => constant_u32(module, constant)?,
+                naga::ArraySize::Dynamic => 0,
+            };
+            (base, count)
+        }
+        _ => (ty, 1),
+    };
+
+    let ty = match space {
+        naga::AddressSpace::Uniform => DescriptorType::UniformBuffer,
+        naga::AddressSpace::Storage { .. } => DescriptorType::StorageBuffer,
+        naga::AddressSpace::Handle => match module.types[ty].inner {
+            naga::TypeInner::Sampler { .. } => DescriptorType::Sampler,
+            naga::TypeInner::Image {
+                class: naga::ImageClass::Storage { .. },
+                ..
+            } => DescriptorType::StorageImage,
+            naga::TypeInner::Image { .. } => DescriptorType::SampledImage,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    Some((ty, count))
+}
+
+fn reflect_push_constants(
+    module: &naga::Module,
+    ty: naga::Handle<naga::Type>,
+) -> ReflectedPushConstants {
+    let inner = &module.types[ty].inner;
+    match inner {
+        naga::TypeInner::Struct { members, span } if !members.is_empty() => {
+            let offset = members.iter().map(|m| m.offset).min().unwrap_or(0);
+            ReflectedPushConstants {
+                offset,
+                size: span - offset,
+            }
+        }
+        _ => ReflectedPushConstants {
+            offset: 0,
+            size: inner.size(&module.constants),
+        },
+    }
+}
+
+fn collect_locations(
+    module: &naga::Module,
+    ty: naga::Handle<naga::Type>,
+    binding: Option<&naga::Binding>,
+    locations: &mut Vec<ReflectedLocation>,
+) {
+    match binding {
+        Some(naga::Binding::Location { location, .. }) => {
+            locations.push(ReflectedLocation {
+                location: *location,
+                format: location_format(&module.types[ty].inner),
+            });
+        }
+        Some(naga::Binding::BuiltIn(_)) => {}
+        None => {
+            if let naga::TypeInner::Struct { members, .. } = &module.types[ty].inner {
+                for member in members {
+                    collect_locations(module, member.ty, member.binding.as_ref(), locations);
+                }
+            }
+        }
+    }
+}
+
+fn location_format(inner: &naga::TypeInner) -> Option<Format> {
+    let (size, kind, width) = match *inner {
+        naga::TypeInner::Scalar { kind, width } => (1, kind, width),
+        naga::TypeInner::Vector { size, kind, width } => (size as u8, kind, width),
+        _ => return None,
+    };
+
+    if width != 4 {
+        return None;
+    }
+
+    let format = match (kind, size) {
+        (naga::ScalarKind::Float, 1) => Format::R32Sfloat,
+        (naga::ScalarKind::Float, 2) => Format::RG32Sfloat,
+        (naga::ScalarKind::Float, 3) => Format::RGB32Sfloat,
+        (naga::ScalarKind::Float, 4) => Format::RGBA32Sfloat,
+        (naga::ScalarKind::Sint, 1) => Format::R32Sint,
+        (naga::ScalarKind::Sint, 2) => Format::RG32Sint,
+        (naga::ScalarKind::Sint, 3) => Format::RGB32Sint,
+        (naga::ScalarKind::Sint, 4) => Format::RGBA32Sint,
+        (naga::ScalarKind::Uint, 1) => Format::R32Uint,
+        (naga::ScalarKind::Uint, 2) => Format::RG32Uint,
+        (naga::ScalarKind::Uint, 3) => Format::RGB32Uint,
+        (naga::ScalarKind::Uint, 4) => Format::RGBA32Uint,
+        _ => return None,
+    };
+    Some(format)
+}
+
+fn constant_u32(module: &naga::Module, constant: naga::Handle<naga::Constant>) -> Option<u32> {
+    match module.constants[constant].inner {
+        naga::ConstantInner::Scalar {
+            value: naga::ScalarValue::Uint(value),
+            ..
+        } => u32::try_from(value).ok(),
+        naga::ConstantInner::Scalar {
+            value: naga::ScalarValue::Sint(value),
+            ..
+        } => u32::try_from(value).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        descriptor::{
+            DescriptorBindingFlags, DescriptorSetLayoutBinding, DescriptorSetLayoutFlags,
+        },
+        shader::ShaderStageFlags,
+    };
+
+    fn set(bindings: &[(u32, DescriptorType, ShaderStageFlags)]) -> DescriptorSetLayoutInfo {
+        DescriptorSetLayoutInfo {
+            bindings: bindings
+                .iter()
+                .map(|&(binding, ty, stages)| DescriptorSetLayoutBinding {
+                    binding,
+                    ty,
+                    count: 1,
+                    stages,
+                    flags: DescriptorBindingFlags::empty(),
+                })
+                .collect(),
+            flags: DescriptorSetLayoutFlags::empty(),
+        }
+    }
+
+    fn entry(bindings: &[(u32, u32, DescriptorType)]) -> EntryPointReflection {
+        EntryPointReflection {
+            name: "main".to_owned(),
+            stage: ShaderStage::Fragment,
+            bindings: bindings
+                .iter()
+                .map(|&(set, binding, ty)| ReflectedBinding {
+                    set,
+                    binding,
+                    ty,
+                    count: 1,
+                })
+                .collect(),
+            push_constants: None,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    fn location(location: u32, format: Format) -> ReflectedLocation {
+        ReflectedLocation {
+            location,
+            format: Some(format),
+        }
+    }
+
+    fn push(stages: ShaderStageFlags, offset: u32, size: u32) -> PushConstant {
+        PushConstant {
+            stages,
+            offset,
+            size,
+        }
+    }
+
+    #[cfg(feature = "glsl")]
+    #[test]
+    fn reflect_glsl_vertex() {
+        let code = r#"
+            #version 450
+            layout(location = 0) in vec3 position;
+            layout(location = 2) in ivec2 index;
+            layout(location = 0) out vec2 uv;
+            layout(set = 0, binding = 1) uniform Globals { mat4 transform; };
+            layout(push_constant) uniform Push { vec4 scale; } push;
+
+            void main() {
+                uv = vec2(index);
+                gl_Position = transform * vec4(position, 1.0) * push.scale;
+            }
+        "#;
+
+        let reflection = ShaderModuleInfo::glsl(code.as_bytes(), ShaderStage::Vertex)
+            .reflect()
+            .unwrap();
+        let entry = reflection.entry_point("main", ShaderStage::Vertex).unwrap();
+
+        assert_eq!(
+            entry.bindings,
+            [ReflectedBinding {
+                set: 0,
+                binding: 1,
+                ty: DescriptorType::UniformBuffer,
+                count: 1,
+            }]
+        );
+        assert_eq!(
+            entry.push_constants,
+            Some(ReflectedPushConstants {
+                offset: 0,
+                size: 16
+            })
+        );
+        assert_eq!(
+            entry.inputs,
+            [
+                location(0, Format::RGB32Sfloat),
+                location(2, Format::RG32Sint)
+            ]
+        );
+        assert_eq!(entry.outputs, [location(0, Format::RG32Sfloat)]);
+    }
+
+    #[cfg(feature = "wgsl")]
+    #[test]
+    fn reflect_wgsl_fragment() {
+        let code = r#"
+            @group(1) @binding(0) var color_texture: texture_2d<f32>;
+            @group(1) @binding(1) var color_sampler: sampler;
+            @group(1) @binding(2) var<storage, read> unused: array<u32>;
+
+            struct Output {
+                @location(0) color: vec4<f32>,
+                @location(1) id: u32,
+            }
+
+            @fragment
+            fn fs_main(@location(0) uv: vec2<f32>) -> Output {
+                return Output(textureSample(color_texture, color_sampler, uv), 1u);
+            }
+        "#;
+
+        let reflection = ShaderModuleInfo::wgsl(code.as_bytes()).reflect().unwrap();
+        assert!(reflection
+            .entry_point("fs_main", ShaderStage::Vertex)
+            .is_none());
+
+        let entry = reflection
+            .entry_point("fs_main", ShaderStage::Fragment)
+            .unwrap();
+
+        // Statically unused storage buffer is not reported.
+        assert_eq!(
+            entry.bindings,
+            [
+                ReflectedBinding {
+                    set: 1,
+                    binding: 0,
+                    ty: DescriptorType::SampledImage,
+                    count: 1,
+                },
+                ReflectedBinding {
+                    set: 1,
+                    binding: 1,
+                    ty: DescriptorType::Sampler,
+                    count: 1,
+                },
+            ]
+        );
+        assert_eq!(entry.push_constants, None);
+        assert_eq!(entry.inputs, [location(0, Format::RG32Sfloat)]);
+        assert_eq!(
+            entry.outputs,
+            [
+                location(0, Format::RGBA32Sfloat),
+                location(1, Format::R32Uint)
+            ]
+        );
+    }
+
+    #[test]
+    fn layout_matches() {
+        let set0 = set(&[
+            (
+                0,
+                DescriptorType::UniformBufferDynamic,
+                ShaderStageFlags::VERTEX | ShaderStageFlags::FRAGMENT,
+            ),
+            (
+                1,
+                DescriptorType::CombinedImageSampler,
+                ShaderStageFlags::FRAGMENT,
+            ),
+        ]);
+
+        let mut mismatches = Vec::new();
+        check_layout(
+            &[&set0],
+            &[],
+            &entry(&[
+                (0, 0, DescriptorType::UniformBuffer),
+                (0, 1, DescriptorType::SampledImage),
+                (0, 1, DescriptorType::Sampler),
+            ]),
+            &mut mismatches,
+        );
+        assert_eq!(mismatches, []);
+    }
+
+    #[test]
+    fn layout_missing_binding() {
+        let set0 = set(&[(0, DescriptorType::UniformBuffer, ShaderStageFlags::FRAGMENT)]);
+
+        let mut mismatches = Vec::new();
+        check_layout(
+            &[&set0],
+            &[],
+            &entry(&[
+                (0, 1, DescriptorType::UniformBuffer),
+                (1, 0, DescriptorType::UniformBuffer),
+            ]),
+            &mut mismatches,
+        );
+        assert_eq!(
+            mismatches,
+            [
+                LayoutMismatch::MissingBinding {
+                    stage: ShaderStage::Fragment,
+                    set: 0,
+                    binding: 1,
+                },
+                LayoutMismatch::MissingBinding {
+                    stage: ShaderStage::Fragment,
+                    set: 1,
+                    binding: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn layout_wrong_descriptor_type() {
+        let set0 = set(&[(0, DescriptorType::StorageBuffer, ShaderStageFlags::FRAGMENT)]);
+
+        let mut mismatches = Vec::new();
+        check_layout(
+            &[&set0],
+            &[],
+            &entry(&[(0, 0, DescriptorType::UniformBuffer)]),
+            &mut mismatches,
+        );
+        assert_eq!(
+            mismatches,
+            [LayoutMismatch::DescriptorTypeMismatch {
+                stage: ShaderStage::Fragment,
+                set: 0,
+                binding: 0,
+                layout: DescriptorType::StorageBuffer,
+                shader: DescriptorType::UniformBuffer,
+            }]
+        );
+    }
+
+    #[test]
+    fn layout_binding_not_visible() {
+        let set0 = set(&[(0, DescriptorType::UniformBuffer, ShaderStageFlags::VERTEX)]);
+
+        let mut mismatches = Vec::new();
+        check_layout(
+            &[&set0],
+            &[],
+            &entry(&[(0, 0, DescriptorType::UniformBuffer)]),
+            &mut mismatches,
+        );
+        assert_eq!(
+            mismatches,
+            [LayoutMismatch::BindingNotVisible {
+                stage: ShaderStage::Fragment,
+                set: 0,
+                binding: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn push_constants_split_ranges() {
+        let used = ReflectedPushConstants {
+            offset: 4,
+            size: 24,
+        };
+        let vertex = ShaderStageFlags::VERTEX;
+        let fragment = ShaderStageFlags::FRAGMENT;
+
+        // Adjacent ranges cover together.
+        assert!(push_constants_cover(
+            &[push(vertex, 16, 16), push(vertex, 0, 16)],
+            ShaderStage::Vertex,
+            used,
+        ));
+
+        // Overlapping ranges cover together.
+        assert!(push_constants_cover(
+            &[push(vertex, 0, 20), push(vertex | fragment, 12, 20)],
+            ShaderStage::Vertex,
+            used,
+        ));
+
+        // Gap between ranges.
+        assert!(!push_constants_cover(
+            &[push(vertex, 0, 12), push(vertex, 16, 16)],
+            ShaderStage::Vertex,
+            used,
+        ));
+
+        // Range for other stage does not count.
+        assert!(!push_constants_cover(
+            &[push(vertex, 0, 16), push(fragment, 16, 16)],
+            ShaderStage::Vertex,
+            used,
+        ));
+    }
+
+    #[test]
+    fn vertex_inputs() {
+        let attribute = |location, format| VertexInputAttribute {
+            location,
+            format,
+            binding: 0,
+            offset: 0,
+        };
+
+        let mut entry = entry(&[]);
+        entry.stage = ShaderStage::Vertex;
+        entry.inputs = vec![
+            location(0, Format::RGB32Sfloat),
+            location(1, Format::RGBA32Sfloat),
+            location(2, Format::R32Uint),
+            location(3, Format::RG32Sint),
+        ];
+
+        let mut mismatches = Vec::new();
+        check_vertex_inputs(
+            &[
+                attribute(0, Format::RGB32Sfloat),
+                // Normalized formats are read as floats.
+                attribute(1, Format::RGBA8Unorm),
+                attribute(2, Format::R32Sint),
+            ],
+            &entry,
+            &mut mismatches,
+        );
+        assert_eq!(
+            mismatches,
+            [
+                LayoutMismatch::VertexAttributeTypeMismatch {
+                    location: 2,
+                    attribute: Format::R32Sint,
+                    shader: Format::R32Uint,
+                },
+                LayoutMismatch::MissingVertexAttribute { location: 3 },
+            ]
+        );
+    }
+
+    #[test]
+    fn fragment_outputs() {
+        let mut entry = entry(&[]);
+        entry.outputs = vec![
+            location(0, Format::RGBA32Sfloat),
+            location(1, Format::RGBA32Sfloat),
+            location(2, Format::RGBA32Sfloat),
+        ];
+
+        let mut mismatches = Vec::new();
+        check_fragment_outputs(
+            &[Format::BGRA8Srgb, Format::R32Uint],
+            &entry,
+            &mut mismatches,
+        );
+        assert_eq!(
+            mismatches,
+            [
+                LayoutMismatch::ColorAttachmentTypeMismatch {
+                    location: 1,
+                    attachment: Format::R32Uint,
+                    shader: Format::RGBA32Sfloat,
+                },
+                LayoutMismatch::MissingColorAttachment { location: 2 },
+            ]
+        );
+    }
+}
@@ -137,6 +137,77 @@ pub struct Subpass {
         serde(skip_serializing_if = "Option::is_none", default)
     )]
     pub depth: Option<(u32, Layout)>,
+
+    /// Indices of attachments that are used as input attachments in this
+    /// subpass.
+    #[cfg_attr(
+        feature = "serde-1",
+        serde(skip_serializing_if = "Vec::is_empty", default)
+    )]
+    pub inputs: Vec<(u32, Layout)>,
+
+    /// Indices of attachments to which color attachments are resolved at the
+    /// end of this subpass.
+    ///
+    /// Must be either empty or have same length as `colors`.
+    /// `None` means that corresponding color attachment is not resolved.
+    #[cfg_attr(
+        feature = "serde-1",
+        serde(skip_serializing_if = "Vec::is_empty", default)
+    )]
+    pub resolves: Vec<Option<(u32, Layout)>>,
+
+    /// Attachment to which depth-stencil attachment is resolved at the end of
+    /// this subpass.
+    #[cfg_attr(
+        feature = "serde-1",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub depth_resolve: Option<DepthStencilResolve>,
+}
+
+/// Defines how multisampled depth-stencil attachment is resolved.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct DepthStencilResolve {
+    /// Index of an attachment that is used as depth-stencil resolve target.
+    pub attachment: u32,
+
+    /// Layout of the resolve attachment during subpass.
+    pub layout: Layout,
+
+    /// Resolve mode for depth aspect.
+    /// `None` means that depth aspect is not resolved.
+    #[cfg_attr(
+        feature = "serde-1",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub depth_mode: Option<ResolveMode>,
+
+    /// Resolve mode for stencil aspect.
+    /// `None` means that stencil aspect is not resolved.
+    #[cfg_attr(
+        feature = "serde-1",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub stencil_mode: Option<ResolveMode>,
+}
+
+/// Specifies how samples are combined when attachment is resolved.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResolveMode {
+    /// Value of sample zero is written to resolve attachment.
+    SampleZero,
+
+    /// Average of all samples is written to resolve attachment.
+    Average,
+
+    /// Minimum of all samples is written to resolve attachment.
+    Min,
+
+    /// Maximum of all samples is written to resolve attachment.
+    Max,
 }
 
 /// Defines memory dependency between two subpasses
@@ -248,6 +319,39 @@ pub enum CreateRenderPassError {
         "Subpass {subpass} attachment index {attachment} for depth attachment is out of bounds"
     )]
     DepthAttachmentReferenceOutOfBound { subpass: usize, attachment: u32 },
+
+    #[error(
+        "Subpass {subpass} attachment index {attachment} for input attachment {index} is out of bounds"
+    )]
+    InputAttachmentReferenceOutOfBound {
+        subpass: usize,
+        index: usize,
+        attachment: u32,
+    },
+
+    #[error(
+        "Subpass {subpass} attachment index {attachment} for resolve attachment {index} is out of bounds"
+    )]
+    ResolveAttachmentReferenceOutOfBound {
+        subpass: usize,
+        index: usize,
+        attachment: u32,
+    },
+
+    #[error("Subpass {subpass} has {resolves} resolve attachments for {colors} color attachments")]
+    ResolveAttachmentCountMismatch {
+        subpass: usize,
+        colors: usize,
+        resolves: usize,
+    },
+
+    #[error(
+        "Subpass {subpass} attachment index {attachment} for depth resolve attachment is out of bounds"
+    )]
+    DepthResolveAttachmentReferenceOutOfBound { subpass: usize, attachment: u32 },
+
+    #[error("Subpass {subpass} uses depth-stencil resolve which requires Vulkan 1.2")]
+    DepthResolveNotSupported { subpass: usize },
 }
 
 pub trait RenderPassInstance {
@@ -1,53 +1,4 @@
-use super::{pad::Padded, ShaderRepr, Std140, Std430};
-
-// macro_rules! impl_unsafe_marker_for_array {
-//     ($( $n:expr ),*) => {
-//         $(
-//             impl<T> ShaderRepr<Std140> for [T; $n]
-//             where
-//                 T: ShaderRepr<Std140>,
-//             {
-//                 const ALIGN_MASK: usize = T::ALIGN_MASK | 15;
-//                 const ARRAY_PADDING: usize = 0;
-
-//                 type Type = [Padded<T::Type, T::ArrayPadding>; $n];
-//                 type ArrayPadding = [u8; 0];
-
-//                 fn copy_to_repr(&self, repr: &mut [Padded<T::Type, T::ArrayPadding>; $n]) {
-//                     #![allow(clippy::reversed_empty_ranges)]
-
-//                     for i in 0..$n {
-//                         self[i].copy_to_repr(&mut repr[i].value);
-//                     }
-//                 }
-//             }
-
-//             impl<T> ShaderRepr<Std430> for [T; $n]
-//             where
-//                 T: ShaderRepr<Std430>,
-//             {
-//                 const ALIGN_MASK: usize = T::ALIGN_MASK;
-//                 const ARRAY_PADDING: usize = 0;
-
-//                 type Type = [Padded<T::Type, T::ArrayPadding>; $n];
-//                 type ArrayPadding = [u8; 0];
-
-//                 fn copy_to_repr(&self, repr: &mut [Padded<T::Type, T::ArrayPadding>; $n]) {
-//                     #![allow(clippy::reversed_empty_ranges)]
-
-//                     for i in 0..$n {
-//                         self[i].copy_to_repr(&mut repr[i].value);
-//                     }
-//                 }
-//             }
-//         )*
-//     }
-// }
-
-// impl_unsafe_marker_for_array!(
-//     0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
-//     26, 27, 28, 29, 30, 31, 32, 48, 64, 96, 128, 256, 512, 1024, 2048, 4096
-// );
+use super::{pad::Padded, Scalar, ShaderRepr, Std140, Std430};
 
 impl<T, const N: usize> ShaderRepr<Std140> for [T; N]
 where
@@ -86,3 +37,141 @@ where
         }
     }
 }
+
+impl<T, const N: usize> ShaderRepr<Scalar> for [T; N]
+where
+    T: ShaderRepr<Scalar>,
+{
+    const ALIGN_MASK: usize = T::ALIGN_MASK;
+    const ARRAY_PADDING: usize = 0;
+
+    type Type = [Padded<T::Type, T::ArrayPadding>; N];
+    type ArrayPadding = [u8; 0];
+
+    fn copy_to_repr(&self, repr: &mut [Padded<T::Type, T::ArrayPadding>; N]) {
+        #![allow(clippy::reversed_empty_ranges)]
+
+        for i in 0..N {
+            self[i].copy_to_repr(&mut repr[i].value);
+        }
+    }
+}
+
+// Compile-time checks of array layouts against offsets
+// GLSL compilers produce for equivalent blocks.
+#[allow(dead_code)]
+const _: () = {
+    use {
+        super::{mat3, vec2, vec3, vec4},
+        core::mem::{offset_of, size_of},
+    };
+
+    #[derive(sierra_proc::ShaderRepr)]
+    struct Light {
+        position: vec3,
+        intensity: f32,
+        color: vec4,
+    }
+
+    // layout(std140) uniform Lights {
+    //     float a;
+    //     float b[3];
+    //     vec2 c;
+    //     Light lights[4];
+    //     float d[2][3];
+    //     mat3 m[2];
+    //     float e;
+    // };
+    #[derive(sierra_proc::ShaderRepr)]
+    #[sierra(std140)]
+    struct Lights {
+        a: f32,
+        b: [f32; 3],
+        c: vec2,
+        lights: [Light; 4],
+        d: [[f32; 3]; 2],
+        m: [mat3; 2],
+        e: f32,
+    }
+
+    assert!(size_of::<<Light as ShaderRepr<Std140>>::Type>() == 32);
+    assert!(size_of::<<[f32; 3] as ShaderRepr<Std140>>::Type>() == 48);
+    assert!(size_of::<<[vec3; 2] as ShaderRepr<Std140>>::Type>() == 32);
+    assert!(size_of::<<[[f32; 3]; 2] as ShaderRepr<Std140>>::Type>() == 96);
+
+    assert!(offset_of!(LightsReprStd140, val_a) == 0);
+    assert!(offset_of!(LightsReprStd140, val_b) == 16);
+    assert!(offset_of!(LightsReprStd140, val_c) == 64);
+    assert!(offset_of!(LightsReprStd140, val_lights) == 80);
+    assert!(offset_of!(LightsReprStd140, val_d) == 208);
+    assert!(offset_of!(LightsReprStd140, val_m) == 304);
+    assert!(offset_of!(LightsReprStd140, val_e) == 400);
+    assert!(size_of::<LightsReprStd140>() == 416);
+
+    #[derive(sierra_proc::ShaderRepr)]
+    #[sierra(std430)]
+    struct Single {
+        x: f32,
+    }
+
+    // layout(std430) buffer Particles {
+    //     float a;
+    //     float b[3];
+    //     vec3 c[2];
+    //     Single s[3];
+    //     vec2 d[2][2];
+    //     float e;
+    // };
+    #[derive(sierra_proc::ShaderRepr)]
+    #[sierra(std430)]
+    struct Particles {
+        a: f32,
+        b: [f32; 3],
+        c: [vec3; 2],
+        s: [Single; 3],
+        d: [[vec2; 2]; 2],
+        e: f32,
+    }
+
+    assert!(size_of::<<Single as ShaderRepr<Std430>>::Type>() == 4);
+    assert!(size_of::<<[f32; 3] as ShaderRepr<Std430>>::Type>() == 12);
+    assert!(size_of::<<[vec3; 2] as ShaderRepr<Std430>>::Type>() == 32);
+
+    assert!(offset_of!(ParticlesReprStd430, val_a) == 0);
+    assert!(offset_of!(ParticlesReprStd430, val_b) == 4);
+    assert!(offset_of!(ParticlesReprStd430, val_c) == 16);
+    assert!(offset_of!(ParticlesReprStd430, val_s) == 48);
+    assert!(offset_of!(ParticlesReprStd430, val_d) == 64);
+    assert!(offset_of!(ParticlesReprStd430, val_e) == 96);
+    assert!(size_of::<ParticlesReprStd430>() == 112);
+
+    // layout(scalar) buffer Vertex {
+    //     vec3 position;
+    //     float u;
+    //     vec2 uv[2];
+    //     mat3 m;
+    //     double d;
+    //     vec3 n[2];
+    // };
+    #[derive(sierra_proc::ShaderRepr)]
+    #[sierra(scalar)]
+    struct Vertex {
+        position: vec3,
+        u: f32,
+        uv: [vec2; 2],
+        m: mat3,
+        d: f64,
+        n: [vec3; 2],
+    }
+
+    assert!(size_of::<<[vec3; 2] as ShaderRepr<Scalar>>::Type>() == 24);
+    assert!(size_of::<<mat3 as ShaderRepr<Scalar>>::Type>() == 36);
+
+    assert!(offset_of!(VertexReprScalar, val_position) == 0);
+    assert!(offset_of!(VertexReprScalar, val_u) == 12);
+    assert!(offset_of!(VertexReprScalar, val_uv) == 16);
+    assert!(offset_of!(VertexReprScalar, val_m) == 32);
+    assert!(offset_of!(VertexReprScalar, val_d) == 72);
+    assert!(offset_of!(VertexReprScalar, val_n) == 80);
+    assert!(size_of::<VertexReprScalar>() == 104);
+};
@@ -1,5 +1,5 @@
 use {
-    super::{native::ShaderNative, pad::Padded, vec::vec, ShaderRepr, Std140, Std430},
+    super::{native::ShaderNative, pad::Padded, vec::vec, Scalar, ShaderRepr, Std140, Std430},
     bytemuck::{Pod, Zeroable},
     core::{
         convert::TryFrom,
@@ -170,6 +170,21 @@ macro_rules! impl_mats {
             }
         }
 
+        impl<T> ShaderRepr<Scalar> for mat<T, $n, $m>
+        where
+            vec<T, $m>: ShaderNative,
+        {
+            const ALIGN_MASK: usize = <vec<T, $m> as ShaderNative>::SCALAR_ALIGN_MASK;
+            const ARRAY_PADDING: usize = 0;
+
+            type Type = [Padded<vec<T, $m>, [u8; 0]>; $n];
+            type ArrayPadding = [u8; 0];
+
+            fn copy_to_repr(&self, repr: &mut Self::Type) {
+                ShaderRepr::<Scalar>::copy_to_repr(&self.0, repr)
+            }
+        }
+
         impl<T: Pod> From<[[T; $m]; $n]> for mat<T, $n, $m> {
             fn from(v: [[T; $m]; $n]) -> Self {
                 bytemuck::cast(v)
@@ -32,6 +32,15 @@ pub enum Std140 {}
 #[derive(Clone, Copy, Debug)]
 pub enum Std430 {}
 
+/// Scalar block layout.
+/// Members are aligned to their scalar components and arrays are tightly packed.
+/// Can be used for both uniforms and storage buffers
+/// if [`Feature::ScalarBlockLayout`] is enabled.
+///
+/// [`Feature::ScalarBlockLayout`]: crate::Feature::ScalarBlockLayout
+#[derive(Clone, Copy, Debug)]
+pub enum Scalar {}
+
 /// Type that can be represented in shader.
 pub trait ShaderRepr<T = Std140> {
     const ALIGN_MASK: usize;
@@ -80,3 +89,17 @@ where
         *repr = *self
     }
 }
+
+impl<T> ShaderRepr<Scalar> for T
+where
+    T: ShaderNative,
+{
+    const ALIGN_MASK: usize = <T as ShaderNative>::SCALAR_ALIGN_MASK;
+    const ARRAY_PADDING: usize = 0;
+    type Type = T;
+    type ArrayPadding = [u8; 0];
+
+    fn copy_to_repr(&self, repr: &mut T) {
+        *repr = *self
+    }
+}
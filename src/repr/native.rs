@@ -9,6 +9,10 @@ use {super::pad::Padding, bytemuck::Pod};
 pub unsafe trait ShaderNative: Pod {
     const ALIGN_MASK: usize;
 
+    /// Alignment mask in scalar block layout.
+    /// Equals to alignment mask of the scalar component.
+    const SCALAR_ALIGN_MASK: usize;
+
     const ARRAY_PADDING_140: usize;
     const ARRAY_PADDING_430: usize;
 
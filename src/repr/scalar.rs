@@ -13,6 +13,7 @@ unsafe impl Pod for boolean {}
 
 unsafe impl ShaderNative for boolean {
     const ALIGN_MASK: usize = 0;
+    const SCALAR_ALIGN_MASK: usize = 0;
     const ARRAY_PADDING_140: usize = 15;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 15];
@@ -21,6 +22,7 @@ unsafe impl ShaderNative for boolean {
 
 unsafe impl ShaderNative for i32 {
     const ALIGN_MASK: usize = 3;
+    const SCALAR_ALIGN_MASK: usize = 3;
     const ARRAY_PADDING_140: usize = 12;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 12];
@@ -29,6 +31,7 @@ unsafe impl ShaderNative for i32 {
 
 unsafe impl ShaderNative for u32 {
     const ALIGN_MASK: usize = 3;
+    const SCALAR_ALIGN_MASK: usize = 3;
     const ARRAY_PADDING_140: usize = 12;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 12];
@@ -37,6 +40,7 @@ unsafe impl ShaderNative for u32 {
 
 unsafe impl ShaderNative for f32 {
     const ALIGN_MASK: usize = 3;
+    const SCALAR_ALIGN_MASK: usize = 3;
     const ARRAY_PADDING_140: usize = 12;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 12];
@@ -45,6 +49,7 @@ unsafe impl ShaderNative for f32 {
 
 unsafe impl ShaderNative for f64 {
     const ALIGN_MASK: usize = 7;
+    const SCALAR_ALIGN_MASK: usize = 7;
     const ARRAY_PADDING_140: usize = 8;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 8];
@@ -28,6 +28,7 @@ pub type vec2<T = f32> = vec<T, 2>;
 
 unsafe impl ShaderNative for vec2<boolean> {
     const ALIGN_MASK: usize = 1;
+    const SCALAR_ALIGN_MASK: usize = 0;
     const ARRAY_PADDING_140: usize = 14;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 14];
@@ -35,6 +36,7 @@ unsafe impl ShaderNative for vec2<boolean> {
 }
 unsafe impl ShaderNative for vec2<i32> {
     const ALIGN_MASK: usize = 7;
+    const SCALAR_ALIGN_MASK: usize = 3;
     const ARRAY_PADDING_140: usize = 8;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 8];
@@ -42,6 +44,7 @@ unsafe impl ShaderNative for vec2<i32> {
 }
 unsafe impl ShaderNative for vec2<u32> {
     const ALIGN_MASK: usize = 7;
+    const SCALAR_ALIGN_MASK: usize = 3;
     const ARRAY_PADDING_140: usize = 8;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 8];
@@ -49,6 +52,7 @@ unsafe impl ShaderNative for vec2<u32> {
 }
 unsafe impl ShaderNative for vec2<f32> {
     const ALIGN_MASK: usize = 7;
+    const SCALAR_ALIGN_MASK: usize = 3;
     const ARRAY_PADDING_140: usize = 8;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 8];
@@ -56,6 +60,7 @@ unsafe impl ShaderNative for vec2<f32> {
 }
 unsafe impl ShaderNative for vec2<f64> {
     const ALIGN_MASK: usize = 15;
+    const SCALAR_ALIGN_MASK: usize = 7;
     const ARRAY_PADDING_140: usize = 0;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 0];
@@ -67,6 +72,7 @@ pub type vec3<T = f32> = vec<T, 3>;
 
 unsafe impl ShaderNative for vec3<boolean> {
     const ALIGN_MASK: usize = 3;
+    const SCALAR_ALIGN_MASK: usize = 0;
     const ARRAY_PADDING_140: usize = 13;
     const ARRAY_PADDING_430: usize = 1;
     type ArrayPadding140 = [u8; 13];
@@ -74,6 +80,7 @@ unsafe impl ShaderNative for vec3<boolean> {
 }
 unsafe impl ShaderNative for vec3<i32> {
     const ALIGN_MASK: usize = 15;
+    const SCALAR_ALIGN_MASK: usize = 3;
     const ARRAY_PADDING_140: usize = 4;
     const ARRAY_PADDING_430: usize = 4;
     type ArrayPadding140 = [u8; 4];
@@ -81,6 +88,7 @@ unsafe impl ShaderNative for vec3<i32> {
 }
 unsafe impl ShaderNative for vec3<u32> {
     const ALIGN_MASK: usize = 15;
+    const SCALAR_ALIGN_MASK: usize = 3;
     const ARRAY_PADDING_140: usize = 4;
     const ARRAY_PADDING_430: usize = 4;
     type ArrayPadding140 = [u8; 4];
@@ -88,6 +96,7 @@ unsafe impl ShaderNative for vec3<u32> {
 }
 unsafe impl ShaderNative for vec3<f32> {
     const ALIGN_MASK: usize = 15;
+    const SCALAR_ALIGN_MASK: usize = 3;
     const ARRAY_PADDING_140: usize = 4;
     const ARRAY_PADDING_430: usize = 4;
     type ArrayPadding140 = [u8; 4];
@@ -95,6 +104,7 @@ unsafe impl ShaderNative for vec3<f32> {
 }
 unsafe impl ShaderNative for vec3<f64> {
     const ALIGN_MASK: usize = 31;
+    const SCALAR_ALIGN_MASK: usize = 7;
     const ARRAY_PADDING_140: usize = 8;
     const ARRAY_PADDING_430: usize = 8;
     type ArrayPadding140 = [u8; 8];
@@ -106,6 +116,7 @@ pub type vec4<T = f32> = vec<T, 4>;
 
 unsafe impl ShaderNative for vec4<boolean> {
     const ALIGN_MASK: usize = 3;
+    const SCALAR_ALIGN_MASK: usize = 0;
     const ARRAY_PADDING_140: usize = 12;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 12];
@@ -113,6 +124,7 @@ unsafe impl ShaderNative for vec4<boolean> {
 }
 unsafe impl ShaderNative for vec4<i32> {
     const ALIGN_MASK: usize = 15;
+    const SCALAR_ALIGN_MASK: usize = 3;
     const ARRAY_PADDING_140: usize = 0;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 0];
@@ -120,6 +132,7 @@ unsafe impl ShaderNative for vec4<i32> {
 }
 unsafe impl ShaderNative for vec4<u32> {
     const ALIGN_MASK: usize = 15;
+    const SCALAR_ALIGN_MASK: usize = 3;
     const ARRAY_PADDING_140: usize = 0;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 0];
@@ -127,6 +140,7 @@ unsafe impl ShaderNative for vec4<u32> {
 }
 unsafe impl ShaderNative for vec4<f32> {
     const ALIGN_MASK: usize = 15;
+    const SCALAR_ALIGN_MASK: usize = 3;
     const ARRAY_PADDING_140: usize = 0;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 0];
@@ -134,6 +148,7 @@ unsafe impl ShaderNative for vec4<f32> {
 }
 unsafe impl ShaderNative for vec4<f64> {
     const ALIGN_MASK: usize = 31;
+    const SCALAR_ALIGN_MASK: usize = 7;
     const ARRAY_PADDING_140: usize = 0;
     const ARRAY_PADDING_430: usize = 0;
     type ArrayPadding140 = [u8; 0];
@@ -1 +1,31 @@
-pub use crate::backend::Semaphore;
+pub use crate::backend::{Semaphore, TimelineSemaphore};
+
+/// Semaphore to wait or signal in queue submission.
+#[derive(Debug)]
+pub enum SubmitSemaphore<'a> {
+    /// Binary semaphore.
+    Binary(&'a mut Semaphore),
+
+    /// Timeline semaphore with counter value to wait for or to signal.
+    /// Same semaphore may be waited and signalled in one submission.
+    Timeline(&'a TimelineSemaphore, u64),
+}
+
+impl<'a> From<&'a mut Semaphore> for SubmitSemaphore<'a> {
+    #[inline]
+    fn from(semaphore: &'a mut Semaphore) -> Self {
+        SubmitSemaphore::Binary(semaphore)
+    }
+}
+
+impl<'a> From<(&'a TimelineSemaphore, u64)> for SubmitSemaphore<'a> {
+    #[inline]
+    fn from((semaphore, value): (&'a TimelineSemaphore, u64)) -> Self {
+        SubmitSemaphore::Timeline(semaphore, value)
+    }
+}
+
+#[allow(dead_code)]
+fn check() {
+    crate::assert_object::<TimelineSemaphore>();
+}
@@ -1,10 +1,17 @@
 use std::{
     borrow::Cow,
+    collections::BTreeMap,
     convert::TryFrom,
     fmt::{self, Debug, Display},
 };
 
-use crate::{assert_error, OutOfMemory};
+use bytemuck::Pod;
+
+use crate::{
+    assert_error,
+    preprocess::{expand_includes, IncludeError, IncludeResolver, SourceMap},
+    OutOfMemory,
+};
 
 pub use crate::backend::ShaderModule;
 
@@ -23,6 +30,7 @@ bitflags::bitflags! {
         const CLOSEST_HIT               = 0b0010000000000;
         const MISS                      = 0b0100000000000;
         const INTERSECTION              = 0b1000000000000;
+        const CALLABLE                  = 0b10000000000000;
 
         const ALL_GRAPHICS              = 0b0000000011111;
     }
@@ -42,6 +50,7 @@ pub enum ShaderStage {
     ClosestHit,
     Miss,
     Intersection,
+    Callable,
 }
 
 impl Display for ShaderStage {
@@ -59,6 +68,7 @@ impl Display for ShaderStage {
             Self::ClosestHit => fmt.write_str("ClosestHit"),
             Self::Miss => fmt.write_str("Miss"),
             Self::Intersection => fmt.write_str("Intersection"),
+            Self::Callable => fmt.write_str("Callable"),
         }
     }
 }
@@ -78,10 +88,41 @@ impl ShaderStage {
             ShaderStage::ClosestHit => ShaderStageFlags::CLOSEST_HIT,
             ShaderStage::Miss => ShaderStageFlags::MISS,
             ShaderStage::Intersection => ShaderStageFlags::INTERSECTION,
+            ShaderStage::Callable => ShaderStageFlags::CALLABLE,
         }
     }
 }
 
+/// Returns naga stage for GLSL shader.
+/// Naga parses only vertex, fragment and compute GLSL shaders.
+#[cfg(feature = "glsl")]
+pub(crate) fn naga_glsl_stage(stage: ShaderStage) -> Option<naga::ShaderStage> {
+    match stage {
+        ShaderStage::Vertex => Some(naga::ShaderStage::Vertex),
+        ShaderStage::Fragment => Some(naga::ShaderStage::Fragment),
+        ShaderStage::Compute => Some(naga::ShaderStage::Compute),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "hlsl")]
+pub(crate) fn shaderc_shader_kind(stage: ShaderStage) -> shaderc::ShaderKind {
+    match stage {
+        ShaderStage::Vertex => shaderc::ShaderKind::Vertex,
+        ShaderStage::TessellationControl => shaderc::ShaderKind::TessControl,
+        ShaderStage::TessellationEvaluation => shaderc::ShaderKind::TessEvaluation,
+        ShaderStage::Geometry => shaderc::ShaderKind::Geometry,
+        ShaderStage::Fragment => shaderc::ShaderKind::Fragment,
+        ShaderStage::Compute => shaderc::ShaderKind::Compute,
+        ShaderStage::Raygen => shaderc::ShaderKind::RayGeneration,
+        ShaderStage::AnyHit => shaderc::ShaderKind::AnyHit,
+        ShaderStage::ClosestHit => shaderc::ShaderKind::ClosestHit,
+        ShaderStage::Miss => shaderc::ShaderKind::Miss,
+        ShaderStage::Intersection => shaderc::ShaderKind::Intersection,
+        ShaderStage::Callable => shaderc::ShaderKind::Callable,
+    }
+}
+
 impl From<ShaderStage> for ShaderStageFlags {
     #[inline]
     fn from(stage: ShaderStage) -> Self {
@@ -99,8 +140,9 @@ pub enum ShaderLanguage {
     GLSL { stage: ShaderStage },
 
     /// High Level Shading Language.
+    /// Compiled to SPIR-V with glslang built into the crate.
     #[cfg(feature = "hlsl")]
-    HLSL,
+    HLSL { stage: ShaderStage },
 
     /// WebGPU Shading Language.
     #[cfg(feature = "wgsl")]
@@ -116,7 +158,7 @@ impl Display for ShaderLanguage {
             #[cfg(feature = "glsl")]
             Self::GLSL { stage } => write!(fmt, "GLSL ({})", stage),
             #[cfg(feature = "hlsl")]
-            Self::HLSL => fmt.write_str("HLSL"),
+            Self::HLSL { stage } => write!(fmt, "HLSL ({})", stage),
             #[cfg(feature = "wgsl")]
             Self::WGSL => fmt.write_str("WGSL"),
             Self::SPIRV => fmt.write_str("SPIRV"),
@@ -134,6 +176,26 @@ pub struct ShaderModuleInfo {
 
     /// Source language.
     pub language: ShaderLanguage,
+
+    /// Preprocessor definitions.
+    /// Used only for GLSL and HLSL source.
+    #[cfg_attr(feature = "serde-1", serde(default))]
+    pub defines: BTreeMap<String, String>,
+
+    /// Name of the entry point function.
+    /// Used only for HLSL source, which is compiled with single entry point
+    /// that keeps this name in the module.
+    #[cfg_attr(feature = "serde-1", serde(default = "default_entry"))]
+    pub entry: Box<str>,
+
+    /// Origin of the source code with resolved includes.
+    /// Used to report errors in included files.
+    #[cfg_attr(feature = "serde-1", serde(default))]
+    pub source_map: Option<SourceMap>,
+}
+
+fn default_entry() -> Box<str> {
+    "main".into()
 }
 
 impl Debug for ShaderModuleInfo {
@@ -141,6 +203,8 @@ impl Debug for ShaderModuleInfo {
         let alternate = fmt.alternate();
         let mut ds = fmt.debug_struct("ShaderModuleInfo");
         ds.field("language", &self.language);
+        ds.field("defines", &self.defines);
+        ds.field("entry", &self.entry);
         if alternate {
             match std::str::from_utf8(&self.code) {
                 Ok(code) => ds.field("code", &code),
@@ -160,6 +224,9 @@ impl ShaderModuleInfo {
         ShaderModuleInfo {
             code: bytes.into(),
             language: ShaderLanguage::GLSL { stage },
+            defines: BTreeMap::new(),
+            entry: default_entry(),
+            source_map: None,
         }
     }
 
@@ -169,15 +236,21 @@ impl ShaderModuleInfo {
         ShaderModuleInfo {
             code: bytes.into(),
             language: ShaderLanguage::WGSL,
+            defines: BTreeMap::new(),
+            entry: default_entry(),
+            source_map: None,
         }
     }
 
     /// Creates HLSL shader module info.
     #[cfg(feature = "hlsl")]
-    pub fn hlsl(bytes: impl Into<Box<[u8]>>) -> Self {
+    pub fn hlsl(bytes: impl Into<Box<[u8]>>, stage: ShaderStage) -> Self {
         ShaderModuleInfo {
             code: bytes.into(),
-            language: ShaderLanguage::HLSL,
+            language: ShaderLanguage::HLSL { stage },
+            defines: BTreeMap::new(),
+            entry: default_entry(),
+            source_map: None,
         }
     }
 
@@ -186,8 +259,49 @@ impl ShaderModuleInfo {
         ShaderModuleInfo {
             code: bytes.into(),
             language: ShaderLanguage::SPIRV,
+            defines: BTreeMap::new(),
+            entry: default_entry(),
+            source_map: None,
         }
     }
+
+    /// Adds preprocessor definition.
+    pub fn with_define(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.defines.insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets name of the entry point function.
+    pub fn with_entry(mut self, entry: impl Into<Box<str>>) -> Self {
+        self.entry = entry.into();
+        self
+    }
+
+    /// Replaces `#include` lines in source code with files returned by `resolver`.
+    /// `name` is used for the root file in diagnostics and to resolve relative includes.
+    ///
+    /// Includes inside inactive `#if` blocks are skipped.
+    /// Only definitions added before this call and `#define`s in the source are considered.
+    pub fn resolve_includes(
+        mut self,
+        name: impl Into<String>,
+        resolver: &(impl IncludeResolver + ?Sized),
+    ) -> Result<Self, IncludeError> {
+        let code = std::str::from_utf8(&self.code)?.to_owned();
+        let (code, source_map) = expand_includes(name.into(), code, &self.defines, resolver)?;
+        self.code = code.into_bytes().into_boxed_slice();
+        self.source_map = Some(source_map);
+        Ok(self)
+    }
+
+    /// Returns definitions in form accepted by naga GLSL frontend.
+    #[cfg(feature = "glsl")]
+    pub(crate) fn naga_defines(&self) -> naga::FastHashMap<String, String> {
+        self.defines
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
 }
 
 /// Valid SPIR-V shader code.
@@ -215,6 +329,9 @@ impl From<Spirv> for ShaderModuleInfo {
         ShaderModuleInfo {
             code: shader.code,
             language: ShaderLanguage::SPIRV,
+            defines: BTreeMap::new(),
+            entry: default_entry(),
+            source_map: None,
         }
     }
 }
@@ -251,6 +368,9 @@ impl From<Glsl> for ShaderModuleInfo {
             language: ShaderLanguage::GLSL {
                 stage: shader.stage,
             },
+            defines: BTreeMap::new(),
+            entry: default_entry(),
+            source_map: None,
         }
     }
 }
@@ -263,6 +383,7 @@ impl From<Glsl> for ShaderModuleInfo {
 #[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hlsl {
     code: Box<str>,
+    stage: ShaderStage,
 }
 
 #[cfg(feature = "hlsl")]
@@ -270,9 +391,10 @@ impl Hlsl {
     /// Wraps string that must contain valid HLSL shader code.
     ///
     /// FIXME: Actually check validity.
-    pub fn new(string: impl Into<Box<str>>) -> Self {
+    pub fn new(string: impl Into<Box<str>>, stage: ShaderStage) -> Self {
         Hlsl {
             code: string.into(),
+            stage,
         }
     }
 }
@@ -282,7 +404,12 @@ impl From<Hlsl> for ShaderModuleInfo {
     fn from(shader: Hlsl) -> Self {
         ShaderModuleInfo {
             code: shader.code.into(),
-            language: ShaderLanguage::HLSL,
+            language: ShaderLanguage::HLSL {
+                stage: shader.stage,
+            },
+            defines: BTreeMap::new(),
+            entry: default_entry(),
+            source_map: None,
         }
     }
 }
@@ -315,6 +442,9 @@ impl From<Wgsl> for ShaderModuleInfo {
         ShaderModuleInfo {
             code: shader.code.into(),
             language: ShaderLanguage::WGSL,
+            defines: BTreeMap::new(),
+            entry: default_entry(),
+            source_map: None,
         }
     }
 }
@@ -331,6 +461,9 @@ pub struct Shader {
 
     /// Stage of this shader.
     pub stage: ShaderStage,
+
+    /// Values of specialization constants.
+    pub specialization: Specialization,
 }
 
 impl Shader {
@@ -340,6 +473,7 @@ impl Shader {
             module,
             entry: Cow::Borrowed("main"),
             stage,
+            specialization: Specialization::default(),
         }
     }
 
@@ -354,9 +488,137 @@ impl Shader {
     pub fn stage(&self) -> ShaderStage {
         self.stage
     }
+
+    pub fn specialization(&self) -> &Specialization {
+        &self.specialization
+    }
+
+    /// Replaces values of specialization constants.
+    pub fn with_specialization(mut self, specialization: Specialization) -> Self {
+        self.specialization = specialization;
+        self
+    }
+}
+
+/// Location of specialization constant value in [`Specialization::data`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpecializationEntry {
+    /// Specialization constant ID declared in shader.
+    pub id: u32,
+
+    /// Offset of the value in bytes.
+    pub offset: u32,
+
+    /// Size of the value in bytes.
+    pub size: usize,
 }
 
-#[derive(Clone, Copy, Debug, thiserror::Error, PartialEq, Eq)]
+/// Values of specialization constants for a shader.
+///
+/// Shaders with different specialization are different shaders
+/// and produce different pipelines.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Specialization {
+    /// Constants specialized by this object.
+    pub entries: Vec<SpecializationEntry>,
+
+    /// Values of all specialized constants.
+    pub data: Box<[u8]>,
+}
+
+impl Specialization {
+    /// Returns specialization without any constants.
+    pub fn new() -> Self {
+        Specialization::default()
+    }
+
+    /// Returns `true` if no constants are specialized.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Sets value of specialization constant with specified ID.
+    ///
+    /// Boolean constants are 32-bit in SPIR-V,
+    /// use [`Specialization::set_bool`] for them.
+    pub fn set<T>(&mut self, id: u32, value: T) -> &mut Self
+    where
+        T: Pod,
+    {
+        self.set_bytes(id, bytemuck::bytes_of(&value))
+    }
+
+    /// Sets value of boolean specialization constant with specified ID.
+    pub fn set_bool(&mut self, id: u32, value: bool) -> &mut Self {
+        self.set(id, u32::from(value))
+    }
+
+    /// Sets value of specialization constant with specified ID.
+    pub fn with<T>(mut self, id: u32, value: T) -> Self
+    where
+        T: Pod,
+    {
+        self.set(id, value);
+        self
+    }
+
+    /// Sets value of boolean specialization constant with specified ID.
+    pub fn with_bool(mut self, id: u32, value: bool) -> Self {
+        self.set_bool(id, value);
+        self
+    }
+
+    fn set_bytes(&mut self, id: u32, bytes: &[u8]) -> &mut Self {
+        let index = match self.entries.binary_search_by_key(&id, |e| e.id) {
+            Ok(index) => {
+                let entry = &self.entries[index];
+                if entry.size == bytes.len() {
+                    let offset = entry.offset as usize;
+                    self.data[offset..offset + bytes.len()].copy_from_slice(bytes);
+                    return self;
+                }
+                index
+            }
+            Err(index) => {
+                self.entries.insert(
+                    index,
+                    SpecializationEntry {
+                        id,
+                        offset: 0,
+                        size: 0,
+                    },
+                );
+                index
+            }
+        };
+
+        // Entries are kept sorted by ID and data is packed in the same order,
+        // so equal sets of constants produce equal objects.
+        let mut data = Vec::with_capacity(
+            self.entries.iter().map(|e| e.size).sum::<usize>() - self.entries[index].size
+                + bytes.len(),
+        );
+
+        for (i, entry) in self.entries.iter_mut().enumerate() {
+            let offset = u32::try_from(data.len()).expect("Too much specialization data");
+            if i == index {
+                data.extend_from_slice(bytes);
+                entry.size = bytes.len();
+            } else {
+                let old = entry.offset as usize;
+                data.extend_from_slice(&self.data[old..old + entry.size]);
+            }
+            entry.offset = offset;
+        }
+
+        self.data = data.into_boxed_slice();
+        self
+    }
+}
+
+#[derive(Clone, Debug, thiserror::Error, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
 pub enum InvalidShader {
     #[error("Source is empty")]
@@ -367,6 +629,12 @@ pub enum InvalidShader {
 
     #[error("Wrong spir-v magic. Expected 0x07230203, found 0x{found:x}")]
     WrongMagic { found: u32 },
+
+    #[error("Malformed spir-v module. {message}")]
+    MalformedSpirv { message: String },
+
+    #[error("Spir-v module failed validation. {message}")]
+    ValidationFailed { message: String },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -387,6 +655,10 @@ pub enum CreateShaderModuleError {
     #[error("Shader language {language:?} is unsupported")]
     UnsupportedShaderLanguage { language: ShaderLanguage },
 
+    #[cfg(feature = "glsl")]
+    #[error("GLSL shader stage {stage} is unsupported")]
+    UnsupportedGlslStage { stage: ShaderStage },
+
     #[error("Source code it not utf-8")]
     SourceNotUtf8 {
         #[from]
@@ -406,6 +678,10 @@ pub enum CreateShaderModuleError {
         source: Box<dyn std::error::Error + Send + Sync>,
     },
 
+    #[cfg(feature = "hlsl")]
+    #[error("Failed to compile HLSL shader. {message}")]
+    HlslCompileError { message: String },
+
     #[cfg(any(feature = "glsl", feature = "wgsl"))]
     #[error("Failed to generate SPIR-V shader code")]
     NagaSpvWriteError {
@@ -433,6 +709,7 @@ pub struct WrongShaderStage {
 pub struct VertexShader {
     module: ShaderModule,
     entry: Cow<'static, str>,
+    specialization: Specialization,
 }
 
 impl VertexShader {
@@ -440,6 +717,7 @@ impl VertexShader {
         VertexShader {
             module,
             entry: entry.into(),
+            specialization: Specialization::default(),
         }
     }
 
@@ -447,6 +725,7 @@ impl VertexShader {
         VertexShader {
             module,
             entry: Cow::Borrowed("main"),
+            specialization: Specialization::default(),
         }
     }
 
@@ -457,6 +736,16 @@ impl VertexShader {
     pub fn entry(&self) -> &str {
         &self.entry
     }
+
+    pub fn specialization(&self) -> &Specialization {
+        &self.specialization
+    }
+
+    /// Replaces values of specialization constants.
+    pub fn with_specialization(mut self, specialization: Specialization) -> Self {
+        self.specialization = specialization;
+        self
+    }
 }
 
 impl TryFrom<Shader> for VertexShader {
@@ -472,6 +761,7 @@ impl TryFrom<Shader> for VertexShader {
             Ok(VertexShader {
                 module: shader.module,
                 entry: shader.entry,
+                specialization: shader.specialization,
             })
         }
     }
@@ -482,6 +772,7 @@ impl From<VertexShader> for Shader {
         Shader {
             module: shader.module,
             entry: shader.entry,
+            specialization: shader.specialization,
             stage: ShaderStage::Vertex,
         }
     }
@@ -491,6 +782,7 @@ impl From<VertexShader> for Shader {
 pub struct TessellationControlShader {
     module: ShaderModule,
     entry: Cow<'static, str>,
+    specialization: Specialization,
 }
 
 impl TessellationControlShader {
@@ -498,6 +790,7 @@ impl TessellationControlShader {
         TessellationControlShader {
             module,
             entry: entry.into(),
+            specialization: Specialization::default(),
         }
     }
 
@@ -505,6 +798,7 @@ impl TessellationControlShader {
         TessellationControlShader {
             module,
             entry: Cow::Borrowed("main"),
+            specialization: Specialization::default(),
         }
     }
 
@@ -515,6 +809,16 @@ impl TessellationControlShader {
     pub fn entry(&self) -> &str {
         &self.entry
     }
+
+    pub fn specialization(&self) -> &Specialization {
+        &self.specialization
+    }
+
+    /// Replaces values of specialization constants.
+    pub fn with_specialization(mut self, specialization: Specialization) -> Self {
+        self.specialization = specialization;
+        self
+    }
 }
 
 impl TryFrom<Shader> for TessellationControlShader {
@@ -530,6 +834,7 @@ impl TryFrom<Shader> for TessellationControlShader {
             Ok(TessellationControlShader {
                 module: shader.module,
                 entry: shader.entry,
+                specialization: shader.specialization,
             })
         }
     }
@@ -540,6 +845,7 @@ impl From<TessellationControlShader> for Shader {
         Shader {
             module: shader.module,
             entry: shader.entry,
+            specialization: shader.specialization,
             stage: ShaderStage::TessellationControl,
         }
     }
@@ -549,6 +855,7 @@ impl From<TessellationControlShader> for Shader {
 pub struct TessellationEvaluationShader {
     module: ShaderModule,
     entry: Cow<'static, str>,
+    specialization: Specialization,
 }
 
 impl TessellationEvaluationShader {
@@ -556,6 +863,7 @@ impl TessellationEvaluationShader {
         TessellationEvaluationShader {
             module,
             entry: entry.into(),
+            specialization: Specialization::default(),
         }
     }
 
@@ -563,6 +871,7 @@ impl TessellationEvaluationShader {
         TessellationEvaluationShader {
             module,
             entry: Cow::Borrowed("main"),
+            specialization: Specialization::default(),
         }
     }
 
@@ -573,6 +882,16 @@ impl TessellationEvaluationShader {
     pub fn entry(&self) -> &str {
         &self.entry
     }
+
+    pub fn specialization(&self) -> &Specialization {
+        &self.specialization
+    }
+
+    /// Replaces values of specialization constants.
+    pub fn with_specialization(mut self, specialization: Specialization) -> Self {
+        self.specialization = specialization;
+        self
+    }
 }
 
 impl TryFrom<Shader> for TessellationEvaluationShader {
@@ -588,6 +907,7 @@ impl TryFrom<Shader> for TessellationEvaluationShader {
             Ok(TessellationEvaluationShader {
                 module: shader.module,
                 entry: shader.entry,
+                specialization: shader.specialization,
             })
         }
     }
@@ -598,6 +918,7 @@ impl From<TessellationEvaluationShader> for Shader {
         Shader {
             module: shader.module,
             entry: shader.entry,
+            specialization: shader.specialization,
             stage: ShaderStage::TessellationEvaluation,
         }
     }
@@ -607,6 +928,7 @@ impl From<TessellationEvaluationShader> for Shader {
 pub struct GeometryShader {
     module: ShaderModule,
     entry: Cow<'static, str>,
+    specialization: Specialization,
 }
 
 impl GeometryShader {
@@ -614,6 +936,7 @@ impl GeometryShader {
         GeometryShader {
             module,
             entry: entry.into(),
+            specialization: Specialization::default(),
         }
     }
 
@@ -621,6 +944,7 @@ impl GeometryShader {
         GeometryShader {
             module,
             entry: Cow::Borrowed("main"),
+            specialization: Specialization::default(),
         }
     }
 
@@ -631,6 +955,16 @@ impl GeometryShader {
     pub fn entry(&self) -> &str {
         &self.entry
     }
+
+    pub fn specialization(&self) -> &Specialization {
+        &self.specialization
+    }
+
+    /// Replaces values of specialization constants.
+    pub fn with_specialization(mut self, specialization: Specialization) -> Self {
+        self.specialization = specialization;
+        self
+    }
 }
 
 impl TryFrom<Shader> for GeometryShader {
@@ -646,6 +980,7 @@ impl TryFrom<Shader> for GeometryShader {
             Ok(GeometryShader {
                 module: shader.module,
                 entry: shader.entry,
+                specialization: shader.specialization,
             })
         }
     }
@@ -656,6 +991,7 @@ impl From<GeometryShader> for Shader {
         Shader {
             module: shader.module,
             entry: shader.entry,
+            specialization: shader.specialization,
             stage: ShaderStage::Geometry,
         }
     }
@@ -665,6 +1001,7 @@ impl From<GeometryShader> for Shader {
 pub struct FragmentShader {
     module: ShaderModule,
     entry: Cow<'static, str>,
+    specialization: Specialization,
 }
 
 impl FragmentShader {
@@ -672,6 +1009,7 @@ impl FragmentShader {
         FragmentShader {
             module,
             entry: entry.into(),
+            specialization: Specialization::default(),
         }
     }
 
@@ -679,6 +1017,7 @@ impl FragmentShader {
         FragmentShader {
             module,
             entry: Cow::Borrowed("main"),
+            specialization: Specialization::default(),
         }
     }
 
@@ -689,6 +1028,16 @@ impl FragmentShader {
     pub fn entry(&self) -> &str {
         &self.entry
     }
+
+    pub fn specialization(&self) -> &Specialization {
+        &self.specialization
+    }
+
+    /// Replaces values of specialization constants.
+    pub fn with_specialization(mut self, specialization: Specialization) -> Self {
+        self.specialization = specialization;
+        self
+    }
 }
 
 impl TryFrom<Shader> for FragmentShader {
@@ -704,6 +1053,7 @@ impl TryFrom<Shader> for FragmentShader {
             Ok(FragmentShader {
                 module: shader.module,
                 entry: shader.entry,
+                specialization: shader.specialization,
             })
         }
     }
@@ -714,6 +1064,7 @@ impl From<FragmentShader> for Shader {
         Shader {
             module: shader.module,
             entry: shader.entry,
+            specialization: shader.specialization,
             stage: ShaderStage::Fragment,
         }
     }
@@ -723,6 +1074,7 @@ impl From<FragmentShader> for Shader {
 pub struct ComputeShader {
     module: ShaderModule,
     entry: Cow<'static, str>,
+    specialization: Specialization,
 }
 
 impl ComputeShader {
@@ -730,6 +1082,7 @@ impl ComputeShader {
         ComputeShader {
             module,
             entry: entry.into(),
+            specialization: Specialization::default(),
         }
     }
 
@@ -737,6 +1090,7 @@ impl ComputeShader {
         ComputeShader {
             module,
             entry: Cow::Borrowed("main"),
+            specialization: Specialization::default(),
         }
     }
 
@@ -747,6 +1101,16 @@ impl ComputeShader {
     pub fn entry(&self) -> &str {
         &self.entry
     }
+
+    pub fn specialization(&self) -> &Specialization {
+        &self.specialization
+    }
+
+    /// Replaces values of specialization constants.
+    pub fn with_specialization(mut self, specialization: Specialization) -> Self {
+        self.specialization = specialization;
+        self
+    }
 }
 
 impl TryFrom<Shader> for ComputeShader {
@@ -762,6 +1126,7 @@ impl TryFrom<Shader> for ComputeShader {
             Ok(ComputeShader {
                 module: shader.module,
                 entry: shader.entry,
+                specialization: shader.specialization,
             })
         }
     }
@@ -772,6 +1137,7 @@ impl From<ComputeShader> for Shader {
         Shader {
             module: shader.module,
             entry: shader.entry,
+            specialization: shader.specialization,
             stage: ShaderStage::Compute,
         }
     }
@@ -781,6 +1147,7 @@ impl From<ComputeShader> for Shader {
 pub struct RaygenShader {
     module: ShaderModule,
     entry: Cow<'static, str>,
+    specialization: Specialization,
 }
 
 impl RaygenShader {
@@ -788,6 +1155,7 @@ impl RaygenShader {
         RaygenShader {
             module,
             entry: entry.into(),
+            specialization: Specialization::default(),
         }
     }
 
@@ -795,6 +1163,7 @@ impl RaygenShader {
         RaygenShader {
             module,
             entry: Cow::Borrowed("main"),
+            specialization: Specialization::default(),
         }
     }
 
@@ -805,6 +1174,16 @@ impl RaygenShader {
     pub fn entry(&self) -> &str {
         &self.entry
     }
+
+    pub fn specialization(&self) -> &Specialization {
+        &self.specialization
+    }
+
+    /// Replaces values of specialization constants.
+    pub fn with_specialization(mut self, specialization: Specialization) -> Self {
+        self.specialization = specialization;
+        self
+    }
 }
 
 impl TryFrom<Shader> for RaygenShader {
@@ -820,6 +1199,7 @@ impl TryFrom<Shader> for RaygenShader {
             Ok(RaygenShader {
                 module: shader.module,
                 entry: shader.entry,
+                specialization: shader.specialization,
             })
         }
     }
@@ -830,6 +1210,7 @@ impl From<RaygenShader> for Shader {
         Shader {
             module: shader.module,
             entry: shader.entry,
+            specialization: shader.specialization,
             stage: ShaderStage::Raygen,
         }
     }
@@ -839,6 +1220,7 @@ impl From<RaygenShader> for Shader {
 pub struct AnyHitShader {
     module: ShaderModule,
     entry: Cow<'static, str>,
+    specialization: Specialization,
 }
 
 impl AnyHitShader {
@@ -846,6 +1228,7 @@ impl AnyHitShader {
         AnyHitShader {
             module,
             entry: entry.into(),
+            specialization: Specialization::default(),
         }
     }
 
@@ -853,6 +1236,7 @@ impl AnyHitShader {
         AnyHitShader {
             module,
             entry: Cow::Borrowed("main"),
+            specialization: Specialization::default(),
         }
     }
 
@@ -863,6 +1247,16 @@ impl AnyHitShader {
     pub fn entry(&self) -> &str {
         &self.entry
     }
+
+    pub fn specialization(&self) -> &Specialization {
+        &self.specialization
+    }
+
+    /// Replaces values of specialization constants.
+    pub fn with_specialization(mut self, specialization: Specialization) -> Self {
+        self.specialization = specialization;
+        self
+    }
 }
 
 impl TryFrom<Shader> for AnyHitShader {
@@ -878,6 +1272,7 @@ impl TryFrom<Shader> for AnyHitShader {
             Ok(AnyHitShader {
                 module: shader.module,
                 entry: shader.entry,
+                specialization: shader.specialization,
             })
         }
     }
@@ -888,6 +1283,7 @@ impl From<AnyHitShader> for Shader {
         Shader {
             module: shader.module,
             entry: shader.entry,
+            specialization: shader.specialization,
             stage: ShaderStage::AnyHit,
         }
     }
@@ -897,6 +1293,7 @@ impl From<AnyHitShader> for Shader {
 pub struct ClosestHitShader {
     module: ShaderModule,
     entry: Cow<'static, str>,
+    specialization: Specialization,
 }
 
 impl ClosestHitShader {
@@ -904,6 +1301,7 @@ impl ClosestHitShader {
         ClosestHitShader {
             module,
             entry: entry.into(),
+            specialization: Specialization::default(),
         }
     }
 
@@ -911,6 +1309,7 @@ impl ClosestHitShader {
         ClosestHitShader {
             module,
             entry: Cow::Borrowed("main"),
+            specialization: Specialization::default(),
         }
     }
 
@@ -921,6 +1320,16 @@ impl ClosestHitShader {
     pub fn entry(&self) -> &str {
         &self.entry
     }
+
+    pub fn specialization(&self) -> &Specialization {
+        &self.specialization
+    }
+
+    /// Replaces values of specialization constants.
+    pub fn with_specialization(mut self, specialization: Specialization) -> Self {
+        self.specialization = specialization;
+        self
+    }
 }
 
 impl TryFrom<Shader> for ClosestHitShader {
@@ -936,6 +1345,7 @@ impl TryFrom<Shader> for ClosestHitShader {
             Ok(ClosestHitShader {
                 module: shader.module,
                 entry: shader.entry,
+                specialization: shader.specialization,
             })
         }
     }
@@ -946,6 +1356,7 @@ impl From<ClosestHitShader> for Shader {
         Shader {
             module: shader.module,
             entry: shader.entry,
+            specialization: shader.specialization,
             stage: ShaderStage::ClosestHit,
         }
     }
@@ -955,6 +1366,7 @@ impl From<ClosestHitShader> for Shader {
 pub struct MissShader {
     module: ShaderModule,
     entry: Cow<'static, str>,
+    specialization: Specialization,
 }
 
 impl MissShader {
@@ -962,6 +1374,7 @@ impl MissShader {
         MissShader {
             module,
             entry: entry.into(),
+            specialization: Specialization::default(),
         }
     }
 
@@ -969,6 +1382,7 @@ impl MissShader {
         MissShader {
             module,
             entry: Cow::Borrowed("main"),
+            specialization: Specialization::default(),
         }
     }
 
@@ -979,6 +1393,16 @@ impl MissShader {
     pub fn entry(&self) -> &str {
         &self.entry
     }
+
+    pub fn specialization(&self) -> &Specialization {
+        &self.specialization
+    }
+
+    /// Replaces values of specialization constants.
+    pub fn with_specialization(mut self, specialization: Specialization) -> Self {
+        self.specialization = specialization;
+        self
+    }
 }
 
 impl TryFrom<Shader> for MissShader {
@@ -994,6 +1418,7 @@ impl TryFrom<Shader> for MissShader {
             Ok(MissShader {
                 module: shader.module,
                 entry: shader.entry,
+                specialization: shader.specialization,
             })
         }
     }
@@ -1004,6 +1429,7 @@ impl From<MissShader> for Shader {
         Shader {
             module: shader.module,
             entry: shader.entry,
+            specialization: shader.specialization,
             stage: ShaderStage::Miss,
         }
     }
@@ -1013,6 +1439,7 @@ impl From<MissShader> for Shader {
 pub struct IntersectionShader {
     module: ShaderModule,
     entry: Cow<'static, str>,
+    specialization: Specialization,
 }
 
 impl IntersectionShader {
@@ -1020,6 +1447,7 @@ impl IntersectionShader {
         IntersectionShader {
             module,
             entry: entry.into(),
+            specialization: Specialization::default(),
         }
     }
 
@@ -1027,6 +1455,7 @@ impl IntersectionShader {
         IntersectionShader {
             module,
             entry: Cow::Borrowed("main"),
+            specialization: Specialization::default(),
         }
     }
 
@@ -1037,6 +1466,16 @@ impl IntersectionShader {
     pub fn entry(&self) -> &str {
         &self.entry
     }
+
+    pub fn specialization(&self) -> &Specialization {
+        &self.specialization
+    }
+
+    /// Replaces values of specialization constants.
+    pub fn with_specialization(mut self, specialization: Specialization) -> Self {
+        self.specialization = specialization;
+        self
+    }
 }
 
 impl TryFrom<Shader> for IntersectionShader {
@@ -1052,6 +1491,7 @@ impl TryFrom<Shader> for IntersectionShader {
             Ok(IntersectionShader {
                 module: shader.module,
                 entry: shader.entry,
+                specialization: shader.specialization,
             })
         }
     }
@@ -1062,13 +1502,125 @@ impl From<IntersectionShader> for Shader {
         Shader {
             module: shader.module,
             entry: shader.entry,
+            specialization: shader.specialization,
             stage: ShaderStage::Intersection,
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CallableShader {
+    module: ShaderModule,
+    entry: Cow<'static, str>,
+    specialization: Specialization,
+}
+
+impl CallableShader {
+    pub fn new(module: ShaderModule, entry: impl Into<Cow<'static, str>>) -> Self {
+        CallableShader {
+            module,
+            entry: entry.into(),
+            specialization: Specialization::default(),
+        }
+    }
+
+    pub fn with_main(module: ShaderModule) -> Self {
+        CallableShader {
+            module,
+            entry: Cow::Borrowed("main"),
+            specialization: Specialization::default(),
+        }
+    }
+
+    pub fn module(&self) -> &ShaderModule {
+        &self.module
+    }
+
+    pub fn entry(&self) -> &str {
+        &self.entry
+    }
+
+    pub fn specialization(&self) -> &Specialization {
+        &self.specialization
+    }
+
+    /// Replaces values of specialization constants.
+    pub fn with_specialization(mut self, specialization: Specialization) -> Self {
+        self.specialization = specialization;
+        self
+    }
+}
+
+impl TryFrom<Shader> for CallableShader {
+    type Error = WrongShaderStage;
+
+    fn try_from(shader: Shader) -> Result<Self, WrongShaderStage> {
+        if shader.stage != ShaderStage::Callable {
+            Err(WrongShaderStage {
+                actual: shader.stage,
+                expected: ShaderStage::Callable,
+            })
+        } else {
+            Ok(CallableShader {
+                module: shader.module,
+                entry: shader.entry,
+                specialization: shader.specialization,
+            })
+        }
+    }
+}
+
+impl From<CallableShader> for Shader {
+    fn from(shader: CallableShader) -> Shader {
+        Shader {
+            module: shader.module,
+            entry: shader.entry,
+            specialization: shader.specialization,
+            stage: ShaderStage::Callable,
+        }
+    }
+}
+
 #[allow(dead_code)]
 fn check_create_shader_module_error() {
     assert_error::<InvalidShader>();
     assert_error::<CreateShaderModuleError>();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Specialization;
+
+    #[test]
+    fn specialization_is_order_independent() {
+        let a = Specialization::new()
+            .with(1, 1u32)
+            .with(0, 2u16)
+            .with_bool(2, true);
+        let b = Specialization::new()
+            .with_bool(2, true)
+            .with(1, 1u32)
+            .with(0, 2u16);
+
+        assert_eq!(a, b);
+        assert_eq!(
+            a.entries.iter().map(|e| e.id).collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn specialization_resize_keeps_data_tight() {
+        let mut spec = Specialization::new().with(0, 1u32).with(1, 2u32);
+        spec.set(0, 3u64);
+
+        assert_eq!(spec.data.len(), 12);
+        assert_eq!(spec.entries[0].offset, 0);
+        assert_eq!(spec.entries[0].size, 8);
+        assert_eq!(spec.entries[1].offset, 8);
+        assert_eq!(&spec.data[8..12], 2u32.to_ne_bytes());
+
+        spec.set(0, 4u32);
+        assert_eq!(spec, Specialization::new().with(0, 4u32).with(1, 2u32));
+    }
+}
@@ -31,6 +31,12 @@ pub enum SurfaceError {
     #[error("Format {{{format:?}}} is not supported for surface images")]
     FormatUnsupported { format: Format },
 
+    #[error("Color space {{{color_space:?}}} is not supported for surface images of format {{{format:?}}}")]
+    ColorSpaceUnsupported {
+        format: Format,
+        color_space: ColorSpace,
+    },
+
     #[error("Presentation mode {{{mode:?}}} is not supported for surface images")]
     PresentModeUnsupported { mode: PresentMode },
 
@@ -48,6 +54,9 @@ pub enum SurfaceError {
 
     #[error("Surface not configured")]
     NotConfigured,
+
+    #[error("Operation is not supported by emulated surface")]
+    Emulated,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -222,6 +231,67 @@ pub enum PresentMode {
     FifoRelaxed,
 }
 
+/// Color space in which presentation engine interprets surface images.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ColorSpace {
+    /// sRGB primaries with sRGB transfer function.
+    /// Supported by every surface.
+    SrgbNonlinear,
+
+    /// scRGB. sRGB primaries with linear transfer function.
+    /// Values outside of `[0, 1]` range are allowed.
+    ExtendedSrgbLinear,
+
+    /// sRGB primaries with sRGB transfer function.
+    /// Values outside of `[0, 1]` range are allowed.
+    ExtendedSrgbNonlinear,
+
+    /// Display-P3 primaries with sRGB transfer function.
+    DisplayP3Nonlinear,
+
+    /// BT.2020 primaries with SMPTE ST 2084 (PQ) transfer function.
+    Hdr10St2084,
+}
+
+impl ColorSpace {
+    /// Returns true if color space can represent high dynamic range.
+    pub fn is_hdr(&self) -> bool {
+        matches!(
+            self,
+            ColorSpace::ExtendedSrgbLinear
+                | ColorSpace::ExtendedSrgbNonlinear
+                | ColorSpace::Hdr10St2084
+        )
+    }
+}
+
+/// Chromaticity coordinates in CIE 1931 color space.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chromaticity {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// HDR metadata of the mastering display and content.
+/// Passed to presentation engine to help it map content to the actual display.
+///
+/// Luminance values are in nits.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct HdrMetadata {
+    pub display_primary_red: Chromaticity,
+    pub display_primary_green: Chromaticity,
+    pub display_primary_blue: Chromaticity,
+    pub white_point: Chromaticity,
+    pub max_luminance: f32,
+    pub min_luminance: f32,
+    pub max_content_light_level: f32,
+    pub max_frame_average_light_level: f32,
+}
+
 bitflags::bitflags! {
     #[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
     pub struct CompositeAlphaFlags: u32 {
@@ -259,21 +329,43 @@ pub struct SurfaceCapabilities {
     pub max_image_extent: Extent2,
     pub supported_usage: ImageUsage,
     pub present_modes: Vec<PresentMode>,
-    pub formats: Vec<Format>,
+    pub formats: Vec<(Format, ColorSpace)>,
     pub supported_composite_alpha: CompositeAlphaFlags,
 }
 
 impl SurfaceCapabilities {
+    /// Returns true if surface supports format in specified color space.
+    pub fn supports_format(&self, format: Format, color_space: ColorSpace) -> bool {
+        self.formats.contains(&(format, color_space))
+    }
+
+    /// Returns preferred 8-bit format in [`ColorSpace::SrgbNonlinear`].
     pub fn get_basic_rendering_format(&self) -> Option<Format> {
         self.formats
             .iter()
-            .filter_min_by_key(|f| match f {
+            .filter(|(_, cs)| *cs == ColorSpace::SrgbNonlinear)
+            .filter_min_by_key(|(f, _)| match f {
                 Format::RGB8Srgb | Format::BGR8Srgb => Some(0),
                 Format::RGB8Unorm | Format::BGR8Unorm => Some(1),
                 Format::RGBA8Srgb | Format::BGRA8Srgb => Some(2),
                 Format::RGBA8Unorm | Format::BGRA8Unorm => Some(3),
                 _ => None,
             })
+            .map(|(f, _)| *f)
+    }
+
+    /// Returns preferred format and color space for HDR output.
+    pub fn get_hdr_rendering_format(&self) -> Option<(Format, ColorSpace)> {
+        self.formats
+            .iter()
+            .filter_min_by_key(|(f, cs)| match (f, cs) {
+                (Format::A2B10G10R10Unorm | Format::A2R10G10B10Unorm, ColorSpace::Hdr10St2084) => {
+                    Some(0)
+                }
+                (Format::RGBA16Sfloat, ColorSpace::ExtendedSrgbLinear) => Some(1),
+                (Format::RGBA16Sfloat, ColorSpace::ExtendedSrgbNonlinear) => Some(2),
+                _ => None,
+            })
             .copied()
     }
 
@@ -291,9 +383,30 @@ impl SurfaceCapabilities {
 }
 
 #[derive(Clone, Copy, Debug)]
-pub struct SurfaceInfo {
-    pub window: RawWindowHandle,
-    pub display: RawDisplayHandle,
+pub enum SurfaceInfo {
+    /// Surface of the native window.
+    Window {
+        window: RawWindowHandle,
+        display: RawDisplayHandle,
+    },
+
+    /// Surface without window.
+    Headless(HeadlessSurfaceInfo),
+}
+
+/// Parameters of surface not backed by any window.
+///
+/// Uses `VK_EXT_headless_surface` when available.
+/// Otherwise swapchain is emulated with a ring of images.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeadlessSurfaceInfo {
+    /// Extent of surface images.
+    pub extent: Extent2,
+
+    /// Forces emulated swapchain so that presented images
+    /// can be read back with [`Surface::presented_image`].
+    pub readback: bool,
 }
 
 unsafe impl Send for SurfaceInfo {}
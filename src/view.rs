@@ -3,7 +3,7 @@ use crate::{
     access::Access,
     backend::Device,
     encode::Encoder,
-    image::{Image, ImageExtent, ImageMemoryBarrier, Layout, SubresourceRange},
+    image::{Image, ImageExtent, ImageInfo, ImageMemoryBarrier, Layout, SubresourceRange},
     queue::{Ownership, QueueId},
     sealed::Sealed,
     stage::PipelineStages,
@@ -29,6 +29,50 @@ pub enum ImageViewKind {
     /// resulting in sample at intersection of cube and
     /// a ray with origin in center of cube and direction of that vector
     Cube,
+
+    /// Array of one dimensional views.
+    D1Array,
+
+    /// Array of two dimensional views.
+    D2Array,
+
+    /// Array of cube views.
+    /// Each 6 consecutive image layers are treated as sides of a cube.
+    ///
+    /// Requires [`Feature::ImageCubeArray`].
+    ///
+    /// [`Feature::ImageCubeArray`]: crate::Feature::ImageCubeArray
+    CubeArray,
+}
+
+impl ImageViewKind {
+    /// Returns true if view kind is an array.
+    pub fn is_array(&self) -> bool {
+        matches!(self, Self::D1Array | Self::D2Array | Self::CubeArray)
+    }
+
+    /// Checks that number of layers is valid for this view kind.
+    pub fn is_valid_layer_count(&self, layers: u32) -> bool {
+        match self {
+            Self::D1 | Self::D2 | Self::D3 => layers == 1,
+            Self::Cube => layers == 6,
+            Self::D1Array | Self::D2Array => layers > 0,
+            Self::CubeArray => layers > 0 && layers.is_multiple_of(6),
+        }
+    }
+
+    /// Checks that view of this kind can be created for image with specified extent.
+    pub fn is_compatible_with(&self, extent: ImageExtent) -> bool {
+        matches!(
+            (self, extent),
+            (Self::D1 | Self::D1Array, ImageExtent::D1 { .. })
+                | (
+                    Self::D2 | Self::D2Array | Self::Cube | Self::CubeArray,
+                    ImageExtent::D2 { .. }
+                )
+                | (Self::D3, ImageExtent::D3 { .. })
+        )
+    }
 }
 
 /// Defines remapping of a color component.
@@ -95,15 +139,13 @@ pub struct ImageViewInfo {
 }
 
 impl ImageViewInfo {
+    /// Returns info for the view of the whole image.
+    /// Array view kind is chosen for images with multiple layers.
     pub fn new(image: Image) -> Self {
         let info = image.info();
 
         ImageViewInfo {
-            view_kind: match info.extent {
-                ImageExtent::D1 { .. } => ImageViewKind::D1,
-                ImageExtent::D2 { .. } => ImageViewKind::D2,
-                ImageExtent::D3 { .. } => ImageViewKind::D3,
-            },
+            view_kind: whole_view_kind(info),
             range: SubresourceRange::new(
                 info.format.aspect_flags(),
                 0..info.levels,
@@ -134,14 +176,17 @@ impl ImageViewInfo {
             return false;
         }
 
-        match (info.extent, self.view_kind) {
-            (ImageExtent::D1 { .. }, ImageViewKind::D1) => {}
-            (ImageExtent::D2 { .. }, ImageViewKind::D2) => {}
-            (ImageExtent::D3 { .. }, ImageViewKind::D3) => {}
-            _ => return false,
-        }
+        self.view_kind == whole_view_kind(info)
+    }
+}
 
-        true
+fn whole_view_kind(info: &ImageInfo) -> ImageViewKind {
+    match info.extent {
+        ImageExtent::D1 { .. } if info.layers > 1 => ImageViewKind::D1Array,
+        ImageExtent::D1 { .. } => ImageViewKind::D1,
+        ImageExtent::D2 { .. } if info.layers > 1 => ImageViewKind::D2Array,
+        ImageExtent::D2 { .. } => ImageViewKind::D2,
+        ImageExtent::D3 { .. } => ImageViewKind::D3,
     }
 }
 
@@ -0,0 +1,469 @@
+use {
+    crate::repr::*,
+    std::{any::TypeId, collections::HashSet},
+};
+
+/// Collects WGSL definitions of types, each one only once
+/// and after definitions of types it depends on.
+#[derive(Debug, Default)]
+pub struct WgslTypeContext {
+    generated: HashSet<TypeId>,
+    code: String,
+}
+
+impl WgslTypeContext {
+    pub fn new() -> Self {
+        WgslTypeContext::default()
+    }
+
+    /// Adds definition of the type and its dependencies.
+    pub fn add<T>(&mut self)
+    where
+        T: WgslType + ?Sized,
+    {
+        if self.generated.insert(TypeId::of::<T>()) {
+            T::deps(self);
+            self.code += &T::def();
+        }
+    }
+
+    /// Adds definition identified by `id` unless it was already added.
+    fn add_def(&mut self, id: TypeId, def: impl FnOnce() -> String) {
+        if self.generated.insert(id) {
+            self.code += &def();
+        }
+    }
+
+    /// Returns collected definitions.
+    pub fn code(self) -> String {
+        self.code
+    }
+}
+
+/// Generates declarations for struct in wgsl shaders.
+pub trait WgslType: 'static {
+    /// Name of the type in WGSL.
+    fn name() -> String;
+
+    /// Adds definitions of types this type depends on.
+    fn deps(_ctx: &mut WgslTypeContext) {}
+
+    /// Definition of the type. Empty for builtin types.
+    fn def() -> String {
+        String::new()
+    }
+
+    /// Declaration of variable or member with this type.
+    fn declare(name: &str) -> String {
+        format!("{}: {}", name, Self::name())
+    }
+
+    /// Alignment and size of the type in uniform buffer.
+    fn uniform_layout() -> (usize, usize);
+
+    /// Name of the type in uniform buffer.
+    ///
+    /// Uniform buffers require array stride to be multiple of 16,
+    /// so elements of other arrays are wrapped into padded struct
+    /// matching std140 layout.
+    fn uniform_name() -> String {
+        Self::name()
+    }
+
+    /// Adds definition of the type and its dependencies for use in uniform buffer.
+    fn uniform_deps(ctx: &mut WgslTypeContext) {
+        ctx.add::<Self>()
+    }
+
+    /// Declaration of uniform buffer member with this type.
+    fn declare_uniform(name: &str) -> String {
+        format!("{}: {}", name, Self::uniform_name())
+    }
+
+    /// Name of the type in struct with scalar block layout.
+    ///
+    /// WGSL aligns vectors and matrices to more than their components,
+    /// so they are declared as arrays of scalars to keep scalar offsets and strides.
+    fn scalar_name() -> String {
+        Self::name()
+    }
+
+    /// Adds definition of the type and its dependencies for use in struct with scalar block layout.
+    fn scalar_deps(ctx: &mut WgslTypeContext) {
+        ctx.add::<Self>()
+    }
+
+    /// Declaration of member with this type in struct with scalar block layout.
+    fn declare_scalar(name: &str) -> String {
+        format!("{}: {}", name, Self::scalar_name())
+    }
+}
+
+/// Rounds `size` up to multiple of `align`.
+pub const fn wgsl_round_up(align: usize, size: usize) -> usize {
+    size.div_ceil(align) * align
+}
+
+/// Returns alignment and size of struct with members of specified layouts.
+pub fn wgsl_struct_layout(members: impl IntoIterator<Item = (usize, usize)>) -> (usize, usize) {
+    let mut align = 1;
+    let mut offset = 0;
+
+    for (member_align, member_size) in members {
+        align = align.max(member_align);
+        offset = wgsl_round_up(member_align, offset) + member_size;
+    }
+
+    (align, wgsl_round_up(align, offset))
+}
+
+/// Element of array in uniform buffer padded to 16 byte stride.
+struct UniformElement<T: ?Sized>(std::marker::PhantomData<T>);
+
+/// Returns stride of array elements of type `T` in uniform buffer
+/// and whether elements must be wrapped to get it.
+fn uniform_stride<T>() -> (usize, bool)
+where
+    T: WgslType,
+{
+    let (align, size) = T::uniform_layout();
+    let stride = wgsl_round_up(align, size);
+    (wgsl_round_up(16, stride), !stride.is_multiple_of(16))
+}
+
+/// Name of padded wrapper of array elements in uniform buffer.
+fn uniform_element_name<T>() -> String
+where
+    T: WgslType,
+{
+    let name = T::uniform_name()
+        .chars()
+        .filter_map(|c| match c {
+            '<' | ',' => Some('_'),
+            '>' | ' ' => None,
+            c => Some(c),
+        })
+        .collect::<String>();
+
+    format!("Std140_{}", name)
+}
+
+fn uniform_element_deps<T>(ctx: &mut WgslTypeContext)
+where
+    T: WgslType,
+{
+    T::uniform_deps(ctx);
+
+    let (stride, wrap) = uniform_stride::<T>();
+    if wrap {
+        ctx.add_def(TypeId::of::<UniformElement<T>>(), || {
+            format!(
+                "struct {} {{\n    @size({}) value: {},\n}}\n",
+                uniform_element_name::<T>(),
+                stride,
+                T::uniform_name(),
+            )
+        });
+    }
+}
+
+fn uniform_element_type<T>() -> String
+where
+    T: WgslType,
+{
+    if uniform_stride::<T>().1 {
+        uniform_element_name::<T>()
+    } else {
+        T::uniform_name()
+    }
+}
+
+macro_rules! builtin_wgsl_type {
+    ($($ty:ty as $name:literal),* $(,)?) => {
+        $(
+            impl WgslType for $ty {
+                fn name() -> String {
+                    $name.to_owned()
+                }
+
+                fn uniform_layout() -> (usize, usize) {
+                    (4, 4)
+                }
+            }
+        )*
+    };
+}
+
+// `bool` is not host-shareable in WGSL.
+builtin_wgsl_type!(f32 as "f32", i32 as "i32", u32 as "u32", boolean as "u32");
+
+impl<T> WgslType for [T]
+where
+    T: WgslType,
+{
+    fn name() -> String {
+        format!("array<{}>", T::name())
+    }
+
+    fn deps(ctx: &mut WgslTypeContext) {
+        ctx.add::<T>()
+    }
+
+    fn uniform_layout() -> (usize, usize) {
+        let (align, _) = T::uniform_layout();
+        (wgsl_round_up(16, align), uniform_stride::<T>().0)
+    }
+
+    fn uniform_name() -> String {
+        format!("array<{}>", uniform_element_type::<T>())
+    }
+
+    fn uniform_deps(ctx: &mut WgslTypeContext) {
+        uniform_element_deps::<T>(ctx)
+    }
+
+    fn scalar_name() -> String {
+        format!("array<{}>", T::scalar_name())
+    }
+
+    fn scalar_deps(ctx: &mut WgslTypeContext) {
+        T::scalar_deps(ctx)
+    }
+}
+
+impl<T, const N: usize> WgslType for [T; N]
+where
+    T: WgslType,
+{
+    fn name() -> String {
+        format!("array<{}, {}>", T::name(), N)
+    }
+
+    fn deps(ctx: &mut WgslTypeContext) {
+        ctx.add::<T>()
+    }
+
+    fn uniform_layout() -> (usize, usize) {
+        let (align, _) = T::uniform_layout();
+        (wgsl_round_up(16, align), uniform_stride::<T>().0 * N)
+    }
+
+    fn uniform_name() -> String {
+        format!("array<{}, {}>", uniform_element_type::<T>(), N)
+    }
+
+    fn uniform_deps(ctx: &mut WgslTypeContext) {
+        uniform_element_deps::<T>(ctx)
+    }
+
+    fn scalar_name() -> String {
+        format!("array<{}, {}>", T::scalar_name(), N)
+    }
+
+    fn scalar_deps(ctx: &mut WgslTypeContext) {
+        T::scalar_deps(ctx)
+    }
+}
+
+macro_rules! builtin_wgsl_vec {
+    ($($t:ty as $s:literal),+ $(,)?) => {$(
+        builtin_wgsl_vec!(@ $t as $s: vec2 2, vec3 3, vec4 4);
+    )+};
+    (@ $t:ty as $s:literal: $($v:ident $n:tt),+) => {$(
+        impl WgslType for $v<$t> {
+            fn name() -> String {
+                std::concat!("vec", $n, "<", $s, ">").to_owned()
+            }
+
+            fn uniform_layout() -> (usize, usize) {
+                vec_layout($n)
+            }
+
+            fn scalar_name() -> String {
+                std::concat!("array<", $s, ", ", $n, ">").to_owned()
+            }
+        }
+    )+};
+}
+
+/// Alignment and size of vector of 32-bit scalars.
+const fn vec_layout(n: usize) -> (usize, usize) {
+    match n {
+        2 => (8, 8),
+        3 => (16, 12),
+        _ => (16, 16),
+    }
+}
+
+builtin_wgsl_vec!(f32 as "f32", i32 as "i32", u32 as "u32", boolean as "u32");
+
+/// WGSL has no 64-bit floats.
+/// Declarations with these types panic when generated.
+macro_rules! unsupported_wgsl_type {
+    ($($ty:ty),* $(,)?) => {$(
+        impl WgslType for $ty {
+            fn name() -> String {
+                panic!("`f64` is not supported in WGSL")
+            }
+
+            fn uniform_layout() -> (usize, usize) {
+                panic!("`f64` is not supported in WGSL")
+            }
+        }
+    )*};
+}
+
+unsupported_wgsl_type!(
+    f64,
+    vec2<f64>,
+    vec3<f64>,
+    vec4<f64>,
+    mat2x2<f64>,
+    mat3x2<f64>,
+    mat4x2<f64>,
+    mat2x3<f64>,
+    mat3x3<f64>,
+    mat4x3<f64>,
+    mat2x4<f64>,
+    mat3x4<f64>,
+    mat4x4<f64>,
+);
+
+macro_rules! builtin_wgsl_mat {
+    ($($t:ty as $s:literal),+ $(,)?) => {$(
+        builtin_wgsl_mat!(@ $t as $s:
+            mat2x2 2 2, mat3x2 3 2, mat4x2 4 2,
+            mat2x3 2 3, mat3x3 3 3, mat4x3 4 3,
+            mat2x4 2 4, mat3x4 3 4, mat4x4 4 4
+        );
+    )+};
+    (@ $t:ty as $s:literal: $($m:ident $c:tt $r:tt),+) => {$(
+        impl WgslType for $m<$t> {
+            fn name() -> String {
+                std::concat!("mat", $c, "x", $r, "<", $s, ">").to_owned()
+            }
+
+            fn uniform_layout() -> (usize, usize) {
+                let (align, size) = vec_layout($r);
+                (align, $c * wgsl_round_up(align, size))
+            }
+
+            fn scalar_name() -> String {
+                std::concat!("array<array<", $s, ", ", $r, ">, ", $c, ">").to_owned()
+            }
+        }
+    )+};
+}
+
+builtin_wgsl_mat!(f32 as "f32");
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        repr::{mat3, vec3},
+        shader::ShaderStage,
+        ShaderInclude,
+    };
+
+    #[derive(crate::ShaderRepr)]
+    #[sierra(scalar)]
+    struct Packed {
+        position: vec3,
+        transform: mat3,
+        points: [vec3; 2],
+        weight: f32,
+    }
+
+    #[derive(crate::ShaderRepr)]
+    #[sierra(std140)]
+    struct Light {
+        color: vec3,
+        intensity: f32,
+    }
+
+    #[allow(dead_code)]
+    #[derive(crate::Descriptors)]
+    struct Material {
+        #[sierra(sampler, fragment)]
+        linear: crate::Sampler,
+
+        #[sierra(image(sampled), fragment)]
+        albedo: crate::ImageView,
+
+        #[sierra(uniform, stages(fragment))]
+        light: Light,
+    }
+
+    #[allow(dead_code)]
+    #[derive(crate::Descriptors)]
+    struct Scene {
+        #[sierra(uniform, stages(fragment))]
+        exposure: f32,
+    }
+
+    #[allow(dead_code)]
+    #[derive(crate::PipelineInput)]
+    struct TwoSets {
+        #[sierra(set)]
+        material: Material,
+
+        #[sierra(set)]
+        scene: Scene,
+    }
+
+    #[test]
+    fn two_sets_declarations() {
+        assert_eq!(
+            TwoSets::wgsl(ShaderStage::Fragment),
+            "struct Light {\n    color: vec3<f32>,\n    intensity: f32,\n}\n\
+             @group(0) @binding(0) var linear: sampler;\n\
+             @group(0) @binding(1) var albedo: texture_2d<f32>;\n\
+             struct MaterialUniforms {\n    light: Light,\n}\n\
+             @group(0) @binding(2) var<uniform> material_uniforms: MaterialUniforms;\n\
+             struct SceneUniforms {\n    exposure: f32,\n}\n\
+             @group(1) @binding(0) var<uniform> scene_uniforms: SceneUniforms;\n",
+        );
+    }
+
+    #[cfg(feature = "wgsl")]
+    #[test]
+    fn two_sets_declarations_parse() {
+        naga::front::wgsl::parse_str(&TwoSets::wgsl(ShaderStage::Fragment)).unwrap();
+    }
+
+    #[test]
+    fn scalar_struct_uses_scalar_arrays() {
+        assert_eq!(
+            Packed::wgsl(ShaderStage::Compute),
+            "struct Packed {\n    position: array<f32, 3>,\n    transform: array<array<f32, 3>, 3>,\n    points: array<array<f32, 3>, 2>,\n    weight: f32,\n}\n",
+        );
+    }
+
+    #[cfg(feature = "wgsl")]
+    #[test]
+    fn scalar_struct_offsets_match_rust() {
+        use crate::repr::{Scalar, ShaderRepr};
+
+        let code = format!(
+            "{}@group(0) @binding(0) var<storage> packed: Packed;\n",
+            Packed::wgsl(ShaderStage::Compute),
+        );
+        let module = naga::front::wgsl::parse_str(&code).unwrap();
+
+        let (members, span) = module
+            .types
+            .iter()
+            .find_map(|(_, ty)| match &ty.inner {
+                naga::TypeInner::Struct { members, span } => Some((members, *span)),
+                _ => None,
+            })
+            .unwrap();
+
+        let offsets = members.iter().map(|m| m.offset).collect::<Vec<_>>();
+        assert_eq!(offsets, [0, 12, 48, 72]);
+        assert_eq!(
+            span as usize,
+            std::mem::size_of::<<Packed as ShaderRepr<Scalar>>::Type>(),
+        );
+    }
+}